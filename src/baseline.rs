@@ -0,0 +1,161 @@
+//! Baseline of accepted findings, so checks on a legacy codebase fail only on
+//! new violations and the accepted set can be ratcheted down over time.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// `--baseline`, `--update-baseline` and `--allow-baseline-growth`.
+#[derive(Debug, Clone, Default)]
+pub struct BaselineOptions {
+    pub path: Option<PathBuf>,
+    /// Rewrite the baseline with the current findings instead of failing.
+    pub update: bool,
+    /// Let `update` add findings that the existing baseline does not contain.
+    pub allow_growth: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineFile {
+    findings: Vec<String>,
+}
+
+/// Known findings, plus the findings seen during this run.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    options: BaselineOptions,
+    existed: bool,
+    known: BTreeSet<String>,
+    current: BTreeSet<String>,
+    /// Key prefixes of checks that did not run.
+    skipped: Vec<String>,
+}
+
+impl Baseline {
+    /// Loads the baseline file, if one is configured. A missing file is only
+    /// accepted when it is about to be created with `update`.
+    pub fn open(options: &BaselineOptions) -> Result<Self, String> {
+        let mut baseline = Self {
+            options: options.clone(),
+            ..Self::default()
+        };
+        let Some(path) = &options.path else {
+            return Ok(baseline);
+        };
+        if !path.exists() {
+            if options.update {
+                return Ok(baseline);
+            }
+            return Err(format!(
+                "Baseline {} not found; create it with --update-baseline",
+                path.display()
+            ));
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let file: BaselineFile = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid baseline {}: {e}", path.display()))?;
+        baseline.existed = true;
+        baseline.known = file.findings.into_iter().collect();
+        Ok(baseline)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.options.path.is_some()
+    }
+
+    /// Records each item's key and keeps only items not already in the baseline.
+    pub fn retain_new<T>(&mut self, items: Vec<T>, key: impl Fn(&T) -> String) -> Vec<T> {
+        if !self.is_enabled() {
+            return items;
+        }
+        items
+            .into_iter()
+            .filter(|item| {
+                let key = key(item);
+                let is_new = !self.known.contains(&key);
+                self.current.insert(key);
+                is_new
+            })
+            .collect()
+    }
+
+    /// Marks the findings whose key starts with `category` as not checked in
+    /// this run: known ones are kept as they are rather than counted fixed.
+    pub fn skip(&mut self, category: &str) {
+        self.skipped.push(category.to_string());
+    }
+
+    fn is_skipped(&self, key: &str) -> bool {
+        self.skipped.iter().any(|c| key.starts_with(c.as_str()))
+    }
+
+    /// Baselined findings that no longer occur.
+    pub fn fixed(&self) -> Vec<&str> {
+        self.known
+            .difference(&self.current)
+            .filter(|key| !self.is_skipped(key))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Current findings that the baseline does not cover.
+    pub fn added(&self) -> Vec<&str> {
+        self.current
+            .difference(&self.known)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Prints fixed findings, then either rewrites the baseline (`update`) or
+    /// turns `failures` into an error.
+    pub fn finish(&self, failures: &[String]) -> Result<(), String> {
+        for key in self.fixed() {
+            println!("* Fixed since baseline: {key}");
+        }
+        if self.options.update {
+            return self.save();
+        }
+        if !self.fixed().is_empty() {
+            println!("* Run with --update-baseline to remove fixed findings from the baseline");
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join(", "))
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.options.path else {
+            return Err("--update-baseline requires --baseline <file>".to_string());
+        };
+        let added = self.added();
+        if self.existed && !added.is_empty() && !self.options.allow_growth {
+            for key in &added {
+                println!("X Not in baseline: {key}");
+            }
+            return Err(format!(
+                "Refusing to grow baseline by {} findings; fix them or pass --allow-baseline-growth",
+                added.len()
+            ));
+        }
+        let kept = self.known.iter().filter(|key| self.is_skipped(key));
+        let findings: BTreeSet<String> = self.current.iter().chain(kept).cloned().collect();
+        let file = BaselineFile {
+            findings: findings.into_iter().collect(),
+        };
+        let json = serde_json::to_string_pretty(&file).map_err(|e| format!("JSON error: {e}"))?;
+        fs::write(path, json + "\n")
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+        println!(
+            "* Baseline {} updated: {} findings ({} fixed, {} added)",
+            path.display(),
+            file.findings.len(),
+            self.fixed().len(),
+            added.len()
+        );
+        Ok(())
+    }
+}
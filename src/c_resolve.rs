@@ -0,0 +1,150 @@
+//! Resolves C/C++ `#include` directives using include paths and `compile_commands.json`.
+
+use crate::lang_c::{self, Include};
+use crate::path_utils;
+use crate::types::DepKind;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Locations searched for a compilation database, relative to the root.
+const COMPILE_DB_LOCATIONS: [&str; 2] = ["compile_commands.json", "build/compile_commands.json"];
+
+/// Include search state: configured directories plus per-file `-I` flags.
+#[derive(Debug, Clone, Default)]
+pub struct IncludeResolver {
+    root: PathBuf,
+    include_dirs: Vec<String>,
+    per_file: HashMap<String, Vec<String>>,
+}
+
+impl IncludeResolver {
+    /// Builds a resolver from configured include paths (relative to `root`)
+    /// and any `compile_commands.json` found in the project.
+    pub fn load(root: &Path, include_paths: &[PathBuf]) -> Self {
+        let canonical_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let mut resolver = Self {
+            root: root.to_path_buf(),
+            include_dirs: include_paths
+                .iter()
+                .filter_map(|p| {
+                    if p.is_absolute() {
+                        relative_to(&canonical_root, p)
+                    } else {
+                        path_utils::normalize_path(&p.to_string_lossy())
+                    }
+                })
+                .collect(),
+            per_file: HashMap::new(),
+        };
+        if let Some(db) = COMPILE_DB_LOCATIONS
+            .iter()
+            .find_map(|loc| fs::read_to_string(root.join(loc)).ok())
+        {
+            resolver.load_compile_db(&db);
+        }
+        resolver
+    }
+
+    fn load_compile_db(&mut self, db: &str) {
+        let Ok(Value::Array(entries)) = serde_json::from_str::<Value>(db) else {
+            return;
+        };
+        let canonical_root = self
+            .root
+            .canonicalize()
+            .unwrap_or_else(|_| self.root.clone());
+        for entry in &entries {
+            let Some(dir) = entry.get("directory").and_then(Value::as_str) else {
+                continue;
+            };
+            let dirs: Vec<String> = include_flags(entry)
+                .iter()
+                .filter_map(|flag| relative_to(&canonical_root, &Path::new(dir).join(flag)))
+                .collect();
+            for dir_entry in &dirs {
+                if !self.include_dirs.contains(dir_entry) {
+                    self.include_dirs.push(dir_entry.clone());
+                }
+            }
+            let file = entry
+                .get("file")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if let Some(rel) = relative_to(&canonical_root, &Path::new(dir).join(file)) {
+                self.per_file.insert(rel, dirs);
+            }
+        }
+    }
+
+    pub fn extract_imports(&self, content: &str, source_path: &str) -> Vec<(String, DepKind)> {
+        lang_c::extract_includes(content)
+            .iter()
+            .filter_map(|include| self.resolve(source_path, include))
+            .map(|target| (target, DepKind::Import))
+            .collect()
+    }
+
+    /// Quoted includes search the including file's directory first; both forms
+    /// then search the file's own `-I` flags and finally every known include path.
+    pub fn resolve(&self, source_path: &str, include: &Include) -> Option<String> {
+        let local = include
+            .quoted
+            .then(|| path_utils::parent_dir(source_path).to_string());
+        let own = self
+            .per_file
+            .get(source_path)
+            .into_iter()
+            .flatten()
+            .cloned();
+
+        local
+            .into_iter()
+            .chain(own)
+            .chain(self.include_dirs.iter().cloned())
+            .filter_map(|dir| path_utils::join_normalized(&dir, &include.path))
+            .find(|candidate| self.root.join(candidate).is_file())
+    }
+}
+
+/// Directories passed via `-I`, `-iquote` or `-isystem` in `arguments` or `command`.
+fn include_flags(entry: &Value) -> Vec<String> {
+    let args: Vec<String> = match entry.get("arguments") {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect(),
+        _ => entry
+            .get("command")
+            .and_then(Value::as_str)
+            .map(|c| c.split_whitespace().map(String::from).collect())
+            .unwrap_or_default(),
+    };
+
+    let mut dirs = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let flag = ["-I", "-iquote", "-isystem"]
+            .iter()
+            .find(|f| arg.starts_with(*f));
+        if let Some(flag) = flag {
+            let attached = arg.get(flag.len()..).unwrap_or_default();
+            let dir = if attached.is_empty() {
+                iter.next().cloned()
+            } else {
+                Some(attached.to_string())
+            };
+            dirs.extend(dir);
+        }
+    }
+    dirs
+}
+
+/// Expresses an absolute path from the compilation database relative to the root.
+fn relative_to(canonical_root: &Path, path: &Path) -> Option<String> {
+    let resolved = path.canonicalize().ok()?;
+    let rel = resolved.strip_prefix(canonical_root).ok()?;
+    Some(rel.to_string_lossy().replace('\\', "/"))
+}
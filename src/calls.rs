@@ -0,0 +1,289 @@
+//! Call edges: functions and associated items a file calls in files it imports.
+
+use crate::lang_python::{self, PyImport};
+use crate::symbols;
+use crate::types::{DepEdge, DepKind};
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::sync::OnceLock;
+
+static CALL_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static IDENT_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static RUST_USE_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static JS_IMPORT_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+/// A call site: optional leading `.` (method on an expression), a keyword
+/// when it is a definition, and the `a::b` / `a.b` path being called.
+fn call_regex() -> Option<&'static Regex> {
+    CALL_RE
+        .get_or_init(|| {
+            Regex::new(
+                r"(\.\s*)?\b(?:(fn|def|function)\s+)?([A-Za-z_]\w*(?:(?:::|\.)[A-Za-z_]\w*)*)\s*\(",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+fn ident_regex() -> Option<&'static Regex> {
+    IDENT_RE
+        .get_or_init(|| Regex::new(r"\b[A-Za-z_]\w*\b").ok())
+        .as_ref()
+}
+
+fn rust_use_regex() -> Option<&'static Regex> {
+    RUST_USE_RE
+        .get_or_init(|| Regex::new(r"\buse\s+[^;]+;").ok())
+        .as_ref()
+}
+
+fn js_import_regex() -> Option<&'static Regex> {
+    JS_IMPORT_RE
+        .get_or_init(|| {
+            Regex::new(
+                r#"\bimport\s+[^;'"]*?\bfrom\b|\b(?:const|let|var)\s*\{[^}]*\}\s*=\s*require\b"#,
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+/// What a file defines, as seen by its callers.
+#[derive(Debug, Default)]
+struct Definitions {
+    module: String,
+    functions: HashSet<String>,
+    methods: HashSet<String>,
+    types: HashSet<String>,
+}
+
+impl Definitions {
+    fn of(path: &str, content: &str) -> Self {
+        let mut defs = Self {
+            module: module_name(path),
+            ..Self::default()
+        };
+        for f in symbols::function_definitions(content, path) {
+            if f.is_method {
+                defs.methods.insert(f.name);
+            } else {
+                defs.functions.insert(f.name);
+            }
+        }
+        defs.types = symbols::type_definitions(content, path)
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        defs
+    }
+}
+
+/// Builds `DepKind::Call` edges between files already connected by an import.
+///
+/// Recognised calls into an imported file:
+/// - `module::name(` / `module.name(` where `module` is the file's module name
+/// - `name(` where `name` appears in one of the caller's import statements
+/// - `Type::name(` / `Type.name(` on a type the imported file defines
+/// - `.name(` on a method only the imported file defines, when the caller
+///   mentions one of its types
+pub fn call_edges(sources: &[(&str, String)], import_edges: &[DepEdge]) -> Vec<DepEdge> {
+    let defs: HashMap<&str, Definitions> = sources
+        .iter()
+        .map(|(path, content)| (*path, Definitions::of(path, content)))
+        .collect();
+
+    let mut method_owners: HashMap<&str, usize> = HashMap::new();
+    for d in defs.values() {
+        for m in &d.methods {
+            *method_owners.entry(m.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut targets: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    for e in import_edges {
+        if matches!(e.kind, DepKind::Import | DepKind::TypeImport) {
+            targets
+                .entry(e.from.as_str())
+                .or_default()
+                .insert(e.to.as_str());
+        }
+    }
+
+    let mut edges = Vec::new();
+    for (path, content) in sources {
+        let Some(imported) = targets.get(path) else {
+            continue;
+        };
+        let caller = Caller::new(path, content);
+        for target in imported {
+            let Some(target_defs) = defs.get(target) else {
+                continue;
+            };
+            let calls = caller.calls_into(target_defs, &method_owners);
+            if !calls.is_empty() {
+                edges.push(DepEdge {
+                    from: (*path).to_string(),
+                    to: (*target).to_string(),
+                    kind: DepKind::Call,
+                    symbols: calls.into_iter().collect(),
+                });
+            }
+        }
+    }
+    edges
+}
+
+/// A call site split into its qualifier and called name.
+struct Site<'a> {
+    on_expression: bool,
+    /// Qualified with `::`, so never a method call on a value.
+    via_path: bool,
+    qualifier: Option<&'a str>,
+    name: &'a str,
+}
+
+/// The calling file: its call sites, imported names and mentioned identifiers.
+struct Caller<'a> {
+    sites: Vec<Site<'a>>,
+    imported: HashSet<String>,
+    mentioned: HashSet<&'a str>,
+}
+
+impl<'a> Caller<'a> {
+    fn new(path: &str, content: &'a str) -> Self {
+        Self {
+            sites: call_sites(content),
+            imported: imported_names(content, path),
+            mentioned: ident_regex()
+                .map(|re| re.find_iter(content).map(|m| m.as_str()).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn calls_into(
+        &self,
+        callee: &Definitions,
+        method_owners: &HashMap<&str, usize>,
+    ) -> BTreeSet<String> {
+        let uses_type = callee
+            .types
+            .iter()
+            .any(|t| self.mentioned.contains(t.as_str()));
+        let mut calls = BTreeSet::new();
+        for site in &self.sites {
+            let name = site.name;
+            let method_call = !site.via_path
+                && uses_type
+                && method_owners.get(name) == Some(&1)
+                && callee.methods.contains(name);
+            match site.qualifier {
+                None if site.on_expression => {
+                    if method_call {
+                        calls.insert(name.to_string());
+                    }
+                }
+                None => {
+                    let defined = callee.functions.contains(name) || callee.types.contains(name);
+                    if defined && self.imported.contains(name) {
+                        calls.insert(name.to_string());
+                    }
+                }
+                Some(q) if q == callee.module && callee.functions.contains(name) => {
+                    calls.insert(name.to_string());
+                }
+                Some(q) if callee.types.contains(q) => {
+                    if callee.methods.contains(name) || callee.functions.contains(name) {
+                        calls.insert(format!("{q}::{name}"));
+                    }
+                }
+                Some(_) => {
+                    if method_call {
+                        calls.insert(name.to_string());
+                    }
+                }
+            }
+        }
+        calls
+    }
+}
+
+fn call_sites(content: &str) -> Vec<Site<'_>> {
+    let Some(re) = call_regex() else {
+        return Vec::new();
+    };
+    re.captures_iter(content)
+        .filter(|c| c.get(2).is_none())
+        .filter_map(|c| {
+            let path = c.get(3)?.as_str();
+            let (qualifier, name) = match path.rfind(['.', ':']) {
+                Some(i) => {
+                    let head = path.get(..i)?.trim_end_matches(':');
+                    let last = head.rsplit(['.', ':']).next()?;
+                    (Some(last), path.get(i + 1..)?)
+                }
+                None => (None, path),
+            };
+            Some(Site {
+                on_expression: c.get(1).is_some(),
+                via_path: path.ends_with(&format!("::{name}")),
+                qualifier,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Identifiers named in the file's import statements.
+fn imported_names(content: &str, path: &str) -> HashSet<String> {
+    let ext = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    let spans: Vec<String> = match ext {
+        "rs" => matches(rust_use_regex(), content),
+        "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => {
+            matches(js_import_regex(), content)
+        }
+        "py" => lang_python::parse_imports(content)
+            .into_iter()
+            .flat_map(|import| match import {
+                PyImport::Module(module) => vec![module],
+                PyImport::From { names, .. } => names,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let Some(re) = ident_regex() else {
+        return HashSet::new();
+    };
+    spans
+        .iter()
+        .flat_map(|span| re.find_iter(span).map(|m| m.as_str().to_string()))
+        .collect()
+}
+
+fn matches(re: Option<&Regex>, content: &str) -> Vec<String> {
+    re.map(|re| {
+        re.find_iter(content)
+            .map(|m| m.as_str().to_string())
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Name callers use to qualify calls into a file: the file stem, or the
+/// directory name for `mod.rs`, `__init__.py` and `index.*`.
+fn module_name(path: &str) -> String {
+    let p = Path::new(path);
+    let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    if matches!(stem, "mod" | "__init__" | "index") {
+        return p
+            .parent()
+            .and_then(|d| d.file_name())
+            .and_then(|d| d.to_str())
+            .unwrap_or("")
+            .to_string();
+    }
+    stem.to_string()
+}
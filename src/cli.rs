@@ -0,0 +1,126 @@
+//! Command-line interface: the subcommands and their flags.
+
+mod args;
+mod command_args;
+
+use args::{BaselineArgs, LocationArgs};
+pub use command_args::{DepsArgs, GenerateArgs, MetricsArgs};
+
+use clap::Subcommand;
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Validate a SEMMAP file
+    Validate {
+        #[command(flatten)]
+        location: LocationArgs,
+        #[arg(long)]
+        strict: bool,
+        #[command(flatten)]
+        baseline: BaselineArgs,
+    },
+    /// Generate a new SEMMAP from a codebase
+    Generate(GenerateArgs),
+    /// Analyze dependencies and generate a dependency map
+    Deps(DepsArgs),
+    /// Report coupling metrics per file: fan-in/out, instability, abstractness
+    Metrics(MetricsArgs),
+    /// List third-party packages, their declared versions and the files using them
+    Externals {
+        #[command(flatten)]
+        location: LocationArgs,
+        /// text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// List the packages used by each file instead
+        #[arg(long)]
+        by_file: bool,
+    },
+    /// Write a self-contained HTML report of the map, graph and issues
+    Report {
+        #[command(flatten)]
+        location: LocationArgs,
+        /// Output HTML file
+        #[arg(long)]
+        html: PathBuf,
+        /// Also list source files missing from the map
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Find files no entry point reaches and exports nothing references
+    Dead {
+        #[command(flatten)]
+        location: LocationArgs,
+        /// text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Fail if anything looks dead
+        #[arg(long)]
+        check: bool,
+        /// Show the use count of every export
+        #[arg(long)]
+        counts: bool,
+    },
+    /// List the files that depend, directly or transitively, on the given files
+    Impact {
+        #[arg(required = true)]
+        paths: Vec<String>,
+        #[command(flatten)]
+        location: LocationArgs,
+        /// text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Only print the affected test files (Layer 4), one per line
+        #[arg(long)]
+        tests: bool,
+    },
+    /// Suggest the fewest file layer moves that fix every layer violation
+    FixLayers {
+        #[command(flatten)]
+        location: LocationArgs,
+        /// Keep files matching this path or glob in their layer (repeatable)
+        #[arg(long)]
+        pin: Vec<String>,
+        /// text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Move the entries in the SEMMAP file
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Update an existing SEMMAP with new/removed files
+    Update {
+        #[command(flatten)]
+        location: LocationArgs,
+    },
+    /// Show the effective configuration: semmap.toml (or Cargo.toml
+    /// `[package.metadata.semmap]`) found at or above --root or here, defaults
+    /// and flags
+    Config {
+        #[command(flatten)]
+        location: LocationArgs,
+        /// toml or json
+        #[arg(long, default_value = "toml")]
+        format: String,
+    },
+}
+
+impl Commands {
+    /// The `--root` flag, where the configuration is looked for.
+    pub fn root(&self) -> Option<&Path> {
+        match self {
+            Self::Generate(args) => args.root.as_deref(),
+            Self::Deps(args) => args.location.root.as_deref(),
+            Self::Metrics(args) => args.location.root.as_deref(),
+            Self::Validate { location, .. }
+            | Self::Externals { location, .. }
+            | Self::Report { location, .. }
+            | Self::Dead { location, .. }
+            | Self::Impact { location, .. }
+            | Self::FixLayers { location, .. }
+            | Self::Update { location }
+            | Self::Config { location, .. } => location.root.as_deref(),
+        }
+    }
+}
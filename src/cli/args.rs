@@ -0,0 +1,80 @@
+//! Flag groups shared by several subcommands.
+
+use clap::Args;
+use semmap::baseline::BaselineOptions;
+use semmap::config::Config;
+use semmap::render::ViewOptions;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct LocationArgs {
+    /// SEMMAP file [default: `[paths] file` in semmap.toml, else SEMMAP.md]
+    #[arg(short, long)]
+    pub file: Option<PathBuf>,
+    /// Project root [default: `[paths] root` in semmap.toml, else .]
+    #[arg(short, long)]
+    pub root: Option<PathBuf>,
+}
+
+impl LocationArgs {
+    /// The file and root, flags first, then the configuration.
+    pub fn resolve(self, config: &Config) -> (PathBuf, PathBuf) {
+        let file = self.file.or_else(|| config.paths.file.clone());
+        let root = self.root.or_else(|| config.paths.root.clone());
+        (file.unwrap_or_default(), root.unwrap_or_default())
+    }
+}
+
+#[derive(Args)]
+pub struct BaselineArgs {
+    /// Only fail on findings not recorded in this baseline file
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+    /// Write the current findings to the baseline file, keeping those of checks not run
+    #[arg(long)]
+    pub update_baseline: bool,
+    /// Let --update-baseline add new findings to an existing baseline
+    #[arg(long)]
+    pub allow_baseline_growth: bool,
+}
+
+impl From<BaselineArgs> for BaselineOptions {
+    fn from(args: BaselineArgs) -> Self {
+        Self {
+            path: args.baseline,
+            update: args.update_baseline,
+            allow_growth: args.allow_baseline_growth,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct ViewArgs {
+    /// Only draw files matching these globs or directories (repeatable)
+    #[arg(long, value_delimiter = ',')]
+    pub include: Vec<String>,
+    /// Leave out files matching these globs or directories (repeatable)
+    #[arg(long, value_delimiter = ',')]
+    pub exclude: Vec<String>,
+    /// Only draw files near this one
+    #[arg(long)]
+    pub focus: Option<String>,
+    /// How many edges away from --focus to draw (default 1)
+    #[arg(long)]
+    pub depth: Option<usize>,
+    /// Draw one node per directory
+    #[arg(long)]
+    pub collapse: bool,
+}
+
+impl From<ViewArgs> for ViewOptions {
+    fn from(args: ViewArgs) -> Self {
+        Self {
+            include: args.include,
+            exclude: args.exclude,
+            focus: args.focus,
+            depth: args.depth,
+            collapse: args.collapse,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Flags of the subcommands with the most options, resolved against the
+//! configuration into command settings.
+
+use super::args::{BaselineArgs, LocationArgs, ViewArgs};
+use clap::Args;
+use semmap::commands::{DepsOptions, GenerateOptions, MetricsOptions};
+use semmap::config::Config;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    /// Project root [default: `[paths] root` in semmap.toml, else .]
+    #[arg(short, long)]
+    pub root: Option<PathBuf>,
+    /// SEMMAP file to write [default: `[paths] file` in semmap.toml, else
+    /// SEMMAP.md]
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    #[arg(long)]
+    pub name: Option<String>,
+    #[arg(long)]
+    pub purpose: Option<String>,
+    /// md, json or toml [default: `[output] format`, else md]
+    #[arg(long)]
+    pub format: Option<String>,
+    /// heuristic (stereotypes and paths) or graph (dependency depth, so
+    /// the map starts without layer violations) [default: `[output]
+    /// layering`, else heuristic]
+    #[arg(long)]
+    pub layering: Option<String>,
+}
+
+impl GenerateArgs {
+    /// The root, output file and settings, flags first, then the configuration.
+    pub fn resolve(self, config: Config) -> (PathBuf, PathBuf, GenerateOptions) {
+        let paths = &config.paths;
+        let root = self.root.or_else(|| paths.root.clone()).unwrap_or_default();
+        let output = self
+            .output
+            .or_else(|| paths.file.clone())
+            .unwrap_or_default();
+        let output_config = &config.output;
+        let format = self.format.or_else(|| output_config.format.clone());
+        let layering = self.layering.or_else(|| output_config.layering.clone());
+        let options = GenerateOptions {
+            name: self.name,
+            purpose: self.purpose,
+            format: format.unwrap_or_default(),
+            layering: layering.unwrap_or_default(),
+            config,
+        };
+        (root, output, options)
+    }
+}
+
+#[derive(Args)]
+pub struct DepsArgs {
+    #[command(flatten)]
+    pub location: LocationArgs,
+    /// mermaid, dot, plantuml, d2, graphml or json [default: `[output]
+    /// diagram` in semmap.toml, else mermaid]
+    #[arg(long)]
+    pub format: Option<String>,
+    #[arg(long)]
+    pub check: bool,
+    /// Extra C/C++ include directory, relative to root (repeatable)
+    /// [default: `[analysis] include_paths` in semmap.toml]
+    #[arg(short = 'I', long = "include-path")]
+    pub include_paths: Vec<PathBuf>,
+    /// Only show these edge kinds: import, type-import, trait, call
+    #[arg(long, value_delimiter = ',')]
+    pub kinds: Vec<String>,
+    /// Fail if the dependency graph contains cycles
+    #[arg(long)]
+    pub check_cycles: bool,
+    /// Which cycles fail --check-cycles: any, directory or layer
+    #[arg(long, default_value = "any")]
+    pub cycle_scope: String,
+    /// Architecture rules file [default: `[[rules]]` of the semmap.toml or
+    /// Cargo.toml metadata found at or above the root]
+    #[arg(long)]
+    pub rules: Option<PathBuf>,
+    #[command(flatten)]
+    pub baseline: BaselineArgs,
+    /// Diagram clusters: layer, directory or none
+    #[arg(long, default_value = "layer")]
+    pub group: String,
+    #[command(flatten)]
+    pub view: ViewArgs,
+    /// Compare the graph with this git revision (formats: mermaid, text, json)
+    #[arg(long, value_name = "REV")]
+    pub diff: Option<String>,
+}
+
+impl DepsArgs {
+    /// The map file, root and settings, flags first, then the configuration.
+    pub fn resolve(self, config: Config) -> (PathBuf, PathBuf, DepsOptions) {
+        let (file, root) = self.location.resolve(&config);
+        let format = self.format.or_else(|| config.output.diagram.clone());
+        let options = DepsOptions {
+            format: format.unwrap_or_default(),
+            check: self.check,
+            include_paths: self.include_paths,
+            kinds: self.kinds,
+            check_cycles: self.check_cycles,
+            cycle_scope: self.cycle_scope,
+            rules: self.rules,
+            baseline: self.baseline.into(),
+            group: self.group,
+            view: self.view.into(),
+            diff: self.diff,
+            config,
+        };
+        (file, root, options)
+    }
+}
+
+#[derive(Args)]
+pub struct MetricsArgs {
+    #[command(flatten)]
+    pub location: LocationArgs,
+    #[arg(long, default_value = "text")]
+    pub format: String,
+    /// Fail if any file exceeds a threshold
+    #[arg(long)]
+    pub check: bool,
+    #[arg(long)]
+    pub max_cbo: Option<usize>,
+    #[arg(long)]
+    pub hub_threshold: Option<f64>,
+    #[arg(long)]
+    pub min_hub_afferent: Option<usize>,
+    #[arg(long)]
+    pub god_module_threshold: Option<usize>,
+    /// Maximum distance from the main sequence
+    #[arg(long)]
+    pub max_distance: Option<f64>,
+}
+
+impl MetricsArgs {
+    /// The map file, root and settings; unset limits stay for `slopchop.toml`.
+    pub fn resolve(self, config: Config) -> (PathBuf, PathBuf, MetricsOptions) {
+        let (file, root) = self.location.resolve(&config);
+        let options = MetricsOptions {
+            format: self.format,
+            check: self.check,
+            max_cbo: self.max_cbo,
+            hub_threshold: self.hub_threshold,
+            min_hub_afferent: self.min_hub_afferent,
+            god_module_threshold: self.god_module_threshold,
+            max_distance: self.max_distance,
+            config,
+        };
+        (file, root, options)
+    }
+}
@@ -0,0 +1,46 @@
+//! `config`: the effective configuration.
+
+use crate::config::{self, Discovered};
+use std::path::PathBuf;
+
+/// Settings for the `config` command; `file` and `root` override the
+/// configuration like they do for other commands.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOptions {
+    pub file: Option<PathBuf>,
+    pub root: Option<PathBuf>,
+    /// toml or json.
+    pub format: String,
+}
+
+/// Prints the discovered configuration, with the flags and defaults applied.
+pub fn config(found: Option<&Discovered>, options: &ConfigOptions) -> Result<(), String> {
+    let mut effective = found.map(|f| f.config.clone()).unwrap_or_default();
+    if options.file.is_some() {
+        effective.paths.file.clone_from(&options.file);
+    }
+    if options.root.is_some() {
+        effective.paths.root.clone_from(&options.root);
+    }
+    let effective = effective.effective();
+    let output = match options.format.as_str() {
+        "json" => {
+            serde_json::to_string_pretty(&effective).map_err(|e| format!("JSON error: {e}"))?
+        }
+        "" | "toml" => {
+            let source = found.map_or_else(
+                || format!("no {} found, defaults", config::FILE_NAME),
+                |f| f.path.display().to_string(),
+            );
+            let body = toml::to_string(&effective).map_err(|e| format!("TOML error: {e}"))?;
+            format!("# Configuration: {source}\n{body}")
+        }
+        other => {
+            return Err(format!(
+                "Unknown config format: {other} (expected toml or json)"
+            ))
+        }
+    };
+    println!("{}", output.trim_end());
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! `dead`: unreachable files and unused exports.
+
+use super::read_map;
+use crate::config::Config;
+use crate::{dead, deps};
+use std::path::Path;
+
+/// Settings for the `dead` command.
+#[derive(Debug, Clone, Default)]
+pub struct DeadOptions {
+    pub format: String,
+    /// Fail if any file is unreachable or any export unused.
+    pub check: bool,
+    /// List the use count of every export, not only the unused ones.
+    pub counts: bool,
+    /// Dependency analysis.
+    pub config: Config,
+}
+
+pub fn dead(file: &Path, root: &Path, options: &DeadOptions) -> Result<(), String> {
+    let semmap = read_map(file)?;
+    let depmap = deps::analyze_with(root, &semmap, &options.config.analyze_options());
+    let found = dead::analyze(root, &semmap, &depmap);
+    let output = if options.format == "json" {
+        serde_json::to_string_pretty(&found).map_err(|e| format!("JSON error: {e}"))?
+    } else {
+        dead::render_text(&found, options.counts)
+    };
+    println!("{}", output.trim_end());
+    if options.check && !found.is_clean() {
+        return Err(format!(
+            "{} unreachable files, {} unused exports",
+            found.unreachable.len(),
+            found.unused_exports().count()
+        ));
+    }
+    Ok(())
+}
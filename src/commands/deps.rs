@@ -0,0 +1,163 @@
+//! `deps`: the dependency graph as a diagram or JSON, and the checks run on
+//! it.
+
+use super::deps_checks::{
+    parse_kinds, report_cycles, report_layer_violations, report_rules, report_stale_allows,
+};
+use super::deps_diff::deps_diff;
+use super::read_map;
+use crate::baseline::{Baseline, BaselineOptions};
+use crate::config::Config;
+use crate::cycles::{self, CycleScope};
+use crate::deps::AnalyzeOptions;
+use crate::layer_check::{self, LayerViolation};
+use crate::render::{self, Grouping, RenderOptions, ViewOptions};
+use crate::types::DependencyMap;
+use crate::{deps, externals, facades, rules};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Settings for the `deps` command.
+#[derive(Debug, Clone, Default)]
+pub struct DepsOptions {
+    pub format: String,
+    pub check: bool,
+    /// Include directories replacing the configured ones, if any are given.
+    pub include_paths: Vec<PathBuf>,
+    /// Edge kinds to show (`import`, `type-import`, `trait`, `call`); empty shows
+    /// all. Checks always see every edge.
+    pub kinds: Vec<String>,
+    /// Fail when dependency cycles exist.
+    pub check_cycles: bool,
+    /// Which cycles fail `check_cycles`: `any`, `directory` or `layer`.
+    pub cycle_scope: String,
+    /// Rules file replacing the `[[rules]]` of `config`.
+    pub rules: Option<PathBuf>,
+    pub baseline: BaselineOptions,
+    /// Diagram clusters: `none`, `layer` or `directory`.
+    pub group: String,
+    /// Which part of the graph diagrams show; JSON output is never filtered.
+    pub view: ViewOptions,
+    /// Compare against this git revision instead (`text`, `json` or `mermaid`).
+    pub diff: Option<String>,
+    /// Facades, rules and dependency analysis.
+    pub config: Config,
+}
+
+impl DepsOptions {
+    /// The configured analysis, with `include_paths` taking precedence.
+    pub fn analyze_options(&self) -> AnalyzeOptions {
+        let mut analyze_options = self.config.analyze_options();
+        if !self.include_paths.is_empty() {
+            analyze_options
+                .include_paths
+                .clone_from(&self.include_paths);
+        }
+        analyze_options
+    }
+}
+
+/// `deps --format json` output: the graph plus its layer violations and
+/// external packages.
+#[derive(Serialize)]
+struct DepsReport<'a> {
+    #[serde(flatten)]
+    depmap: &'a DependencyMap,
+    violations: &'a [LayerViolation],
+    externals: &'a externals::Inventory,
+}
+
+pub fn deps(file: &Path, root: &Path, options: &DepsOptions) -> Result<(), String> {
+    let semmap = read_map(file)?;
+    if options.diff.is_some() {
+        return deps_diff(file, root, &semmap, options);
+    }
+    let kinds = parse_kinds(&options.kinds)?;
+    let depmap = deps::analyze_with(root, &semmap, &options.analyze_options());
+    let found = cycles::find_cycles(&depmap);
+    let facades = facades::detect(root, &semmap, &options.config.facades);
+    let violations = deps::check_layer_violations_with(&depmap, &semmap, &facades);
+    let inventory = externals::inventory(root, &semmap);
+    if options.baseline.update && !options.check && !options.check_cycles {
+        return Err("--update-baseline needs --check or --check-cycles".to_string());
+    }
+    let mut baseline = Baseline::open(&options.baseline)?;
+    if !options.check {
+        for category in ["Layer violation", "Stale allow", "Rule "] {
+            baseline.skip(category);
+        }
+    }
+    if !options.check_cycles {
+        baseline.skip("Cycle");
+    }
+    let mut failures = Vec::new();
+    if options.check {
+        failures.extend(report_layer_violations(&violations, &mut baseline));
+        failures.extend(report_stale_allows(
+            &depmap,
+            &semmap,
+            &facades,
+            &mut baseline,
+        ));
+        let rules = rules::load(&options.config.rules, options.rules.as_deref())?;
+        failures.extend(report_rules(&rules, &depmap, &inventory, &mut baseline));
+    }
+    if options.check_cycles {
+        let scope = CycleScope::parse(&options.cycle_scope)
+            .ok_or_else(|| format!("Unknown cycle scope: {}", options.cycle_scope))?;
+        failures.extend(report_cycles(&found, scope, &depmap, &mut baseline));
+    }
+    baseline.finish(&failures)?;
+    // `--kinds` narrows what is shown, never what is checked
+    let mut depmap = depmap;
+    deps::retain_kinds(&mut depmap, &kinds);
+    let violations: Vec<LayerViolation> = violations
+        .into_iter()
+        .filter(|v| kinds.is_empty() || kinds.contains(&v.kind))
+        .collect();
+    let output = if options.format == "json" {
+        let report = DepsReport {
+            depmap: &depmap,
+            violations: &violations,
+            externals: &inventory,
+        };
+        serde_json::to_string_pretty(&report).map_err(|e| format!("JSON error: {e}"))?
+    } else {
+        let grouping = Grouping::parse(&options.group)
+            .ok_or_else(|| format!("Unknown grouping: {}", options.group))?;
+        let render_options = RenderOptions {
+            highlighted: cycles::cycle_edges(&found, &depmap),
+            violations: layer_check::violation_edges(&violations),
+            grouping,
+            layer_names: semmap
+                .layers
+                .iter()
+                .map(|l| (l.number, l.name.clone()))
+                .collect(),
+            ..RenderOptions::default()
+        };
+        render_diagram(&depmap, render_options, &options.view, &options.format)?
+    };
+    println!("{output}");
+    Ok(())
+}
+
+fn render_diagram(
+    depmap: &DependencyMap,
+    options: RenderOptions,
+    view: &ViewOptions,
+    format: &str,
+) -> Result<String, String> {
+    let shown = render::view(depmap, view)?;
+    let options = RenderOptions {
+        highlighted: render::view_edges(&options.highlighted, view),
+        violations: render::view_edges(&options.violations, view),
+        ..options
+    };
+    render::render(format, &shown, &options).ok_or_else(|| {
+        format!(
+            "Unknown format: {format} (expected {} or json)",
+            render::FORMATS.join(", ")
+        )
+    })
+}
@@ -0,0 +1,106 @@
+//! Checks run by `deps --check` / `--check-cycles`, printing their findings.
+//! Findings already recorded in the baseline are not reported.
+
+use crate::baseline::Baseline;
+use crate::cycles::{Cycle, CycleScope};
+use crate::externals::Inventory;
+use crate::layer_check::{self, LayerViolation};
+use crate::rules::{self, Rule};
+use crate::types::{DepKind, DependencyMap, SemmapFile};
+use std::collections::HashSet;
+
+pub fn report_layer_violations(
+    violations: &[LayerViolation],
+    baseline: &mut Baseline,
+) -> Option<String> {
+    let violations = baseline.retain_new(violations.iter().collect(), ToString::to_string);
+    if violations.is_empty() {
+        print_clean(baseline, "layer violations");
+        return None;
+    }
+    for v in &violations {
+        println!("X {v}");
+        println!("    fix: {}", v.suggestion);
+    }
+    Some(format!("{} layer violations", violations.len()))
+}
+
+/// Allow lines that no longer match a real dependency; they would otherwise
+/// silently cover whatever edge appears there next.
+pub fn report_stale_allows(
+    depmap: &DependencyMap,
+    semmap: &SemmapFile,
+    facades: &HashSet<String>,
+    baseline: &mut Baseline,
+) -> Option<String> {
+    let found = layer_check::stale_allows(depmap, semmap, facades);
+    let stale = baseline.retain_new(found, Clone::clone);
+    if stale.is_empty() {
+        return None;
+    }
+    for line in &stale {
+        println!("X {line}");
+    }
+    Some(format!("{} stale allows", stale.len()))
+}
+
+pub fn report_cycles(
+    cycles: &[Cycle],
+    scope: CycleScope,
+    depmap: &DependencyMap,
+    baseline: &mut Baseline,
+) -> Option<String> {
+    let failing: Vec<&Cycle> = cycles.iter().filter(|c| c.crosses(scope, depmap)).collect();
+    let failing = baseline.retain_new(failing, |c| format!("Cycle: {}", c.files.join(", ")));
+    if failing.is_empty() {
+        print_clean(baseline, "dependency cycles");
+        return None;
+    }
+    for cycle in &failing {
+        println!("X Cycle: {cycle}");
+    }
+    Some(format!("{} dependency cycles", failing.len()))
+}
+
+pub fn report_rules(
+    rules: &[Rule],
+    depmap: &DependencyMap,
+    externals: &Inventory,
+    baseline: &mut Baseline,
+) -> Option<String> {
+    if rules.is_empty() {
+        return None;
+    }
+    let mut found = rules::evaluate(rules, depmap);
+    found.extend(rules::evaluate_externals(rules, externals, depmap));
+    let violations = baseline.retain_new(found, |v| {
+        format!("Rule {}: {} -> {}", v.rule, v.from, v.to)
+    });
+    if violations.is_empty() {
+        if baseline.is_enabled() {
+            println!("* No new architecture rule violations");
+        } else {
+            println!("* All {} architecture rules pass", rules.len());
+        }
+        return None;
+    }
+    for line in rules::report(rules, &violations) {
+        println!("X {line}");
+    }
+    Some(format!("{} rule violations", violations.len()))
+}
+
+fn print_clean(baseline: &Baseline, what: &str) {
+    if baseline.is_enabled() {
+        println!("* No new {what}");
+    } else {
+        println!("* No {what}");
+    }
+}
+
+pub fn parse_kinds(names: &[String]) -> Result<Vec<DepKind>, String> {
+    names
+        .iter()
+        .map(|name| DepKind::parse(name).ok_or_else(|| format!("Unknown edge kind: {name}")))
+        .collect()
+}
@@ -0,0 +1,98 @@
+//! `deps --diff <rev>`: the dependency graph of a git revision against the
+//! working tree.
+
+use super::{deps_checks::parse_kinds, DepsOptions};
+use crate::deps;
+use crate::graph_diff::{self, Analysis, Snapshot};
+use crate::render::{self, Grouping, Highlighted, RenderOptions};
+use crate::types::{DepEdge, DependencyMap, SemmapFile};
+use crate::{cycles, layer_check, parser};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+pub fn deps_diff(
+    file: &Path,
+    root: &Path,
+    semmap: &SemmapFile,
+    options: &DepsOptions,
+) -> Result<(), String> {
+    let rev = options.diff.as_deref().unwrap_or("HEAD");
+    let analyze_options = options.analyze_options();
+    let kinds = parse_kinds(&options.kinds)?;
+    let globs = &options.config.facades;
+    let mut new = Analysis::compute(root, semmap, &analyze_options, globs);
+    let mut snapshot = Snapshot::open(root, rev)?;
+    // Layers as they were: the revision's own map, if it had one
+    let map_path = file.strip_prefix(root).unwrap_or(file);
+    snapshot.write(|path| Path::new(path) == map_path)?;
+    let old_map = fs::read_to_string(snapshot.dir().join(map_path))
+        .ok()
+        .and_then(|content| parser::parse(&content).ok());
+    let old_semmap = old_map.as_ref().unwrap_or(semmap);
+    let mapped: HashSet<&str> = old_semmap
+        .all_paths()
+        .into_iter()
+        .chain(semmap.all_paths())
+        .collect();
+    snapshot.write(|path| mapped.contains(path) || graph_diff::is_analyzer_input(path))?;
+    let mut old = Analysis::compute(snapshot.dir(), old_semmap, &analyze_options, globs);
+    // Violations and cycles are found on every edge; `--kinds` narrows the graph
+    deps::retain_kinds(&mut new.depmap, &kinds);
+    deps::retain_kinds(&mut old.depmap, &kinds);
+    let diff = graph_diff::compare(rev, &old, &new);
+
+    let output = match options.format.as_str() {
+        "json" => serde_json::to_string_pretty(&diff).map_err(|e| format!("JSON error: {e}"))?,
+        "text" => graph_diff::render_text(&diff),
+        "" | "mermaid" => {
+            let grouping = Grouping::parse(&options.group)
+                .ok_or_else(|| format!("Unknown grouping: {}", options.group))?;
+            let render_options = RenderOptions {
+                highlighted: cycles::cycle_edges(&diff.new_cycles, &new.depmap),
+                violations: layer_check::violation_edges(&diff.new_violations),
+                grouping,
+                layer_names: semmap
+                    .layers
+                    .iter()
+                    .map(|l| (l.number, l.name.clone()))
+                    .collect(),
+                added: new_pairs(&diff.added_edges, &old.depmap),
+                removed: new_pairs(&diff.removed_edges, &new.depmap),
+            };
+            let merged = graph_diff::merged(&old.depmap, &new.depmap);
+            render::render_mermaid_with(&merged, &render_options)
+        }
+        other => {
+            return Err(format!(
+                "Unknown diff format: {other} (expected mermaid, text or json)"
+            ))
+        }
+    };
+    println!("{}", output.trim_end());
+
+    let regressions = diff.new_violations.len() + diff.new_cycles.len();
+    if options.check && regressions > 0 {
+        return Err(format!(
+            "{} new layer violations, {} new dependency cycles since {rev}",
+            diff.new_violations.len(),
+            diff.new_cycles.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Endpoints of `edges` that `other` has no edge between at all; a pair
+/// whose edge only changed kind is left unstyled.
+fn new_pairs(edges: &[DepEdge], other: &DependencyMap) -> Highlighted {
+    let linked: HashSet<(&str, &str)> = other
+        .edges
+        .iter()
+        .map(|e| (e.from.as_str(), e.to.as_str()))
+        .collect();
+    edges
+        .iter()
+        .filter(|e| !linked.contains(&(e.from.as_str(), e.to.as_str())))
+        .map(|e| (e.from.clone(), e.to.clone()))
+        .collect()
+}
@@ -0,0 +1,25 @@
+//! `externals`: third-party packages and the files using them.
+
+use super::read_map;
+use crate::externals;
+use std::path::Path;
+
+/// Settings for the `externals` command.
+#[derive(Debug, Clone, Default)]
+pub struct ExternalsOptions {
+    pub format: String,
+    /// List each file's packages instead of each package's files.
+    pub by_file: bool,
+}
+
+pub fn externals(file: &Path, root: &Path, options: &ExternalsOptions) -> Result<(), String> {
+    let semmap = read_map(file)?;
+    let inventory = externals::inventory(root, &semmap);
+    let output = if options.format == "json" {
+        serde_json::to_string_pretty(&inventory).map_err(|e| format!("JSON error: {e}"))?
+    } else {
+        externals::render_text(&inventory, options.by_file)
+    };
+    println!("{}", output.trim_end());
+    Ok(())
+}
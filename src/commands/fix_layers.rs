@@ -0,0 +1,41 @@
+//! `fix-layers`: the fewest layer moves that clear every layer violation.
+
+use super::read_map;
+use crate::config::Config;
+use crate::{deps, facades, formatter, layer_solver};
+use std::fs;
+use std::path::Path;
+
+/// Settings for the `fix-layers` command.
+#[derive(Debug, Clone, Default)]
+pub struct FixLayersOptions {
+    /// Paths or globs of files that must keep their layer.
+    pub pin: Vec<String>,
+    pub format: String,
+    /// Rewrite the map with the proposed layers.
+    pub apply: bool,
+    /// Facades and dependency analysis.
+    pub config: Config,
+}
+
+pub fn fix_layers(file: &Path, root: &Path, options: &FixLayersOptions) -> Result<(), String> {
+    let mut semmap = read_map(file)?;
+    let depmap = deps::analyze_with(root, &semmap, &options.config.analyze_options());
+    let facades = facades::detect(root, &semmap, &options.config.facades);
+    let violations = deps::check_layer_violations_with(&depmap, &semmap, &facades);
+    let moves =
+        layer_solver::solve(&depmap, &semmap, &facades, &options.pin).map_err(|e| e.to_string())?;
+    let output = if options.format == "json" {
+        serde_json::to_string_pretty(&moves).map_err(|e| format!("JSON error: {e}"))?
+    } else {
+        layer_solver::render_text(&moves, violations.len(), &semmap)
+    };
+    println!("{}", output.trim_end());
+    if options.apply && !moves.is_empty() {
+        layer_solver::apply(&mut semmap, &moves);
+        fs::write(file, formatter::to_markdown(&semmap))
+            .map_err(|e| format!("Failed to write {}: {e}", file.display()))?;
+        println!("* Moved {} files in {}", moves.len(), file.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! `generate`: writes a new map of the files under a root.
+
+use crate::config::Config;
+use crate::{formatter, generator};
+use std::fs;
+use std::path::Path;
+
+/// Settings for the `generate` command.
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    pub name: Option<String>,
+    pub purpose: Option<String>,
+    /// md, json or toml.
+    pub format: String,
+    /// heuristic or graph.
+    pub layering: String,
+    /// Discovery, layer schema and facades.
+    pub config: Config,
+}
+
+pub fn generate(root: &Path, output: &Path, options: &GenerateOptions) -> Result<(), String> {
+    let settings = options.config.generator();
+    let layering = match options.layering.as_str() {
+        "" => settings.layering,
+        name => generator::Layering::parse(name)
+            .ok_or_else(|| format!("Unknown layering: {name} (expected heuristic or graph)"))?,
+    };
+    let config = generator::GeneratorConfig {
+        project_name: options.name.clone().unwrap_or_default(),
+        purpose: options.purpose.clone().unwrap_or_default(),
+        layering,
+        ..settings
+    };
+    let semmap = generator::generate(root, config);
+    let content = match options.format.as_str() {
+        "json" => formatter::to_json(&semmap).map_err(|e| format!("JSON error: {e}"))?,
+        "toml" => formatter::to_toml(&semmap).map_err(|e| format!("TOML error: {e}"))?,
+        _ => formatter::to_markdown(&semmap),
+    };
+    fs::write(output, &content)
+        .map_err(|e| format!("Failed to write {}: {e}", output.display()))?;
+    let file_count: usize = semmap.layers.iter().map(|l| l.entries.len()).sum();
+    println!(
+        "* Generated {} ({} layers, {file_count} files)",
+        output.display(),
+        semmap.layers.len()
+    );
+    Ok(())
+}
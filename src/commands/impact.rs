@@ -0,0 +1,33 @@
+//! `impact`: the files depending on the given ones.
+
+use super::read_map;
+use crate::config::Config;
+use crate::{deps, impact};
+use std::path::Path;
+
+/// Settings for the `impact` command.
+#[derive(Debug, Clone, Default)]
+pub struct ImpactOptions {
+    /// Files whose dependents are wanted.
+    pub paths: Vec<String>,
+    pub format: String,
+    /// Only print the affected test files, one per line.
+    pub tests: bool,
+    /// Dependency analysis.
+    pub config: Config,
+}
+
+pub fn impact(file: &Path, root: &Path, options: &ImpactOptions) -> Result<(), String> {
+    let semmap = read_map(file)?;
+    let depmap = deps::analyze_with(root, &semmap, &options.config.analyze_options());
+    let result = impact::analyze(&depmap, &options.paths)?;
+    let output = if options.tests {
+        result.tests.join("\n")
+    } else if options.format == "json" {
+        serde_json::to_string_pretty(&result).map_err(|e| format!("JSON error: {e}"))?
+    } else {
+        impact::render_text(&result, &semmap)
+    };
+    println!("{}", output.trim_end());
+    Ok(())
+}
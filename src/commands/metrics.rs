@@ -0,0 +1,59 @@
+//! `metrics`: coupling metrics per file, optionally checked against limits.
+
+use super::read_map;
+use crate::config::Config;
+use crate::deps;
+use crate::metrics::{self, Thresholds};
+use std::path::Path;
+
+/// Settings for the `metrics` command; unset limits come from `slopchop.toml`.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsOptions {
+    pub format: String,
+    pub check: bool,
+    pub max_cbo: Option<usize>,
+    pub hub_threshold: Option<f64>,
+    pub min_hub_afferent: Option<usize>,
+    pub god_module_threshold: Option<usize>,
+    pub max_distance: Option<f64>,
+    /// Dependency analysis.
+    pub config: Config,
+}
+
+impl MetricsOptions {
+    fn thresholds(&self, root: &Path) -> Thresholds {
+        let loaded = Thresholds::load(root);
+        Thresholds {
+            max_cbo: self.max_cbo.unwrap_or(loaded.max_cbo),
+            hub_threshold: self.hub_threshold.unwrap_or(loaded.hub_threshold),
+            min_hub_afferent: self.min_hub_afferent.unwrap_or(loaded.min_hub_afferent),
+            god_module_threshold: self
+                .god_module_threshold
+                .unwrap_or(loaded.god_module_threshold),
+            max_distance: self.max_distance.or(loaded.max_distance),
+        }
+    }
+}
+
+pub fn metrics(file: &Path, root: &Path, options: &MetricsOptions) -> Result<(), String> {
+    let semmap = read_map(file)?;
+    let depmap = deps::analyze_with(root, &semmap, &options.config.analyze_options());
+    let thresholds = options.thresholds(root);
+    let results = metrics::compute(root, &depmap, &thresholds);
+    let output = match options.format.as_str() {
+        "json" => serde_json::to_string_pretty(&results).map_err(|e| format!("JSON error: {e}"))?,
+        _ => metrics::render_table(&results),
+    };
+    println!("{output}");
+    if options.check {
+        let issues = metrics::issues(&results, &thresholds);
+        if !issues.is_empty() {
+            for issue in &issues {
+                println!("X {issue}");
+            }
+            return Err(format!("{} metric thresholds exceeded", issues.len()));
+        }
+        println!("* All files within metric thresholds");
+    }
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! `report`: a self-contained HTML report of the map, graph and issues.
+
+use super::read_map;
+use crate::config::Config;
+use crate::{deps, facades, report, validator};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings for the `report` command.
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    /// Where to write the HTML report.
+    pub html: PathBuf,
+    /// Also list source files missing from the map.
+    pub strict: bool,
+    /// Facades and dependency analysis.
+    pub config: Config,
+}
+
+pub fn report(file: &Path, root: &Path, options: &ReportOptions) -> Result<(), String> {
+    let semmap = read_map(file)?;
+    let analyze_options = options.config.analyze_options();
+    let validation = if options.strict {
+        validator::validate_against_codebase(&semmap, root, &analyze_options)
+    } else {
+        validator::validate(&semmap, Some(root))
+    };
+    let depmap = deps::analyze_with(root, &semmap, &analyze_options);
+    let facades = facades::detect(root, &semmap, &options.config.facades);
+    let violations = deps::check_layer_violations_with(&depmap, &semmap, &facades);
+    let html = report::render_html(&semmap, &depmap, &validation, &violations);
+    fs::write(&options.html, html)
+        .map_err(|e| format!("Failed to write {}: {e}", options.html.display()))?;
+    println!(
+        "* Report written to {} ({} issues, {} layer violations)",
+        options.html.display(),
+        validation.issues.len(),
+        violations.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,47 @@
+//! `update`: adds new files to a map and drops deleted ones.
+
+use super::read_map;
+use super::update_helpers::{add_new_entries, remove_deleted_entries};
+use crate::config::Config;
+use crate::{formatter, generator, path_utils};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Adds files discovered under `root` (as `config` selects them) to the map
+/// and drops the ones that are gone.
+pub fn update(file: &Path, root: &Path, config: &Config) -> Result<(), String> {
+    let mut semmap = read_map(file)?;
+    let fresh = generator::generate(
+        root,
+        generator::GeneratorConfig {
+            project_name: semmap.project_name.clone(),
+            purpose: semmap.purpose.clone(),
+            ..config.generator()
+        },
+    );
+    let semmap_dir = file.parent().unwrap_or(Path::new("."));
+    let root_prefix = path_utils::build_root_prefix_relative(semmap_dir, root);
+    let existing: HashSet<String> = semmap.all_paths().into_iter().map(String::from).collect();
+    let current: HashSet<String> = fresh
+        .all_paths()
+        .into_iter()
+        .map(|p| path_utils::prefix_path(&root_prefix, p))
+        .collect();
+    let added: Vec<String> = current.difference(&existing).cloned().collect();
+    let removed: Vec<String> = existing.difference(&current).cloned().collect();
+
+    add_new_entries(&mut semmap, &added, &fresh, &root_prefix);
+    remove_deleted_entries(&mut semmap, &removed);
+
+    let output = formatter::to_markdown(&semmap);
+    fs::write(file, &output).map_err(|e| format!("Failed to write {}: {e}", file.display()))?;
+    println!("* Updated SEMMAP: +{} -{}", added.len(), removed.len());
+    for path in &added {
+        println!("  + {path}");
+    }
+    for path in &removed {
+        println!("  - {path}");
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+//! `validate`: checks a map on its own, or against the codebase when strict.
+
+use super::read_map;
+use crate::baseline::{Baseline, BaselineOptions};
+use crate::config::Config;
+use crate::error::{Severity, ValidationIssue};
+use crate::validator::{self, ValidationResult};
+use std::path::Path;
+
+/// Settings for the `validate` command.
+#[derive(Debug, Clone, Default)]
+pub struct ValidateOptions {
+    /// Also fail on warnings, and check against the codebase.
+    pub strict: bool,
+    pub baseline: BaselineOptions,
+    /// Dependency analysis for the declared dependency check.
+    pub config: Config,
+}
+
+/// Baseline key prefixes of the findings only strict mode checks.
+const WARNING: &str = "Validate warning";
+const CODEBASE: &str = "Validate codebase";
+
+pub fn validate(file: &Path, root: &Path, options: &ValidateOptions) -> Result<(), String> {
+    let semmap = read_map(file)?;
+    let strict = options.strict;
+    let (errors, warnings): (Vec<_>, Vec<_>) = validator::validate(&semmap, Some(root))
+        .issues
+        .into_iter()
+        .partition(|i| i.severity == Severity::Error);
+    let mut baseline = Baseline::open(&options.baseline)?;
+    let mut result = ValidationResult {
+        issues: baseline.retain_new(errors, |i| issue_key("Validate", i)),
+    };
+    if strict {
+        let codebase = validator::codebase_issues(&semmap, root, &options.config.analyze_options());
+        let warnings = baseline.retain_new(warnings, |i| issue_key(WARNING, i));
+        let codebase = baseline.retain_new(codebase, |i| issue_key(CODEBASE, i));
+        result.issues.extend(warnings.into_iter().chain(codebase));
+    } else {
+        // Warnings are shown but not baselined, and the codebase is not checked
+        baseline.skip(WARNING);
+        baseline.skip(CODEBASE);
+        result.issues.extend(warnings);
+    }
+    print_validation_result(&result);
+
+    let has_errors = result.error_count() > 0;
+    let has_warnings = result.warning_count() > 0;
+
+    let mut failures = Vec::new();
+    if has_errors || (strict && has_warnings) {
+        failures.push(format!(
+            "{} errors, {} warnings",
+            result.error_count(),
+            result.warning_count()
+        ));
+    }
+    baseline.finish(&failures)?;
+    if failures.is_empty() {
+        println!("* SEMMAP is valid");
+    }
+    Ok(())
+}
+
+/// Baseline key for a validation issue; line numbers are left out because
+/// they shift whenever the map is edited.
+fn issue_key(prefix: &str, issue: &ValidationIssue) -> String {
+    match &issue.path {
+        Some(path) => format!("{prefix} [{path}]: {}", issue.message),
+        None => format!("{prefix}: {}", issue.message),
+    }
+}
+
+fn print_validation_result(result: &ValidationResult) {
+    for issue in &result.issues {
+        let icon = if issue.severity == Severity::Error {
+            "X"
+        } else {
+            "!"
+        };
+        match (&issue.path, &issue.line) {
+            (Some(p), Some(l)) => println!("{icon} [{p}:{l}] {}", issue.message),
+            (Some(p), None) => println!("{icon} [{p}] {}", issue.message),
+            (None, Some(l)) => println!("{icon} [line {l}] {}", issue.message),
+            (None, None) => println!("{icon} {}", issue.message),
+        }
+    }
+    if !result.issues.is_empty() {
+        println!();
+    }
+}
@@ -0,0 +1,253 @@
+//! Project configuration from `semmap.toml`, or `[package.metadata.semmap]`
+//! (or `[workspace.metadata.semmap]`) in `Cargo.toml`, found by walking up
+//! from a directory. Paths are relative to the file declaring them; globs
+//! match paths relative to the project root.
+//!
+//! ```toml
+//! facades = ["src/api/index.ts"]
+//!
+//! [paths]
+//! file = "docs/SEMMAP.md"   # relative to this file
+//! root = "."
+//!
+//! [output]
+//! format = "md"             # generate: md, json or toml
+//! layering = "graph"        # generate: heuristic or graph
+//! diagram = "mermaid"       # deps
+//!
+//! [analysis]
+//! include_paths = ["include"] # C/C++, relative to the root
+//!
+//! [discovery]
+//! include = ["src/**", "tests/**"]
+//! exclude = ["src/generated/**"]
+//! extensions = ["rs", "toml"]
+//! exclude_dirs = ["target", "vendor"]
+//!
+//! [[layers]]
+//! number = 3
+//! name = "Adapters"
+//!
+//! [layer_overrides]
+//! "src/legacy/**" = 3
+//!
+//! [[rules]]
+//! name = "no-test-imports"
+//! to = "tests/**"
+//! ```
+
+use crate::deps::AnalyzeOptions;
+use crate::generator::{GeneratorConfig, Layering};
+use crate::rules;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const FILE_NAME: &str = "semmap.toml";
+
+/// Every setting is optional; unset ones take the CLI defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Globs of files that only re-export others.
+    pub facades: Vec<String>,
+    pub paths: Paths,
+    pub output: Output,
+    pub analysis: Analysis,
+    pub discovery: Discovery,
+    /// Layer names by number, replacing the defaults.
+    pub layers: Vec<LayerName>,
+    /// Globs whose files `generate` puts in the given layer; the longest
+    /// matching glob wins.
+    pub layer_overrides: BTreeMap<String, u8>,
+    /// Architecture rules, checked by `deps --check` (see `rules`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<toml::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Paths {
+    /// The SEMMAP file.
+    pub file: Option<PathBuf>,
+    /// The project root.
+    pub root: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Output {
+    /// Map format written by `generate`.
+    pub format: Option<String>,
+    /// How `generate` assigns layers.
+    pub layering: Option<String>,
+    /// Diagram format of `deps`.
+    pub diagram: Option<String>,
+}
+
+/// How every command builds the dependency graph.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Analysis {
+    /// Extra C/C++ include directories, relative to the project root.
+    pub include_paths: Vec<PathBuf>,
+}
+
+/// Which files `generate` and `update` map.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Discovery {
+    /// Globs a file must match, if any are given.
+    pub include: Vec<String>,
+    /// Globs of files to leave out.
+    pub exclude: Vec<String>,
+    pub extensions: Option<Vec<String>>,
+    /// Exact file names mapped whatever their extension.
+    pub files: Option<Vec<String>>,
+    pub exclude_dirs: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LayerName {
+    pub number: u8,
+    pub name: String,
+}
+
+/// A configuration and the file it came from.
+#[derive(Debug, Clone)]
+pub struct Discovered {
+    pub path: PathBuf,
+    pub config: Config,
+}
+
+/// The nearest configuration at or above `start`, with its paths made
+/// relative to `start`.
+pub fn discover(start: &Path) -> Result<Option<Discovered>, String> {
+    let Ok(absolute) = fs::canonicalize(start) else {
+        return Ok(None);
+    };
+    for (ups, dir) in absolute.ancestors().enumerate() {
+        let Some((path, mut config)) = read_dir_config(dir)? else {
+            continue;
+        };
+        // `start/../..`, or nothing to add when `start` is the working directory
+        let mut base = if start == Path::new(".") {
+            PathBuf::new()
+        } else {
+            start.to_path_buf()
+        };
+        base.extend(std::iter::repeat_n("..", ups));
+        if !base.as_os_str().is_empty() {
+            config.paths.file = config.paths.file.map(|p| base.join(p));
+            config.paths.root = config.paths.root.map(|p| base.join(p));
+        }
+        return Ok(Some(Discovered { path, config }));
+    }
+    Ok(None)
+}
+
+fn read_dir_config(dir: &Path) -> Result<Option<(PathBuf, Config)>, String> {
+    let path = dir.join(FILE_NAME);
+    if path.is_file() {
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let config: Config =
+            toml::from_str(&content).map_err(|e| format!("{}: {e}", path.display()))?;
+        rules::from_values(config.rules.clone()).map_err(|e| format!("{}: {e}", path.display()))?;
+        return Ok(Some((path, config)));
+    }
+    let manifest = dir.join("Cargo.toml");
+    let Ok(content) = fs::read_to_string(&manifest) else {
+        return Ok(None);
+    };
+    let Ok(value) = content.parse::<toml::Value>() else {
+        return Ok(None);
+    };
+    let table = ["package", "workspace"].iter().find_map(|section| {
+        value
+            .get(section)
+            .and_then(|s| s.get("metadata"))
+            .and_then(|m| m.get("semmap"))
+    });
+    let Some(table) = table else {
+        return Ok(None);
+    };
+    let config: Config = table
+        .clone()
+        .try_into()
+        .map_err(|e| format!("{} [metadata.semmap]: {e}", manifest.display()))?;
+    rules::from_values(config.rules.clone())
+        .map_err(|e| format!("{} [metadata.semmap]: {e}", manifest.display()))?;
+    Ok(Some((manifest, config)))
+}
+
+impl Config {
+    /// This configuration with every default written out, as the commands
+    /// see it.
+    #[must_use]
+    pub fn effective(&self) -> Config {
+        let mut config = self.clone();
+        let defaults = GeneratorConfig::default();
+        let generator = self.generator();
+        config.paths.file.get_or_insert_with(|| "SEMMAP.md".into());
+        config.paths.root.get_or_insert_with(|| ".".into());
+        config.output.format.get_or_insert_with(|| "md".into());
+        config
+            .output
+            .layering
+            .get_or_insert_with(|| "heuristic".into());
+        config
+            .output
+            .diagram
+            .get_or_insert_with(|| "mermaid".into());
+        let discovery = &mut config.discovery;
+        discovery.extensions.get_or_insert(defaults.include_exts);
+        discovery.files.get_or_insert(defaults.include_files);
+        discovery.exclude_dirs.get_or_insert(defaults.exclude_dirs);
+        config.layers = (0..5)
+            .map(|number| LayerName {
+                number,
+                name: generator.layer_name(number).to_string(),
+            })
+            .chain(self.layers.iter().filter(|l| l.number >= 5).cloned())
+            .collect();
+        config
+    }
+
+    /// Dependency analysis settings.
+    pub fn analyze_options(&self) -> AnalyzeOptions {
+        AnalyzeOptions {
+            include_paths: self.analysis.include_paths.clone(),
+        }
+    }
+
+    /// Generator settings: discovery, layering and the layer schema.
+    pub fn generator(&self) -> GeneratorConfig {
+        let defaults = GeneratorConfig::default();
+        let discovery = self.discovery.clone();
+        GeneratorConfig {
+            include_exts: discovery.extensions.unwrap_or(defaults.include_exts),
+            include_files: discovery.files.unwrap_or(defaults.include_files),
+            exclude_dirs: discovery.exclude_dirs.unwrap_or(defaults.exclude_dirs),
+            include: discovery.include,
+            exclude: discovery.exclude,
+            layering: self
+                .output
+                .layering
+                .as_deref()
+                .and_then(Layering::parse)
+                .unwrap_or_default(),
+            layer_names: self
+                .layers
+                .iter()
+                .map(|l| (l.number, l.name.clone()))
+                .collect(),
+            layer_overrides: self.layer_overrides.clone(),
+            facades: self.facades.clone(),
+            analysis: self.analyze_options(),
+            ..defaults
+        }
+    }
+}
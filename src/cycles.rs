@@ -0,0 +1,209 @@
+//! Dependency cycle detection via strongly connected components.
+
+use crate::path_utils;
+use crate::scc;
+use crate::types::{DepEdge, DependencyMap};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+
+/// Which cycles count when checking: all of them, or only those crossing a
+/// directory or layer boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CycleScope {
+    #[default]
+    Any,
+    Directory,
+    Layer,
+}
+
+impl CycleScope {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "" | "any" => Some(Self::Any),
+            "directory" | "dir" => Some(Self::Directory),
+            "layer" => Some(Self::Layer),
+            _ => None,
+        }
+    }
+}
+
+/// A strongly connected component with one concrete loop through it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Cycle {
+    /// Every file in the component, sorted.
+    pub files: Vec<String>,
+    /// Edges of the shortest loop from the first file back to itself.
+    pub chain: Vec<DepEdge>,
+}
+
+impl Cycle {
+    /// Whether the component spans more than one directory or layer.
+    pub fn crosses(&self, scope: CycleScope, depmap: &DependencyMap) -> bool {
+        match scope {
+            CycleScope::Any => true,
+            CycleScope::Directory => distinct(self.files.iter().map(|f| path_utils::parent_dir(f))),
+            CycleScope::Layer => {
+                let layers: HashMap<&str, u8> = depmap
+                    .nodes
+                    .iter()
+                    .map(|n| (n.path.as_str(), n.layer))
+                    .collect();
+                distinct(self.files.iter().filter_map(|f| layers.get(f.as_str())))
+            }
+        }
+    }
+}
+
+fn distinct<T: Eq + std::hash::Hash>(items: impl Iterator<Item = T>) -> bool {
+    items.collect::<HashSet<_>>().len() > 1
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(first) = self.chain.first() else {
+            return write!(f, "{}", self.files.join(", "));
+        };
+        write!(f, "{}", first.from)?;
+        for edge in &self.chain {
+            write!(f, " -[{}]-> {}", edge.kind.name(), edge.to)?;
+        }
+        if self.files.len() > self.chain.len() {
+            write!(f, " (component of {} files)", self.files.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// All dependency cycles, ordered by their first file.
+pub fn find_cycles(depmap: &DependencyMap) -> Vec<Cycle> {
+    let graph = Graph::new(depmap);
+    let mut cycles: Vec<Cycle> = graph
+        .components()
+        .into_iter()
+        .filter(|c| c.len() > 1)
+        .filter_map(|component| {
+            let files: BTreeSet<&str> = component.iter().map(|&i| graph.name(i)).collect();
+            let start = graph.index(files.first()?)?;
+            Some(Cycle {
+                chain: graph.shortest_loop(start, &component)?,
+                files: files.into_iter().map(String::from).collect(),
+            })
+        })
+        .collect();
+    cycles.sort_by(|a, b| a.files.cmp(&b.files));
+    cycles
+}
+
+/// `(from, to)` pairs of every edge inside a cycle, for highlighting.
+pub fn cycle_edges(cycles: &[Cycle], depmap: &DependencyMap) -> HashSet<(String, String)> {
+    let members: HashMap<&str, usize> = cycles
+        .iter()
+        .enumerate()
+        .flat_map(|(i, c)| c.files.iter().map(move |f| (f.as_str(), i)))
+        .collect();
+    depmap
+        .edges
+        .iter()
+        .filter(|e| {
+            let from = members.get(e.from.as_str());
+            from.is_some() && from == members.get(e.to.as_str())
+        })
+        .map(|e| (e.from.clone(), e.to.clone()))
+        .collect()
+}
+
+/// Adjacency lists over node indices, one entry per distinct `(from, to)`.
+struct Graph<'a> {
+    names: Vec<&'a str>,
+    ids: HashMap<&'a str, usize>,
+    adjacent: Vec<Vec<(usize, &'a DepEdge)>>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(depmap: &'a DependencyMap) -> Self {
+        let mut graph = Self {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            adjacent: Vec::new(),
+        };
+        let mut seen = HashSet::new();
+        for edge in &depmap.edges {
+            let from = graph.intern(&edge.from);
+            let to = graph.intern(&edge.to);
+            if seen.insert((from, to)) {
+                if let Some(list) = graph.adjacent.get_mut(from) {
+                    list.push((to, edge));
+                }
+            }
+        }
+        graph
+    }
+
+    fn intern(&mut self, name: &'a str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name);
+        self.ids.insert(name, id);
+        self.adjacent.push(Vec::new());
+        id
+    }
+
+    fn name(&self, id: usize) -> &'a str {
+        self.names.get(id).copied().unwrap_or("")
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    fn successors(&self, id: usize) -> &[(usize, &'a DepEdge)] {
+        self.adjacent.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    fn components(&self) -> Vec<Vec<usize>> {
+        let successors: Vec<Vec<usize>> = self
+            .adjacent
+            .iter()
+            .map(|list| list.iter().map(|&(to, _)| to).collect())
+            .collect();
+        scc::strongly_connected(&successors)
+    }
+
+    /// Breadth-first search for the shortest path from `start` back to itself
+    /// that stays inside `component`.
+    fn shortest_loop(&self, start: usize, component: &[usize]) -> Option<Vec<DepEdge>> {
+        let inside: HashSet<usize> = component.iter().copied().collect();
+        let mut came_from: HashMap<usize, &DepEdge> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for &(next, edge) in self.successors(node) {
+                if !inside.contains(&next) || came_from.contains_key(&next) {
+                    continue;
+                }
+                came_from.insert(next, edge);
+                if next == start {
+                    return Some(self.unwind(start, &came_from));
+                }
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+
+    fn unwind(&self, start: usize, came_from: &HashMap<usize, &DepEdge>) -> Vec<DepEdge> {
+        let mut chain = Vec::new();
+        let mut node = start;
+        while let Some(edge) = came_from.get(&node) {
+            chain.push((*edge).clone());
+            node = self.index(&edge.from).unwrap_or(start);
+            if node == start {
+                break;
+            }
+        }
+        chain.reverse();
+        chain
+    }
+}
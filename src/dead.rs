@@ -0,0 +1,185 @@
+//! Dead code candidates: mapped files no entry point reaches, and exports
+//! listed in `→ Exports:` that no other file mentions.
+
+mod entries;
+
+use crate::path_utils;
+use crate::types::{DependencyMap, SemmapFile};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static WORD_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+fn word_regex() -> Option<&'static Regex> {
+    WORD_RE
+        .get_or_init(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").ok())
+        .as_ref()
+}
+
+/// How many other files mention an exported name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportUsage {
+    pub path: String,
+    pub name: String,
+    pub uses: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DeadReport {
+    pub entry_points: Vec<String>,
+    /// Mapped source files no entry point reaches through the graph.
+    pub unreachable: Vec<String>,
+    /// Usage counts for the exports of every file that is not an entry point.
+    pub exports: Vec<ExportUsage>,
+}
+
+impl DeadReport {
+    /// Exports no other file references: possibly dead.
+    pub fn unused_exports(&self) -> impl Iterator<Item = &ExportUsage> {
+        self.exports.iter().filter(|e| e.uses == 0)
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.unreachable.is_empty() && self.unused_exports().next().is_none()
+    }
+}
+
+pub fn analyze(root: &Path, semmap: &SemmapFile, depmap: &DependencyMap) -> DeadReport {
+    let entry_points = entries::find(root, semmap);
+    let reached = reachable(depmap, &entry_points);
+    let unreachable = semmap
+        .all_paths()
+        .into_iter()
+        .filter(|p| is_source(p) && !reached.contains(p))
+        .map(String::from)
+        .collect();
+    DeadReport {
+        exports: export_usage(root, semmap, &entry_points),
+        entry_points: entry_points.into_iter().collect(),
+        unreachable,
+    }
+}
+
+fn is_source(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(path_utils::is_source_ext)
+}
+
+/// Every file reachable from the entry points along dependency edges.
+fn reachable<'a>(depmap: &'a DependencyMap, entries: &'a BTreeSet<String>) -> HashSet<&'a str> {
+    let mut forward: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in &depmap.edges {
+        forward.entry(&edge.from).or_default().push(&edge.to);
+    }
+    let mut reached: HashSet<&str> = entries.iter().map(String::as_str).collect();
+    let mut queue: VecDeque<&str> = reached.iter().copied().collect();
+    while let Some(current) = queue.pop_front() {
+        for &next in forward.get(current).into_iter().flatten() {
+            if reached.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    reached
+}
+
+/// Counts, for each export, the other mapped files containing its name as a word.
+fn export_usage(
+    root: &Path,
+    semmap: &SemmapFile,
+    entry_points: &BTreeSet<String>,
+) -> Vec<ExportUsage> {
+    let words: Vec<(&str, HashSet<String>)> = semmap
+        .all_paths()
+        .into_iter()
+        .filter(|p| is_source(p))
+        .filter_map(|p| Some((p, words(&fs::read_to_string(root.join(p)).ok()?))))
+        .collect();
+    let mut usage = Vec::new();
+    for entry in semmap.layers.iter().flat_map(|l| &l.entries) {
+        if entry_points.contains(&entry.path) {
+            continue;
+        }
+        for export in entry.exports.iter().flatten() {
+            let Some(name) = export_name(export) else {
+                continue;
+            };
+            let users: Vec<String> = words
+                .iter()
+                .filter(|(path, found)| *path != entry.path && found.contains(name))
+                .map(|(path, _)| (*path).to_string())
+                .collect();
+            usage.push(ExportUsage {
+                path: entry.path.clone(),
+                name: name.to_string(),
+                uses: users.len(),
+                users,
+            });
+        }
+    }
+    usage
+}
+
+fn words(content: &str) -> HashSet<String> {
+    word_regex()
+        .map(|re| {
+            re.find_iter(content)
+                .map(|m| m.as_str().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The identifier in an export like `parse`, `Parser::new` or `fn parse()`.
+fn export_name(export: &str) -> Option<&str> {
+    let head = export.split('(').next().unwrap_or(export);
+    word_regex()?.find_iter(head).last().map(|m| m.as_str())
+}
+
+/// Unreachable files and unused exports; with `counts`, every export's uses.
+pub fn render_text(report: &DeadReport, counts: bool) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{} entry points, {} unreachable files, {} possibly unused exports",
+        report.entry_points.len(),
+        report.unreachable.len(),
+        report.unused_exports().count()
+    );
+    if !report.unreachable.is_empty() {
+        let _ = writeln!(out, "\nUnreachable from any entry point:");
+        for path in &report.unreachable {
+            let _ = writeln!(out, "  {path}");
+        }
+    }
+    let listed: Vec<&ExportUsage> = if counts {
+        report.exports.iter().collect()
+    } else {
+        report.unused_exports().collect()
+    };
+    if !listed.is_empty() {
+        let title = if counts {
+            "Export uses"
+        } else {
+            "Exports no other file references"
+        };
+        let _ = writeln!(out, "\n{title}:");
+        for export in listed {
+            let _ = writeln!(
+                out,
+                "  {:>3}  {}  {}",
+                export.uses, export.path, export.name
+            );
+        }
+    }
+    out
+}
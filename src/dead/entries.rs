@@ -0,0 +1,128 @@
+//! Entry points: files that are reachable without being imported.
+
+use crate::impact::TEST_LAYER;
+use crate::path_utils;
+use crate::types::SemmapFile;
+use serde_json::Value as Json;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use toml::Value as Toml;
+
+/// File names that run or are loaded by a tool on their own.
+const ENTRY_NAMES: [&str; 13] = [
+    "main.rs",
+    "lib.rs",
+    "build.rs",
+    "main.go",
+    "main.c",
+    "main.cpp",
+    "main.swift",
+    "Package.swift",
+    "__main__.py",
+    "main.py",
+    "manage.py",
+    "setup.py",
+    "conftest.py",
+];
+
+/// Directories whose files are compiled or collected as separate targets.
+const ENTRY_DIRS: [&str; 6] = ["bin", "tests", "benches", "examples", "__tests__", "test"];
+
+const CARGO_TARGETS: [&str; 4] = ["bin", "test", "bench", "example"];
+
+/// Entry points among the mapped files: `[ENTRY]` tags, the test layer,
+/// conventional names and targets declared in `Cargo.toml` / `package.json`.
+pub fn find(root: &Path, semmap: &SemmapFile) -> BTreeSet<String> {
+    let mapped: BTreeSet<&str> = semmap.all_paths().into_iter().collect();
+    let mut entries: BTreeSet<String> = semmap
+        .layers
+        .iter()
+        .flat_map(|layer| layer.entries.iter().map(move |e| (layer.number, e)))
+        .filter(|(layer, e)| {
+            *layer == TEST_LAYER || e.tags.iter().any(|t| t == "ENTRY") || is_entry_path(&e.path)
+        })
+        .map(|(_, e)| e.path.clone())
+        .collect();
+
+    let manifests = mapped
+        .iter()
+        .copied()
+        .chain(["Cargo.toml", "package.json"])
+        .filter(|p| p.ends_with("Cargo.toml") || p.ends_with("package.json"))
+        .collect::<BTreeSet<_>>();
+    for manifest in manifests {
+        let dir = path_utils::parent_dir(manifest);
+        let Ok(content) = fs::read_to_string(root.join(manifest)) else {
+            continue;
+        };
+        let targets = if manifest.ends_with("Cargo.toml") {
+            cargo_targets(&content)
+        } else {
+            npm_targets(&content)
+        };
+        entries.extend(
+            targets
+                .iter()
+                .filter_map(|t| path_utils::join_normalized(dir, t))
+                .filter(|p| mapped.contains(p.as_str())),
+        );
+    }
+    entries
+}
+
+/// Conventional entry names, Cargo target directories and test file patterns.
+pub fn is_entry_path(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let stem = name.split('.').next().unwrap_or(name);
+    let in_entry_dir = path
+        .split('/')
+        .rev()
+        .skip(1)
+        .any(|dir| ENTRY_DIRS.contains(&dir));
+    ENTRY_NAMES.contains(&name)
+        || in_entry_dir
+        || stem == "index"
+        || name.contains(".test.")
+        || name.contains(".spec.")
+        || name.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("Test")
+}
+
+/// `[lib] path` and the `path` of every `[[bin]]`, `[[test]]`, `[[bench]]`
+/// and `[[example]]` target.
+fn cargo_targets(content: &str) -> Vec<String> {
+    let Ok(doc) = content.parse::<Toml>() else {
+        return Vec::new();
+    };
+    let lib = doc.get("lib").into_iter();
+    let targets = CARGO_TARGETS
+        .iter()
+        .filter_map(|kind| doc.get(*kind)?.as_array())
+        .flatten();
+    lib.chain(targets)
+        .filter_map(|target| target.get("path")?.as_str())
+        .map(String::from)
+        .collect()
+}
+
+/// `main`, `module` and `bin` (a path or a map of command names to paths).
+fn npm_targets(content: &str) -> Vec<String> {
+    let Ok(doc) = serde_json::from_str::<Json>(content) else {
+        return Vec::new();
+    };
+    let mut targets: Vec<String> = ["main", "module"]
+        .iter()
+        .filter_map(|field| doc.get(*field)?.as_str())
+        .map(String::from)
+        .collect();
+    match doc.get("bin") {
+        Some(Json::String(bin)) => targets.push(bin.clone()),
+        Some(Json::Object(bins)) => {
+            targets.extend(bins.values().filter_map(Json::as_str).map(String::from));
+        }
+        _ => {}
+    }
+    targets
+}
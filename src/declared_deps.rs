@@ -0,0 +1,51 @@
+//! Compares `→ Depends:` declarations with the dependencies found in code.
+
+use crate::error::ValidationIssue;
+use crate::path_utils;
+use crate::types::{DependencyMap, SemmapFile};
+use std::collections::BTreeSet;
+
+/// Reports, for every entry that declares its dependencies, the real
+/// dependencies it does not declare and the declared ones it does not use.
+pub fn check(semmap: &SemmapFile, depmap: &DependencyMap) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    for entry in semmap.layers.iter().flat_map(|l| &l.entries) {
+        let Some(declared) = &entry.depends else {
+            continue;
+        };
+        let actual: BTreeSet<&str> = depmap
+            .edges
+            .iter()
+            .filter(|e| e.from == entry.path && e.to != entry.path)
+            .map(|e| e.to.as_str())
+            .collect();
+        for target in actual.iter().filter(|t| !is_declared(declared, t)) {
+            let message = format!("Undeclared dependency: {target}");
+            issues.push(ValidationIssue::warning(message).for_path(&entry.path));
+        }
+        for target in declared {
+            if !actual.iter().any(|t| matches(target, t)) {
+                let message = format!("Declared dependency unused: {target}");
+                issues.push(ValidationIssue::warning(message).for_path(&entry.path));
+            }
+        }
+    }
+    issues
+}
+
+/// Whether any entry declares dependencies, so callers can skip analysis.
+pub fn any_declared(semmap: &SemmapFile) -> bool {
+    semmap
+        .layers
+        .iter()
+        .flat_map(|l| &l.entries)
+        .any(|e| e.depends.is_some())
+}
+
+fn is_declared(declared: &[String], path: &str) -> bool {
+    declared.iter().any(|d| matches(d, path))
+}
+
+fn matches(declared: &str, path: &str) -> bool {
+    declared == path || path_utils::glob_match(declared, path)
+}
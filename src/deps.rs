@@ -1,14 +1,35 @@
 //! Dependency analysis and layer violation detection.
 
+use crate::c_resolve::IncludeResolver;
+use crate::calls;
+use crate::js_resolve::JsResolver;
+use crate::jvm_resolve::JvmResolver;
+use crate::lang_js;
 use crate::lang_python;
+use crate::lang_swift::SwiftResolver;
+use crate::python_resolve::PythonResolver;
+use crate::trait_edges;
 use crate::types::{DepEdge, DepKind, DepNode, DependencyMap, SemmapFile};
 use regex::Regex;
 use std::collections::HashSet;
-use std::fmt::Write;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+pub use crate::layer_check::{check_layer_violations, check_layer_violations_with, LayerViolation};
+pub use crate::render::{render_dot, render_mermaid, render_mermaid_highlighted};
+
+/// Options controlling dependency analysis.
+#[derive(Debug, Clone, Default)]
+pub struct AnalyzeOptions {
+    /// Extra C/C++ include directories, relative to the project root.
+    pub include_paths: Vec<PathBuf>,
+}
 
 pub fn analyze(root: &Path, semmap: &SemmapFile) -> DependencyMap {
+    analyze_with(root, semmap, &AnalyzeOptions::default())
+}
+
+pub fn analyze_with(root: &Path, semmap: &SemmapFile, options: &AnalyzeOptions) -> DependencyMap {
     let mut depmap = DependencyMap::new();
     let path_to_layer = semmap.path_to_layer();
 
@@ -21,27 +42,65 @@ pub fn analyze(root: &Path, semmap: &SemmapFile) -> DependencyMap {
     }
 
     let known_paths: HashSet<_> = semmap.all_paths().into_iter().collect();
+    let resolvers = Resolvers::load(root, &semmap.all_paths(), options);
+    let mut seen = HashSet::new();
+    let mut sources = Vec::new();
 
     for path in semmap.all_paths() {
         let full_path = root.join(path);
         if let Ok(content) = fs::read_to_string(&full_path) {
-            let imports = extract_imports(&content, path);
+            let imports = extract_imports(&content, path, &resolvers);
             for (target, kind) in imports {
-                if known_paths.contains(target.as_str()) && target != path {
+                if !known_paths.contains(target.as_str()) || target == path {
+                    continue;
+                }
+                if seen.insert((path, target.clone(), kind)) {
                     depmap.edges.push(DepEdge {
                         from: path.to_string(),
                         to: target,
                         kind,
+                        symbols: Vec::new(),
                     });
                 }
             }
+            sources.push((path, content));
         }
     }
 
+    let trait_edges = trait_edges::trait_edges(&sources, &depmap.edges);
+    let call_edges = calls::call_edges(&sources, &depmap.edges);
+    depmap.edges.extend(trait_edges);
+    depmap.edges.extend(call_edges);
+
     depmap
 }
 
-fn extract_imports(content: &str, source_path: &str) -> Vec<(String, DepKind)> {
+/// Per-language resolution state, loaded once per analysis.
+struct Resolvers {
+    js: JsResolver,
+    python: PythonResolver,
+    jvm: JvmResolver,
+    c: IncludeResolver,
+    swift: SwiftResolver,
+}
+
+impl Resolvers {
+    fn load(root: &Path, paths: &[&str], options: &AnalyzeOptions) -> Self {
+        Self {
+            js: JsResolver::load(root),
+            python: PythonResolver::load(root),
+            jvm: JvmResolver::load(root, paths),
+            c: IncludeResolver::load(root, &options.include_paths),
+            swift: SwiftResolver::load(root, paths),
+        }
+    }
+}
+
+fn extract_imports(
+    content: &str,
+    source_path: &str,
+    resolvers: &Resolvers,
+) -> Vec<(String, DepKind)> {
     let ext = Path::new(source_path)
         .extension()
         .and_then(|e| e.to_str())
@@ -49,8 +108,15 @@ fn extract_imports(content: &str, source_path: &str) -> Vec<(String, DepKind)> {
 
     match ext {
         "rs" => extract_rust_imports(content, source_path),
-        "ts" | "js" => extract_js_imports(content, source_path),
-        "py" => lang_python::extract_imports(content),
+        "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => {
+            lang_js::extract_imports(content, source_path, &resolvers.js)
+        }
+        "py" => lang_python::extract_imports(content, source_path, &resolvers.python),
+        "java" | "kt" => resolvers.jvm.extract_imports(content),
+        "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => {
+            resolvers.c.extract_imports(content, source_path)
+        }
+        "swift" => resolvers.swift.extract_imports(content, source_path),
         _ => Vec::new(),
     }
 }
@@ -77,11 +143,13 @@ fn extract_rust_imports(content: &str, source_path: &str) -> Vec<(String, DepKin
     }
 
     if let Some(re) = mod_re {
+        let mod_dir = rust_mod_dir(source_path, &base_dir);
         for cap in re.captures_iter(content) {
             if let Some(m) = cap.get(1) {
                 let module = m.as_str();
-                let target = resolve_rust_module(&base_dir, module);
-                deps.push((target, DepKind::Import));
+                deps.push((resolve_rust_module(&mod_dir, module), DepKind::Import));
+                let nested = resolve_rust_module(&mod_dir, &format!("{module}/mod"));
+                deps.push((nested, DepKind::Import));
             }
         }
     }
@@ -89,6 +157,25 @@ fn extract_rust_imports(content: &str, source_path: &str) -> Vec<(String, DepKin
     deps
 }
 
+/// `mod x;` in a crate root (`lib.rs`, `main.rs`, `build.rs`, a file in
+/// `src/bin`, `tests`, `benches` or `examples`) or a `mod.rs` lives next to
+/// the file; anywhere else (`a/b.rs`) it lives in the file's own directory.
+fn rust_mod_dir(source_path: &str, base_dir: &str) -> String {
+    let path = Path::new(source_path);
+    let owns_dir = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| matches!(n, "lib.rs" | "main.rs" | "mod.rs" | "build.rs"));
+    let target_dir = base_dir
+        .rsplit('/')
+        .next()
+        .is_some_and(|d| matches!(d, "bin" | "tests" | "benches" | "examples"));
+    if owns_dir || target_dir {
+        return base_dir.to_string();
+    }
+    path.with_extension("").to_string_lossy().replace('\\', "/")
+}
+
 fn resolve_rust_module(base_dir: &str, module: &str) -> String {
     if base_dir.is_empty() {
         format!("src/{module}.rs")
@@ -97,96 +184,9 @@ fn resolve_rust_module(base_dir: &str, module: &str) -> String {
     }
 }
 
-fn extract_js_imports(content: &str, source_path: &str) -> Vec<(String, DepKind)> {
-    let mut deps = Vec::new();
-
-    let import_re = Regex::new(r#"(?:import|from)\s+['"]([./][^'"]+)['"]"#).ok();
-    let require_re = Regex::new(r#"require\(['"]([./][^'"]+)['"]\)"#).ok();
-
-    let base_dir = Path::new(source_path).parent();
-
-    let extract = |re: Option<Regex>| -> Vec<String> {
-        re.map_or_else(Vec::new, |r| {
-            r.captures_iter(content)
-                .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
-                .collect()
-        })
-    };
-
-    for relative in extract(import_re).into_iter().chain(extract(require_re)) {
-        if let Some(resolved) = resolve_js_path(base_dir, &relative) {
-            deps.push((resolved, DepKind::Import));
-        }
-    }
-
-    deps
-}
-
-fn resolve_js_path(base: Option<&Path>, relative: &str) -> Option<String> {
-    let base = base?;
-    let mut path = base.join(relative);
-
-    if path.extension().is_none() {
-        path.set_extension("ts");
-        if !path.exists() {
-            path.set_extension("js");
-        }
-    }
-
-    Some(path.to_string_lossy().to_string())
-}
-
-pub fn render_mermaid(depmap: &DependencyMap) -> String {
-    let mut out = String::from("graph TD\n");
-
-    for node in &depmap.nodes {
-        let id = sanitize_id(&node.path);
-        let label = Path::new(&node.path)
-            .file_name()
-            .map_or_else(|| node.path.clone(), |n| n.to_string_lossy().to_string());
-        let _ = writeln!(out, "    {id}[\"{label}\"]");
+/// Keeps only edges of the given kinds; an empty list keeps everything.
+pub fn retain_kinds(depmap: &mut DependencyMap, kinds: &[DepKind]) {
+    if !kinds.is_empty() {
+        depmap.edges.retain(|e| kinds.contains(&e.kind));
     }
-
-    for edge in &depmap.edges {
-        let from_id = sanitize_id(&edge.from);
-        let to_id = sanitize_id(&edge.to);
-        let arrow = match edge.kind {
-            DepKind::Import => "-->",
-            DepKind::Trait => "-.->",
-            DepKind::Call => "==>",
-        };
-        let _ = writeln!(out, "    {from_id} {arrow} {to_id}");
-    }
-
-    out
-}
-
-fn sanitize_id(path: &str) -> String {
-    path.replace(['/', '.', '-'], "_")
-}
-
-pub fn check_layer_violations(depmap: &DependencyMap, semmap: &SemmapFile) -> Vec<String> {
-    let mut violations = Vec::new();
-    let path_to_layer = semmap.path_to_layer();
-
-    for edge in &depmap.edges {
-        // Skip facade files (lib.rs, mod.rs) - they re-export, not depend
-        if edge.from.ends_with("lib.rs") || edge.from.ends_with("mod.rs") {
-            continue;
-        }
-
-        let from_layer = path_to_layer.get(edge.from.as_str()).copied();
-        let to_layer = path_to_layer.get(edge.to.as_str()).copied();
-
-        if let (Some(fl), Some(tl)) = (from_layer, to_layer) {
-            if tl > fl {
-                violations.push(format!(
-                    "Layer violation: {} (L{fl}) depends on {} (L{tl})",
-                    edge.from, edge.to
-                ));
-            }
-        }
-    }
-
-    violations
 }
@@ -1,9 +1,12 @@
 //! Extracts documentation comments from source files.
 
-/// Extract the primary doc comment from a Rust file.
-/// Returns module-level `//!` comment if present, else first `///` block.
+/// Extract the primary doc comment from a source file.
+/// Returns module-level `//!` comment if present, else first `///` block,
+/// else the first Javadoc/KDoc `/** */` block.
 pub fn extract_doc_comment(content: &str) -> Option<String> {
-    extract_module_doc(content).or_else(|| extract_first_item_doc(content))
+    extract_module_doc(content)
+        .or_else(|| extract_first_item_doc(content))
+        .or_else(|| extract_first_block_doc(content))
 }
 
 /// Extract module-level documentation (//! comments at file start).
@@ -51,6 +54,32 @@ fn extract_first_item_doc(content: &str) -> Option<String> {
     }
 }
 
+/// Extract the summary of the first `/** ... */` block, stopping at block tags.
+/// A leading Doxygen `@brief` marker is dropped.
+fn extract_first_block_doc(content: &str) -> Option<String> {
+    let start = content.find("/**")?;
+    let body = content.get(start + 3..)?;
+    let end = body.find("*/")?;
+    let doc_lines: Vec<&str> = body
+        .get(..end)?
+        .lines()
+        .map(|l| l.trim().trim_start_matches('*').trim())
+        .map(|l| {
+            l.strip_prefix("@brief")
+                .or_else(|| l.strip_prefix("\\brief"))
+                .map_or(l, str::trim)
+        })
+        .take_while(|l| !l.starts_with('@') && !l.starts_with('\\'))
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    if doc_lines.is_empty() {
+        None
+    } else {
+        Some(collapse_doc_lines(&doc_lines))
+    }
+}
+
 /// Collapse multiple doc lines into a single sentence.
 fn collapse_doc_lines(lines: &[&str]) -> String {
     let joined = lines.join(" ");
@@ -89,6 +118,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_block_doc() {
+        let content = "package a;\n\n/**\n * Loads user accounts.\n * Cached.\n * @author x\n */\npublic class Users {}";
+        assert_eq!(
+            extract_doc_comment(content),
+            Some("Loads user accounts.".into())
+        );
+    }
+
     #[test]
     fn test_no_doc() {
         let content = "use std::io;\npub fn parse() {}";
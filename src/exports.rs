@@ -1,18 +1,26 @@
+use crate::lang_c;
+use crate::lang_jvm;
+use crate::lang_swift;
 use std::collections::BTreeSet;
 use std::fs;
 use std::path::Path;
 
 pub fn extract_exports(file: &Path) -> Option<Vec<String>> {
     let content = fs::read_to_string(file).ok()?;
-    let mut exports = BTreeSet::new();
+    let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("");
 
-    for line in content.lines() {
-        let trimmed = line.trim();
-
-        if let Some(name) = try_extract_pub_item(trimmed) {
-            exports.insert(name);
+    let exports: BTreeSet<String> = match ext {
+        "java" => lang_jvm::java_exports(&content).into_iter().collect(),
+        "kt" => lang_jvm::kotlin_exports(&content).into_iter().collect(),
+        "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => {
+            lang_c::extract_exports(&content).into_iter().collect()
         }
-    }
+        "swift" => lang_swift::extract_exports(&content).into_iter().collect(),
+        _ => content
+            .lines()
+            .filter_map(|line| try_extract_pub_item(line.trim()))
+            .collect(),
+    };
 
     if exports.is_empty() {
         None
@@ -0,0 +1,223 @@
+//! External packages: the third-party crates, npm and pip packages that
+//! mapped files import, with versions from the nearest manifest.
+//!
+//! Rust crates are only recognised when declared in a `Cargo.toml`, since a
+//! `name::` path cannot otherwise be told apart from a local module.
+
+mod manifests;
+
+pub use manifests::Ecosystem;
+
+use crate::js_resolve::JsResolver;
+use crate::lang_js;
+use crate::lang_python::{self, PyImport};
+use crate::python_resolve::PythonResolver;
+use crate::python_stdlib;
+use crate::types::SemmapFile;
+use manifests::{Manifest, Manifests};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static RUST_PATH_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+fn rust_path_regex() -> Option<&'static Regex> {
+    RUST_PATH_RE
+        .get_or_init(|| Regex::new(r"(?:^|[^\w:])(\w+)::|\bextern\s+crate\s+(\w+)").ok())
+        .as_ref()
+}
+
+/// Node modules that ship with the runtime.
+const NODE_BUILTINS: [&str; 31] = [
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "crypto",
+    "dgram",
+    "dns",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "querystring",
+    "readline",
+    "stream",
+    "string_decoder",
+    "timers",
+    "tls",
+    "tty",
+    "url",
+    "util",
+    "zlib",
+];
+
+/// A third-party package at one declared version and the mapped files that
+/// use it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExternalPackage {
+    /// The name declared in the manifest, or the imported name if undeclared.
+    pub name: String,
+    pub ecosystem: Ecosystem,
+    /// Version requirement from the manifest; `None` when undeclared.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub files: Vec<String>,
+}
+
+/// Every external package, and for each mapped file the packages it uses.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Inventory {
+    pub packages: Vec<ExternalPackage>,
+    pub files: BTreeMap<String, Vec<String>>,
+}
+
+impl Inventory {
+    pub fn package(&self, name: &str) -> Option<&ExternalPackage> {
+        self.packages.iter().find(|p| p.name == name)
+    }
+}
+
+pub fn inventory(root: &Path, semmap: &SemmapFile) -> Inventory {
+    let js = JsResolver::load(root);
+    let python = PythonResolver::load(root);
+    let mut manifests = Manifests::new(root);
+    // Keyed by version too: manifests may declare one package at different versions
+    let mut packages: BTreeMap<(String, Ecosystem, Option<String>), ExternalPackage> =
+        BTreeMap::new();
+    let mut files = BTreeMap::new();
+
+    for path in semmap.all_paths() {
+        let Some(ecosystem) = Ecosystem::of(path) else {
+            continue;
+        };
+        let Ok(content) = fs::read_to_string(root.join(path)) else {
+            continue;
+        };
+        let manifest = manifests.nearest(path, ecosystem);
+        let imported = match ecosystem {
+            Ecosystem::Cargo => rust_crates(&content, manifest),
+            Ecosystem::Npm => js_packages(&content, path, &js),
+            Ecosystem::Pypi => python_packages(&content, path, &python),
+        };
+        let mut used = BTreeSet::new();
+        for import in imported {
+            let declared = manifest.lookup(&import);
+            let name = declared.map_or(import.clone(), |(name, _)| name.to_string());
+            let version = declared.map(|(_, version)| version.to_string());
+            let package = packages
+                .entry((name.clone(), ecosystem, version.clone()))
+                .or_insert_with(|| ExternalPackage {
+                    name: name.clone(),
+                    ecosystem,
+                    version,
+                    files: Vec::new(),
+                });
+            package.files.push(path.to_string());
+            used.insert(name);
+        }
+        if !used.is_empty() {
+            files.insert(path.to_string(), used.into_iter().collect());
+        }
+    }
+    Inventory {
+        packages: packages.into_values().collect(),
+        files,
+    }
+}
+
+/// Declared crates referenced as a path root (`regex::Regex`) or `extern crate`.
+fn rust_crates(content: &str, manifest: &Manifest) -> BTreeSet<String> {
+    let Some(re) = rust_path_regex() else {
+        return BTreeSet::new();
+    };
+    content
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or(line))
+        .flat_map(|line| re.captures_iter(line))
+        .filter_map(|cap| cap.get(1).or_else(|| cap.get(2)))
+        .map(|m| m.as_str())
+        .filter(|name| manifest.lookup(name).is_some())
+        .map(String::from)
+        .collect()
+}
+
+/// Bare specifiers that are neither builtins nor resolvable project paths.
+fn js_packages(content: &str, path: &str, resolver: &JsResolver) -> BTreeSet<String> {
+    lang_js::extract_specifiers(content)
+        .into_iter()
+        .map(|(specifier, _)| specifier)
+        .filter(|s| !s.starts_with(['.', '/', '#']) && !s.contains(':'))
+        .filter(|s| resolver.resolve(path, s).is_none())
+        .filter_map(|s| js_package_name(&s))
+        .filter(|name| !NODE_BUILTINS.contains(&name.as_str()))
+        .collect()
+}
+
+/// `@scope/pkg/sub` -> `@scope/pkg`, `pkg/sub` -> `pkg`.
+fn js_package_name(specifier: &str) -> Option<String> {
+    let mut segments = specifier.split('/');
+    let first = segments.next().filter(|s| !s.is_empty())?;
+    if first.starts_with('@') {
+        let name = segments.next().filter(|s| !s.is_empty())?;
+        return Some(format!("{first}/{name}"));
+    }
+    Some(first.to_string())
+}
+
+/// Top-level modules of absolute imports that are not stdlib or project code.
+fn python_packages(content: &str, path: &str, resolver: &PythonResolver) -> BTreeSet<String> {
+    lang_python::parse_imports(content)
+        .into_iter()
+        .filter_map(|import| match import {
+            PyImport::Module(module)
+            | PyImport::From {
+                level: 0, module, ..
+            } => Some(module),
+            PyImport::From { .. } => None,
+        })
+        .filter(|module| !python_stdlib::is_stdlib(module))
+        .filter_map(|module| module.split('.').next().map(String::from))
+        .filter(|top| !top.is_empty() && resolver.resolve_absolute(path, top).is_none())
+        .collect()
+}
+
+/// Packages with their versions and users, or with `by_file` each file's packages.
+pub fn render_text(inventory: &Inventory, by_file: bool) -> String {
+    let mut out = String::new();
+    if by_file {
+        for (file, packages) in &inventory.files {
+            let _ = writeln!(out, "{file}: {}", packages.join(", "));
+        }
+        return out;
+    }
+    let _ = writeln!(
+        out,
+        "{} external packages used by {} files",
+        inventory.packages.len(),
+        inventory.files.len()
+    );
+    for package in &inventory.packages {
+        let version = package.version.as_deref().unwrap_or("undeclared");
+        let ecosystem = package.ecosystem.name();
+        let _ = writeln!(out, "\n{} {version} ({ecosystem})", package.name);
+        for file in &package.files {
+            let _ = writeln!(out, "  {file}");
+        }
+    }
+    out
+}
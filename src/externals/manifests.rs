@@ -0,0 +1,223 @@
+//! Declared dependencies from `Cargo.toml`, `package.json` and `pyproject.toml`.
+
+use crate::path_utils;
+use serde::Serialize;
+use serde_json::Value as Json;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::Value as Toml;
+
+const CARGO_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+const NPM_SECTIONS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+/// Package registry a source file draws its externals from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Pypi,
+}
+
+impl Ecosystem {
+    /// The ecosystem of a source file, by extension.
+    pub fn of(path: &str) -> Option<Self> {
+        match Path::new(path).extension()?.to_str()? {
+            "rs" => Some(Self::Cargo),
+            "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::Npm),
+            "py" => Some(Self::Pypi),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::Npm => "npm",
+            Self::Pypi => "pypi",
+        }
+    }
+
+    fn manifest(self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo.toml",
+            Self::Npm => "package.json",
+            Self::Pypi => "pyproject.toml",
+        }
+    }
+
+    /// Crates are imported with `_` for `-`; Python names also ignore case and `.`.
+    fn normalize(self, name: &str) -> String {
+        match self {
+            Self::Cargo => name.replace('-', "_"),
+            Self::Npm => name.to_string(),
+            Self::Pypi => name.to_lowercase().replace(['-', '.'], "_"),
+        }
+    }
+}
+
+/// One manifest's declared packages, keyed by their normalized import name.
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    ecosystem: Option<Ecosystem>,
+    packages: HashMap<String, (String, String)>,
+}
+
+impl Manifest {
+    /// The declared name and version requirement of an imported package.
+    pub fn lookup(&self, import: &str) -> Option<(&str, &str)> {
+        let key = self.ecosystem?.normalize(import);
+        self.packages
+            .get(&key)
+            .map(|(name, version)| (name.as_str(), version.as_str()))
+    }
+
+    fn parse(ecosystem: Ecosystem, content: &str) -> Self {
+        let declared = match ecosystem {
+            Ecosystem::Cargo => cargo(content),
+            Ecosystem::Npm => npm(content),
+            Ecosystem::Pypi => pyproject(content),
+        };
+        Self {
+            ecosystem: Some(ecosystem),
+            packages: declared
+                .into_iter()
+                .map(|(name, version)| (ecosystem.normalize(&name), (name, version)))
+                .collect(),
+        }
+    }
+}
+
+/// Finds and caches the manifest nearest to each source file.
+pub struct Manifests {
+    root: PathBuf,
+    loaded: HashMap<String, Manifest>,
+}
+
+impl Manifests {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            loaded: HashMap::new(),
+        }
+    }
+
+    /// The manifest in `path`'s directory or the closest one above it;
+    /// empty when there is none up to the root.
+    pub fn nearest(&mut self, path: &str, ecosystem: Ecosystem) -> &Manifest {
+        let mut dir = path_utils::parent_dir(path);
+        let found = loop {
+            let candidate = path_utils::prefix_path(dir, ecosystem.manifest());
+            if self.root.join(&candidate).is_file() {
+                break candidate;
+            }
+            if dir.is_empty() {
+                break String::new();
+            }
+            dir = path_utils::parent_dir(dir);
+        };
+        let root = &self.root;
+        self.loaded.entry(found).or_insert_with_key(|manifest| {
+            fs::read_to_string(root.join(manifest))
+                .map(|content| Manifest::parse(ecosystem, &content))
+                .unwrap_or_default()
+        })
+    }
+}
+
+fn cargo(content: &str) -> Vec<(String, String)> {
+    let Ok(doc) = content.parse::<Toml>() else {
+        return Vec::new();
+    };
+    let workspace = doc.get("workspace").and_then(|w| w.get("dependencies"));
+    CARGO_SECTIONS
+        .iter()
+        .filter_map(|section| doc.get(*section))
+        .chain(workspace)
+        .filter_map(Toml::as_table)
+        .flatten()
+        .map(|(name, spec)| (name.clone(), cargo_version(spec)))
+        .collect()
+}
+
+/// `"1.0"`, `{ version = "1.0" }`, or where the crate comes from instead.
+fn cargo_version(spec: &Toml) -> String {
+    if let Some(version) = spec.as_str().or_else(|| spec.get("version")?.as_str()) {
+        return version.to_string();
+    }
+    ["workspace", "path", "git"]
+        .into_iter()
+        .find(|key| spec.get(*key).is_some())
+        .unwrap_or("*")
+        .to_string()
+}
+
+fn npm(content: &str) -> Vec<(String, String)> {
+    let Ok(doc) = serde_json::from_str::<Json>(content) else {
+        return Vec::new();
+    };
+    NPM_SECTIONS
+        .iter()
+        .filter_map(|section| doc.get(*section)?.as_object())
+        .flatten()
+        .map(|(name, version)| (name.clone(), version.as_str().unwrap_or("*").to_string()))
+        .collect()
+}
+
+fn pyproject(content: &str) -> Vec<(String, String)> {
+    let Ok(doc) = content.parse::<Toml>() else {
+        return Vec::new();
+    };
+    let project = doc.get("project");
+    let required = project.and_then(|p| p.get("dependencies")?.as_array());
+    let optional = project
+        .and_then(|p| p.get("optional-dependencies")?.as_table())
+        .into_iter()
+        .flat_map(|groups| groups.values().filter_map(Toml::as_array));
+    let mut declared: Vec<(String, String)> = required
+        .into_iter()
+        .chain(optional)
+        .flatten()
+        .filter_map(|req| pep508(req.as_str()?))
+        .collect();
+
+    let poetry = doc.get("tool").and_then(|t| t.get("poetry"));
+    let groups = poetry
+        .and_then(|p| p.get("group")?.as_table())
+        .into_iter()
+        .flat_map(|groups| groups.values().filter_map(|g| g.get("dependencies")));
+    let tables = ["dependencies", "dev-dependencies"]
+        .iter()
+        .filter_map(|section| poetry?.get(*section))
+        .chain(groups)
+        .filter_map(Toml::as_table)
+        .flatten()
+        .filter(|(name, _)| name.as_str() != "python");
+    declared.extend(tables.map(|(name, spec)| (name.clone(), cargo_version(spec))));
+    declared
+}
+
+/// Splits `requests[socks] >=2.0; python_version<"3.12"` into name and version.
+fn pep508(requirement: &str) -> Option<(String, String)> {
+    let requirement = requirement.split(';').next()?.trim();
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    let (name, rest) = requirement.split_at(end);
+    if name.is_empty() {
+        return None;
+    }
+    let rest = match rest.trim_start().strip_prefix('[') {
+        Some(extras) => extras.split_once(']').map_or("", |(_, after)| after),
+        None => rest,
+    };
+    let version = rest.trim().trim_matches(|c| c == '(' || c == ')').trim();
+    let version = if version.is_empty() { "*" } else { version };
+    Some((name.to_string(), version.to_string()))
+}
@@ -0,0 +1,94 @@
+//! Facade files: modules that only re-export others (`mod`/`pub use`,
+//! `export * from`, `from .x import y`). Layer checks look through them.
+
+use crate::path_utils;
+use crate::types::SemmapFile;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static RUST_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static JS_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static PY_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+/// Comments, attributes and re-export statements, in that order of alternatives.
+fn rust_regex() -> Option<&'static Regex> {
+    RUST_RE
+        .get_or_init(|| {
+            Regex::new(
+                r"//[^\n]*|/\*(?s:.*?)\*/|#!?\[[^\]]*\]|(?:pub(?:\([^)]*\))?\s+)?mod\s+\w+\s*;|pub(?:\([^)]*\))?\s+use\s+[^;]+;",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+fn js_regex() -> Option<&'static Regex> {
+    JS_RE
+        .get_or_init(|| {
+            Regex::new(
+                r#"//[^\n]*|/\*(?s:.*?)\*/|export\s+(?:type\s+)?(?:\*(?:\s+as\s+\w+)?|\{[^}]*\})\s+from\s+['"][^'"]+['"]\s*;?"#,
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+fn py_regex() -> Option<&'static Regex> {
+    PY_RE
+        .get_or_init(|| {
+            Regex::new(
+                r#"#[^\n]*|"""(?s:.*?)"""|from\s+\.[\w.]*\s+import\s+(?:\([^)]*\)|[^\n]+)|__all__\s*=\s*[\[(][^\])]*[\])]"#,
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+/// Mapped files that match a configured glob or only re-export other modules.
+pub fn detect(root: &Path, semmap: &SemmapFile, globs: &[String]) -> HashSet<String> {
+    semmap
+        .all_paths()
+        .into_iter()
+        .filter(|path| {
+            globs.iter().any(|g| path_utils::glob_match(g, path))
+                || fs::read_to_string(root.join(path)).is_ok_and(|c| is_facade(&c, path))
+        })
+        .map(String::from)
+        .collect()
+}
+
+/// True if `content` has at least one re-export and nothing but re-exports,
+/// comments and attributes.
+pub fn is_facade(content: &str, path: &str) -> bool {
+    let ext = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    let re = match ext {
+        "rs" => rust_regex(),
+        "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => js_regex(),
+        "py" => py_regex(),
+        _ => None,
+    };
+    let Some(re) = re else {
+        return false;
+    };
+    let mut reexports = 0;
+    let rest = re.replace_all(content, |caps: &regex::Captures| {
+        let text = caps.get(0).map_or("", |m| m.as_str());
+        if !is_comment(text) {
+            reexports += 1;
+        }
+        ""
+    });
+    reexports > 0 && rest.trim().is_empty()
+}
+
+fn is_comment(text: &str) -> bool {
+    ["//", "/*", "#", "\"\"\""]
+        .iter()
+        .any(|p| text.starts_with(p))
+}
@@ -36,7 +36,11 @@ fn write_layers(out: &mut String, semmap: &SemmapFile) {
         let _ = writeln!(out, "## Layer {} -- {}\n", layer.number, layer.name);
 
         for entry in &layer.entries {
-            let _ = writeln!(out, "`{}`", entry.path);
+            let tags = entry.tags.iter().fold(String::new(), |mut tags, t| {
+                let _ = write!(tags, " `[{t}]`");
+                tags
+            });
+            let _ = writeln!(out, "`{}`{tags}", entry.path);
 
             let desc = format_description(&entry.description);
             let _ = writeln!(out, "{desc}");
@@ -51,6 +55,14 @@ fn write_layers(out: &mut String, semmap: &SemmapFile) {
                 let _ = writeln!(out, "→ Touch: {touch}");
             }
 
+            if let Some(depends) = &entry.depends {
+                let _ = writeln!(out, "→ Depends: {}", depends.join(", "));
+            }
+
+            for allow in &entry.allows {
+                let _ = writeln!(out, "→ Allow: {} -- {}", allow.target, allow.reason);
+            }
+
             out.push('\n');
         }
     }
@@ -1,7 +1,11 @@
+use crate::deps::AnalyzeOptions;
 use crate::exports;
+use crate::graph_layers;
 use crate::inference;
+use crate::path_utils;
+use crate::swift_package::SwiftPackage;
 use crate::types::{FileEntry, Layer, LegendEntry, SemmapFile};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 use walkdir::WalkDir;
@@ -10,7 +14,42 @@ pub struct GeneratorConfig {
     pub project_name: String,
     pub purpose: String,
     pub include_exts: Vec<String>,
+    /// Exact file names collected regardless of extension (e.g. `pom.xml`).
+    pub include_files: Vec<String>,
     pub exclude_dirs: Vec<String>,
+    /// Globs (relative to the root) a file must match, if any are given.
+    pub include: Vec<String>,
+    /// Globs of files to leave out.
+    pub exclude: Vec<String>,
+    pub layering: Layering,
+    /// Layer names replacing the defaults.
+    pub layer_names: BTreeMap<u8, String>,
+    /// Globs whose files go to the given layer; the longest match wins.
+    pub layer_overrides: BTreeMap<String, u8>,
+    /// Facade globs, which `Layering::Graph` leaves out of layer constraints.
+    pub facades: Vec<String>,
+    /// How `Layering::Graph` builds the dependency graph.
+    pub analysis: AnalyzeOptions,
+}
+
+/// How `generate` assigns files to layers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Layering {
+    /// Stereotype and path heuristics per file.
+    #[default]
+    Heuristic,
+    /// Depth in the dependency graph, blended with the heuristics.
+    Graph,
+}
+
+impl Layering {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "heuristic" => Some(Self::Heuristic),
+            "graph" => Some(Self::Graph),
+            _ => None,
+        }
+    }
 }
 
 impl Default for GeneratorConfig {
@@ -18,12 +57,18 @@ impl Default for GeneratorConfig {
         Self {
             project_name: String::new(),
             purpose: String::new(),
-            include_exts: vec!["rs", "ts", "js", "py", "go", "java", "toml", "yaml", "json"]
-                .into_iter()
-                .map(String::from)
-                .collect(),
+            include_exts: vec![
+                "rs", "ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "py", "go", "java",
+                "kt", "kts", "gradle", "c", "h", "cc", "cpp", "cxx", "hpp", "hh", "hxx", "swift",
+                "toml", "yaml", "json",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            include_files: vec!["pom.xml".to_string(), "gradle.properties".to_string()],
             exclude_dirs: vec![
                 ".git",
+                ".gradle",
                 "target",
                 "node_modules",
                 "dist",
@@ -33,25 +78,51 @@ impl Default for GeneratorConfig {
             .into_iter()
             .map(String::from)
             .collect(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            layering: Layering::default(),
+            layer_names: BTreeMap::new(),
+            layer_overrides: BTreeMap::new(),
+            facades: Vec::new(),
+            analysis: AnalyzeOptions::default(),
         }
     }
 }
 
+impl GeneratorConfig {
+    /// The configured name of a layer, else the default for the layering.
+    pub fn layer_name(&self, number: u8) -> &str {
+        let defaults = match self.layering {
+            Layering::Heuristic => &HEURISTIC_NAMES,
+            Layering::Graph => &graph_layers::LAYER_NAMES,
+        };
+        self.layer_names
+            .get(&number)
+            .map(String::as_str)
+            .or_else(|| defaults.get(usize::from(number)).copied())
+            .unwrap_or("Other")
+    }
+}
+
 pub fn generate(root: &Path, config: GeneratorConfig) -> SemmapFile {
     let files = collect_files(root, &config);
     let classified = classify_by_layer(&files, root);
 
-    let project_name = if config.project_name.is_empty() {
+    let mut semmap = SemmapFile::new(String::new(), String::new());
+    semmap.legend = default_legend();
+    semmap.layers = build_layers(classified, &config);
+    if config.layering == Layering::Graph {
+        let classified = graph_layers::classify(root, &semmap, &config.facades, &config.analysis);
+        semmap.layers = build_layers(classified, &config);
+    }
+
+    semmap.project_name = if config.project_name.is_empty() {
         root.file_name()
             .map_or_else(|| "project".into(), |n| n.to_string_lossy().to_string())
     } else {
         config.project_name
     };
-
-    let mut semmap = SemmapFile::new(project_name, config.purpose);
-    semmap.legend = default_legend();
-    semmap.layers = build_layers(&classified);
-
+    semmap.purpose = config.purpose;
     semmap
 }
 
@@ -72,8 +143,22 @@ fn collect_files(root: &Path, config: &GeneratorConfig) -> Vec<std::path::PathBu
             .extension()
             .and_then(|e| e.to_str())
             .is_some_and(|e| config.include_exts.iter().any(|inc| inc == e));
+        let matches_name = config
+            .include_files
+            .iter()
+            .any(|name| entry.file_name() == name.as_str());
 
-        if matches_ext {
+        let rel_path = relative(entry.path(), root);
+        let included = config.include.is_empty()
+            || config
+                .include
+                .iter()
+                .any(|g| path_utils::glob_match(g, &rel_path));
+        let excluded = config
+            .exclude
+            .iter()
+            .any(|g| path_utils::glob_match(g, &rel_path));
+        if (matches_ext || matches_name) && included && !excluded {
             files.push(entry.path().to_path_buf());
         }
     }
@@ -93,15 +178,16 @@ fn is_excluded(entry: &walkdir::DirEntry, excludes: &[String]) -> bool {
 
 fn classify_by_layer(files: &[std::path::PathBuf], root: &Path) -> HashMap<u8, Vec<FileEntry>> {
     let mut layers: HashMap<u8, Vec<FileEntry>> = HashMap::new();
+    let swift_package = SwiftPackage::load(root);
 
     for file in files {
-        let rel_path = file
-            .strip_prefix(root)
-            .map(|p| p.to_string_lossy().replace('\\', "/"))
-            .unwrap_or_default();
+        let rel_path = relative(file, root);
 
         let content = fs::read_to_string(file).unwrap_or_default();
-        let layer = inference::infer_layer(&rel_path, file, &content);
+        let layer = swift_package
+            .as_ref()
+            .and_then(|p| p.layer_for(&rel_path))
+            .unwrap_or_else(|| inference::infer_layer(&rel_path, file, &content));
         let entry = create_entry(&rel_path, file, &content);
 
         layers.entry(layer).or_default().push(entry);
@@ -120,26 +206,63 @@ fn create_entry(rel_path: &str, file: &Path, content: &str) -> FileEntry {
     entry
 }
 
-fn build_layers(classified: &HashMap<u8, Vec<FileEntry>>) -> Vec<Layer> {
-    let names = ["Config", "Core", "Domain", "Utilities", "Tests"];
-    let mut layers = Vec::new();
+fn relative(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default()
+}
+
+const HEURISTIC_NAMES: [&str; 5] = ["Config", "Core", "Domain", "Utilities", "Tests"];
 
-    for num in 0..5u8 {
-        if let Some(entries) = classified.get(&num) {
-            if !entries.is_empty() {
-                let name = names.get(num as usize).copied().unwrap_or("Other");
-                let mut layer = Layer::new(num, (*name).to_string());
-                layer.entries.clone_from(entries);
-                // Sort entries by path for deterministic output
-                layer.entries.sort_by(|a, b| a.path.cmp(&b.path));
-                layers.push(layer);
-            }
+/// Layers in order, after moving files matched by a layer override.
+fn build_layers(
+    mut classified: HashMap<u8, Vec<FileEntry>>,
+    config: &GeneratorConfig,
+) -> Vec<Layer> {
+    if !config.layer_overrides.is_empty() {
+        let mut moved: Vec<(u8, FileEntry)> = Vec::new();
+        for (&number, entries) in &mut classified {
+            let (keep, leave): (Vec<_>, Vec<_>) = entries
+                .drain(..)
+                .partition(|e| layer_override(config, &e.path).unwrap_or(number) == number);
+            *entries = keep;
+            moved.extend(
+                leave
+                    .into_iter()
+                    .filter_map(|e| Some((layer_override(config, &e.path)?, e))),
+            );
+        }
+        for (number, entry) in moved {
+            classified.entry(number).or_default().push(entry);
+        }
+    }
+
+    let mut numbers: Vec<u8> = classified.keys().copied().collect();
+    numbers.sort_unstable();
+    let mut layers = Vec::new();
+    for num in numbers {
+        if let Some(entries) = classified.remove(&num).filter(|e| !e.is_empty()) {
+            let mut layer = Layer::new(num, config.layer_name(num).to_string());
+            layer.entries = entries;
+            // Sort entries by path for deterministic output
+            layer.entries.sort_by(|a, b| a.path.cmp(&b.path));
+            layers.push(layer);
         }
     }
 
     layers
 }
 
+/// The layer of the longest override glob matching `path`.
+fn layer_override(config: &GeneratorConfig, path: &str) -> Option<u8> {
+    config
+        .layer_overrides
+        .iter()
+        .filter(|(glob, _)| glob.as_str() == path || path_utils::glob_match(glob, path))
+        .max_by_key(|(glob, _)| glob.len())
+        .map(|(_, &layer)| layer)
+}
+
 fn default_legend() -> Vec<LegendEntry> {
     vec![
         LegendEntry {
@@ -0,0 +1,218 @@
+//! Dependency graph changes between a git revision and the working tree:
+//! added and removed edges, and the violations, cycles and external
+//! packages the working tree introduces.
+
+mod git;
+
+pub use git::Snapshot;
+
+use crate::cycles::{self, Cycle};
+use crate::deps::{self, AnalyzeOptions};
+use crate::externals::{self, ExternalPackage, Inventory};
+use crate::layer_check::LayerViolation;
+use crate::types::{DepEdge, DepKind, DependencyMap, SemmapFile};
+use crate::{facades, path_utils};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Manifests the analyzers read besides mapped files.
+const ANALYZER_INPUTS: [&str; 7] = [
+    "Cargo.toml",
+    "package.json",
+    "tsconfig*.json",
+    "jsconfig.json",
+    "pyproject.toml",
+    "Package.swift",
+    "compile_commands.json",
+];
+
+/// Whether `path` is a manifest analysis reads, so a snapshot needs it
+/// besides the mapped files.
+pub fn is_analyzer_input(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    ANALYZER_INPUTS
+        .iter()
+        .any(|pattern| path_utils::glob_match(pattern, name))
+}
+
+/// Everything `deps --diff` compares, for one side.
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    pub depmap: DependencyMap,
+    pub violations: Vec<LayerViolation>,
+    pub cycles: Vec<Cycle>,
+    pub externals: Inventory,
+}
+
+impl Analysis {
+    /// Analyzes the files under `root`; both sides of a diff take the working
+    /// tree's `facade_globs`.
+    pub fn compute(
+        root: &Path,
+        semmap: &SemmapFile,
+        options: &AnalyzeOptions,
+        facade_globs: &[String],
+    ) -> Self {
+        let depmap = deps::analyze_with(root, semmap, options);
+        let facades = facades::detect(root, semmap, facade_globs);
+        let violations = deps::check_layer_violations_with(&depmap, semmap, &facades);
+        let cycles = cycles::find_cycles(&depmap);
+        Self {
+            violations,
+            cycles,
+            externals: externals::inventory(root, semmap),
+            depmap,
+        }
+    }
+}
+
+/// What changed from `revision` to the working tree.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GraphDiff {
+    pub revision: String,
+    pub added_edges: Vec<DepEdge>,
+    pub removed_edges: Vec<DepEdge>,
+    pub new_violations: Vec<LayerViolation>,
+    pub new_cycles: Vec<Cycle>,
+    pub new_externals: Vec<ExternalPackage>,
+}
+
+impl GraphDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.new_violations.is_empty()
+            && self.new_cycles.is_empty()
+            && self.new_externals.is_empty()
+    }
+}
+
+pub fn compare(revision: &str, old: &Analysis, new: &Analysis) -> GraphDiff {
+    let old_violations: HashSet<(&str, &str)> = old
+        .violations
+        .iter()
+        .map(|v| (v.from.as_str(), v.to.as_str()))
+        .collect();
+    let old_cycles: HashSet<&[String]> = old.cycles.iter().map(|c| c.files.as_slice()).collect();
+    let old_externals: HashSet<(&str, _)> = old
+        .externals
+        .packages
+        .iter()
+        .map(|p| (p.name.as_str(), p.ecosystem))
+        .collect();
+    GraphDiff {
+        revision: revision.to_string(),
+        added_edges: missing_edges(&new.depmap, &old.depmap),
+        removed_edges: missing_edges(&old.depmap, &new.depmap),
+        new_violations: new
+            .violations
+            .iter()
+            .filter(|v| !old_violations.contains(&(v.from.as_str(), v.to.as_str())))
+            .cloned()
+            .collect(),
+        new_cycles: new
+            .cycles
+            .iter()
+            .filter(|c| !old_cycles.contains(c.files.as_slice()))
+            .cloned()
+            .collect(),
+        new_externals: new
+            .externals
+            .packages
+            .iter()
+            .filter(|p| !old_externals.contains(&(p.name.as_str(), p.ecosystem)))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Edges of `from` that `other` lacks, comparing endpoints and kind.
+fn missing_edges(from: &DependencyMap, other: &DependencyMap) -> Vec<DepEdge> {
+    let present: HashSet<(&str, &str, DepKind)> = other
+        .edges
+        .iter()
+        .map(|e| (e.from.as_str(), e.to.as_str(), e.kind))
+        .collect();
+    from.edges
+        .iter()
+        .filter(|e| !present.contains(&(e.from.as_str(), e.to.as_str(), e.kind)))
+        .cloned()
+        .collect()
+}
+
+/// The working-tree graph plus the links (and files) only `old` has, for
+/// drawing both sides in one diagram.
+pub fn merged(old: &DependencyMap, new: &DependencyMap) -> DependencyMap {
+    let mut merged = new.clone();
+    let known: HashSet<&str> = new.nodes.iter().map(|n| n.path.as_str()).collect();
+    let linked: HashSet<(&str, &str)> = new
+        .edges
+        .iter()
+        .map(|e| (e.from.as_str(), e.to.as_str()))
+        .collect();
+    let removed: Vec<DepEdge> = missing_edges(old, new)
+        .into_iter()
+        .filter(|e| !linked.contains(&(e.from.as_str(), e.to.as_str())))
+        .collect();
+    let endpoints: HashSet<&str> = removed
+        .iter()
+        .flat_map(|e| [e.from.as_str(), e.to.as_str()])
+        .collect();
+    merged.nodes.extend(
+        old.nodes
+            .iter()
+            .filter(|n| endpoints.contains(n.path.as_str()) && !known.contains(n.path.as_str()))
+            .cloned(),
+    );
+    merged.edges.extend(removed);
+    merged
+}
+
+pub fn render_text(diff: &GraphDiff) -> String {
+    let mut out = String::new();
+    if diff.is_empty() {
+        let _ = writeln!(out, "* No dependency changes since {}", diff.revision);
+        return out;
+    }
+    let _ = writeln!(
+        out,
+        "Dependency changes since {}: +{} -{} edges",
+        diff.revision,
+        diff.added_edges.len(),
+        diff.removed_edges.len()
+    );
+    for (sign, edges) in [("+", &diff.added_edges), ("-", &diff.removed_edges)] {
+        for edge in edges {
+            let _ = writeln!(
+                out,
+                "{sign} {} -> {} ({})",
+                edge.from,
+                edge.to,
+                edge.kind.name()
+            );
+        }
+    }
+    if !diff.new_violations.is_empty() {
+        let _ = writeln!(out, "\nNew layer violations:");
+        for v in &diff.new_violations {
+            let _ = writeln!(out, "X {v}\n    fix: {}", v.suggestion);
+        }
+    }
+    if !diff.new_cycles.is_empty() {
+        let _ = writeln!(out, "\nNew dependency cycles:");
+        for cycle in &diff.new_cycles {
+            let _ = writeln!(out, "X Cycle: {cycle}");
+        }
+    }
+    if !diff.new_externals.is_empty() {
+        let _ = writeln!(out, "\nNew external packages:");
+        for package in &diff.new_externals {
+            let version = package.version.as_deref().unwrap_or("undeclared");
+            let files = package.files.join(", ");
+            let _ = writeln!(out, "+ {} {version}: {files}", package.name);
+        }
+    }
+    out
+}
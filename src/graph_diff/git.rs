@@ -0,0 +1,163 @@
+//! Reads a revision's files straight from the object database into a
+//! temporary directory, leaving the working tree and index untouched. Only
+//! the files asked for are written, never the whole tree.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SNAPSHOTS: AtomicUsize = AtomicUsize::new(0);
+
+/// A revision's files under `root`, written on request into a directory
+/// that is removed again when dropped.
+pub struct Snapshot {
+    root: PathBuf,
+    dir: PathBuf,
+    /// `(blob id, path)` of every file not written yet.
+    pending: Vec<(String, String)>,
+}
+
+impl Snapshot {
+    /// Lists the files of `rev` below `root`'s directory in the repository;
+    /// nothing is written until `write`.
+    pub fn open(root: &Path, rev: &str) -> Result<Self, String> {
+        let commit = format!("{rev}^{{commit}}");
+        git(
+            root,
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                "--end-of-options",
+                &commit,
+            ],
+        )
+        .map_err(|_| format!("Unknown git revision: {rev}"))?;
+        let prefix = String::from_utf8_lossy(&git(root, &["rev-parse", "--show-prefix"])?)
+            .trim()
+            .to_string();
+        let listing = git(
+            root,
+            &[
+                "ls-tree",
+                "-r",
+                "-z",
+                "--full-tree",
+                "--end-of-options",
+                rev,
+            ],
+        )?;
+        let pending = String::from_utf8_lossy(&listing)
+            .split('\0')
+            .filter_map(|line| blob_entry(line, &prefix))
+            .collect();
+
+        let name = format!(
+            "semmap-{}-{}",
+            std::process::id(),
+            SNAPSHOTS.fetch_add(1, Ordering::Relaxed)
+        );
+        let snapshot = Self {
+            root: root.to_path_buf(),
+            dir: std::env::temp_dir().join(name),
+            pending,
+        };
+        fs::create_dir_all(&snapshot.dir).map_err(|e| format!("Failed to create snapshot: {e}"))?;
+        Ok(snapshot)
+    }
+
+    /// Writes the listed files whose path `wanted` accepts.
+    pub fn write(&mut self, wanted: impl Fn(&str) -> bool) -> Result<(), String> {
+        let (blobs, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|(_, path)| wanted(path));
+        self.pending = rest;
+        let ids: Vec<&str> = blobs.iter().map(|(id, _)| id.as_str()).collect();
+        for ((_, path), content) in blobs.iter().zip(cat_blobs(&self.root, &ids)?) {
+            let target = self.dir.join(path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to write {path}: {e}"))?;
+            }
+            fs::write(&target, content).map_err(|e| format!("Failed to write {path}: {e}"))?;
+        }
+        Ok(())
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for Snapshot {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// `<mode> blob <id>\t<path>` -> `(id, path relative to prefix)`; symlinks
+/// and submodules are skipped.
+fn blob_entry(line: &str, prefix: &str) -> Option<(String, String)> {
+    let (meta, path) = line.split_once('\t')?;
+    let mut fields = meta.split(' ');
+    let mode = fields.next()?;
+    if fields.next()? != "blob" || mode == "120000" {
+        return None;
+    }
+    let relative = path.strip_prefix(prefix)?;
+    Some((fields.next()?.to_string(), relative.to_string()))
+}
+
+/// Contents of the given blobs, in order, through one `git cat-file --batch`.
+fn cat_blobs(root: &Path, ids: &[&str]) -> Result<Vec<Vec<u8>>, String> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut child = Command::new("git")
+        .args(["cat-file", "--batch"])
+        .current_dir(root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    let input = ids.join("\n") + "\n";
+    let mut stdin = child.stdin.take().ok_or("Failed to write to git")?;
+    // Write from a thread so a full stdout pipe cannot block us
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    let _ = writer.join();
+
+    let mut rest = output.stdout.as_slice();
+    let mut blobs = Vec::with_capacity(ids.len());
+    while let Some(newline) = rest.iter().position(|b| *b == b'\n') {
+        let (header, body) = rest.split_at(newline);
+        let size: usize = String::from_utf8_lossy(header)
+            .rsplit(' ')
+            .next()
+            .and_then(|s| s.parse().ok())
+            .ok_or("Unexpected git cat-file output")?;
+        let content = body.get(1..=size).ok_or("Truncated git cat-file output")?;
+        blobs.push(content.to_vec());
+        rest = body.get(size + 2..).unwrap_or_default();
+    }
+    if blobs.len() != ids.len() {
+        return Err("Unexpected git cat-file output".into());
+    }
+    Ok(blobs)
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args.join(" "), stderr.trim()));
+    }
+    Ok(output.stdout)
+}
@@ -0,0 +1,161 @@
+//! Layers from the dependency graph, for `generate --layering graph`.
+//!
+//! Source files are placed by their depth in the condensation of the graph
+//! the layer check sees (leaves low, entry points high), nudged toward their
+//! stereotype, and never below anything they depend on. Files of a cycle
+//! share a layer; test files keep their heuristic layer, and config files stay
+//! in layer 0 unless they depend on code.
+
+use crate::deps::AnalyzeOptions;
+use crate::stereotype::{self, Stereotype};
+use crate::types::{FileEntry, SemmapFile};
+use crate::{deps, facades, layer_check, scc};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Layer names matching the graph's direction: foundations first.
+pub const LAYER_NAMES: [&str; 5] = ["Config", "Core", "Domain", "Application", "Tests"];
+
+const LOWEST: u8 = 1;
+const HIGHEST: u8 = 3;
+
+/// Entries of `semmap` (laid out by the heuristics) grouped by graph layer;
+/// files matching `facade_globs` are facades as in `deps --check`.
+pub fn classify(
+    root: &Path,
+    semmap: &SemmapFile,
+    facade_globs: &[String],
+    options: &AnalyzeOptions,
+) -> HashMap<u8, Vec<FileEntry>> {
+    let depmap = deps::analyze_with(root, semmap, options);
+    let facades = facades::detect(root, semmap, facade_globs);
+    let heuristic = semmap.path_to_layer();
+    let mut paths = semmap.all_paths();
+    paths.sort_unstable();
+    let index: HashMap<&str, usize> = paths.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+    let mut adjacent = vec![Vec::new(); paths.len()];
+    for (from, to) in layer_check::layer_constraints(&depmap, semmap, &facades) {
+        if let (Some(&f), Some(&t)) = (index.get(from.as_str()), index.get(to.as_str())) {
+            if let Some(successors) = adjacent.get_mut(f) {
+                successors.push(t);
+            }
+        }
+    }
+    let heuristic: Vec<u8> = paths
+        .iter()
+        .map(|p| heuristic.get(p).copied().unwrap_or(LOWEST))
+        .collect();
+    let hints: Vec<Option<u8>> = paths
+        .iter()
+        .map(|p| hint(p, &fs::read_to_string(root.join(p)).unwrap_or_default()))
+        .collect();
+
+    let layers = assign(&adjacent, &heuristic, &hints);
+    let mut classified: HashMap<u8, Vec<FileEntry>> = HashMap::new();
+    for entry in semmap.layers.iter().flat_map(|l| &l.entries) {
+        let layer = index
+            .get(entry.path.as_str())
+            .and_then(|&i| layers.get(i))
+            .copied()
+            .unwrap_or(LOWEST);
+        classified.entry(layer).or_default().push(entry.clone());
+    }
+    classified
+}
+
+/// Where a stereotype belongs when dependencies point down.
+fn hint(path: &str, content: &str) -> Option<u8> {
+    match stereotype::classify(path, content) {
+        Stereotype::Entrypoint | Stereotype::Cli => Some(HIGHEST),
+        Stereotype::Entity | Stereotype::Error | Stereotype::Utility => Some(LOWEST),
+        Stereotype::Parser
+        | Stereotype::Formatter
+        | Stereotype::Service
+        | Stereotype::Repository
+        | Stereotype::Handler => Some(2),
+        Stereotype::Config | Stereotype::Test | Stereotype::Unknown => None,
+    }
+}
+
+/// One layer per node: test files keep theirs; every other component takes
+/// its depth layer averaged with its strongest hint (config files prefer
+/// their own layer), raised to the highest layer it depends on.
+fn assign(adjacent: &[Vec<usize>], heuristic: &[u8], hints: &[Option<u8>]) -> Vec<u8> {
+    // Dependencies come first
+    let components = scc::strongly_connected(adjacent);
+    let mut component_of = vec![0; adjacent.len()];
+    for (c, members) in components.iter().enumerate() {
+        for &n in members {
+            if let Some(slot) = component_of.get_mut(n) {
+                *slot = c;
+            }
+        }
+    }
+    let component_of = component_of.as_slice();
+    let layer_of = |n: usize| heuristic.get(n).copied().unwrap_or(LOWEST);
+    let is_test = |n: usize| layer_of(n) > HIGHEST;
+    let successors = |c: usize| {
+        let members = components.get(c).map(Vec::as_slice).unwrap_or_default();
+        members
+            .iter()
+            .filter(|&&n| !is_test(n))
+            .flat_map(|&n| adjacent.get(n).into_iter().flatten().copied())
+            .filter(|&m| component_of.get(m) != Some(&c))
+            .collect::<Vec<_>>()
+    };
+    // Components of config files alone, which neither count toward depth nor
+    // take a depth layer
+    let config_only: Vec<bool> = components
+        .iter()
+        .map(|members| {
+            members
+                .iter()
+                .filter(|&&n| !is_test(n))
+                .all(|&n| layer_of(n) < LOWEST)
+        })
+        .collect();
+
+    let mut depth = vec![0usize; components.len()];
+    for c in 0..components.len() {
+        let deepest = successors(c)
+            .into_iter()
+            .filter_map(|m| component_of.get(m).copied())
+            .filter(|&d| !config_only.get(d).copied().unwrap_or(true))
+            .filter_map(|d| depth.get(d))
+            .map(|d| d + 1)
+            .max()
+            .unwrap_or(0);
+        if let Some(slot) = depth.get_mut(c) {
+            *slot = deepest;
+        }
+    }
+    let max_depth = depth.iter().copied().max().unwrap_or(0).max(1);
+
+    let mut layer: Vec<u8> = (0..adjacent.len()).map(layer_of).collect();
+    for (c, members) in components.iter().enumerate() {
+        let span = usize::from(HIGHEST - LOWEST);
+        let steps = (depth.get(c).copied().unwrap_or(0) * span + max_depth / 2) / max_depth;
+        let by_depth = LOWEST + u8::try_from(steps).unwrap_or(0);
+        let hint = members
+            .iter()
+            .filter_map(|&n| hints.get(n).copied().flatten())
+            .max();
+        let preferred = if config_only.get(c).copied().unwrap_or(false) {
+            0
+        } else {
+            hint.map_or(by_depth, |hint| (by_depth + hint).div_ceil(2))
+        };
+        let floor = successors(c)
+            .into_iter()
+            .filter_map(|m| layer.get(m).copied())
+            .max()
+            .unwrap_or(0);
+        for &n in members.iter().filter(|&&n| !is_test(n)) {
+            if let Some(slot) = layer.get_mut(n) {
+                *slot = preferred.max(floor);
+            }
+        }
+    }
+    layer
+}
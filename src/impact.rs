@@ -0,0 +1,148 @@
+//! Impact analysis: which files depend, directly or transitively, on a set
+//! of files, found by walking dependency edges backwards.
+
+use crate::types::{DependencyMap, SemmapFile};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt::Write;
+
+/// The layer holding test files.
+pub const TEST_LAYER: u8 = 4;
+
+/// A file affected by a change to one of the targets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Dependent {
+    pub path: String,
+    pub layer: u8,
+    /// Number of edges to the nearest target; 1 means a direct dependent.
+    pub distance: usize,
+    /// Shortest dependency chain, from this file down to a target.
+    pub chain: Vec<String>,
+}
+
+/// Everything that depends on the queried files.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Impact {
+    pub targets: Vec<String>,
+    /// Dependents ordered by layer, then distance, then path.
+    pub dependents: Vec<Dependent>,
+    /// Test-layer files to run: affected tests and any targets that are tests.
+    pub tests: Vec<String>,
+}
+
+impl Impact {
+    pub fn direct_count(&self) -> usize {
+        self.dependents.iter().filter(|d| d.distance == 1).count()
+    }
+
+    pub fn by_layer(&self) -> BTreeMap<u8, Vec<&Dependent>> {
+        let mut layers: BTreeMap<u8, Vec<&Dependent>> = BTreeMap::new();
+        for dependent in &self.dependents {
+            layers.entry(dependent.layer).or_default().push(dependent);
+        }
+        layers
+    }
+}
+
+/// Walks reverse edges from `targets`, breadth first, so every dependent gets
+/// one of its shortest chains. Fails on targets that are not in the map.
+pub fn analyze(depmap: &DependencyMap, targets: &[String]) -> Result<Impact, String> {
+    let layers: HashMap<&str, u8> = depmap
+        .nodes
+        .iter()
+        .map(|n| (n.path.as_str(), n.layer))
+        .collect();
+    let targets: BTreeSet<&str> = targets
+        .iter()
+        .map(|t| t.strip_prefix("./").unwrap_or(t))
+        .collect();
+    if let Some(unknown) = targets.iter().find(|t| !layers.contains_key(**t)) {
+        return Err(format!("Not in SEMMAP: {unknown}"));
+    }
+    let mut reverse: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    for edge in &depmap.edges {
+        reverse.entry(&edge.to).or_default().insert(&edge.from);
+    }
+
+    // Maps each reached file to the next file on its way to a target
+    let mut next: HashMap<&str, Option<&str>> = targets.iter().map(|t| (*t, None)).collect();
+    let mut queue: VecDeque<&str> = targets.iter().copied().collect();
+    let mut order = Vec::new();
+    while let Some(current) = queue.pop_front() {
+        for &dependent in reverse.get(current).into_iter().flatten() {
+            if !next.contains_key(dependent) {
+                next.insert(dependent, Some(current));
+                order.push(dependent);
+                queue.push_back(dependent);
+            }
+        }
+    }
+
+    let mut dependents: Vec<Dependent> = order
+        .into_iter()
+        .map(|path| {
+            let chain = chain(path, &next);
+            Dependent {
+                path: path.to_string(),
+                layer: layers.get(path).copied().unwrap_or(0),
+                distance: chain.len() - 1,
+                chain,
+            }
+        })
+        .collect();
+    dependents.sort_by(|a, b| (a.layer, a.distance, &a.path).cmp(&(b.layer, b.distance, &b.path)));
+    let tests = targets
+        .iter()
+        .map(ToString::to_string)
+        .chain(dependents.iter().map(|d| d.path.clone()))
+        .filter(|p| layers.get(p.as_str()) == Some(&TEST_LAYER))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    Ok(Impact {
+        targets: targets.into_iter().map(String::from).collect(),
+        dependents,
+        tests,
+    })
+}
+
+fn chain(start: &str, next: &HashMap<&str, Option<&str>>) -> Vec<String> {
+    let mut chain = vec![start.to_string()];
+    let mut current = start;
+    while let Some(Some(step)) = next.get(current) {
+        chain.push((*step).to_string());
+        current = step;
+    }
+    chain
+}
+
+/// Human-readable listing, grouped under the map's layer headings.
+pub fn render_text(impact: &Impact, semmap: &SemmapFile) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Impact of {}: {} dependents ({} direct) in {} layers",
+        impact.targets.join(", "),
+        impact.dependents.len(),
+        impact.direct_count(),
+        impact.by_layer().len()
+    );
+    for (layer, dependents) in impact.by_layer() {
+        let _ = match semmap.layers.iter().find(|l| l.number == layer) {
+            Some(l) => writeln!(out, "\n## Layer {layer} -- {}", l.name),
+            None => writeln!(out, "\n## Layer {layer}"),
+        };
+        for dependent in dependents {
+            let kind = if dependent.distance == 1 {
+                "direct"
+            } else {
+                "transitive"
+            };
+            let _ = writeln!(out, "  {} ({kind})", dependent.path);
+            if dependent.distance > 1 {
+                let _ = writeln!(out, "    {}", dependent.chain.join(" -> "));
+            }
+        }
+    }
+    out
+}
@@ -1,12 +1,18 @@
 //! Layer and description inference for SEMMAP generation.
 
 use crate::doc_extractor;
+use crate::lang_jvm;
+use crate::path_utils;
 use crate::stereotype::{self, Stereotype};
 use crate::swum;
 use std::path::Path;
 
 /// Infer the layer number for a file based on its path and content.
 pub fn infer_layer(rel_path: &str, file: &Path, content: &str) -> u8 {
+    if let Some(layer) = lang_jvm::source_set_layer(rel_path) {
+        return layer;
+    }
+
     let stereotype = stereotype::classify(rel_path, content);
 
     match stereotype {
@@ -52,7 +58,10 @@ fn infer_layer_from_path(rel_path: &str, file: &Path) -> u8 {
 }
 
 fn is_config_ext(ext: &str) -> bool {
-    matches!(ext, "toml" | "yaml" | "yml" | "json")
+    matches!(
+        ext,
+        "toml" | "yaml" | "yml" | "json" | "gradle" | "properties"
+    )
 }
 
 /// Infer the WHAT description for a file.
@@ -75,16 +84,24 @@ pub fn infer_what(rel_path: &str, file: &Path, content: &str) -> String {
         "rs" if rel_path.contains("mod") => return format!("Module definitions for {stem}."),
         "toml" if stem == "Cargo" => return "Rust package manifest and dependencies.".into(),
         "json" if stem == "package" => return "Node.js package manifest.".into(),
+        "swift" if stem == "Package" => return "Swift package manifest and targets.".into(),
+        "xml" if stem == "pom" => return "Maven project model and dependencies.".into(),
+        "gradle" | "kts" if stem.starts_with("build.gradle") || stem == "build" => {
+            return "Gradle build script and dependencies.".into()
+        }
+        "gradle" | "kts" if stem.starts_with("settings.gradle") || stem == "settings" => {
+            return "Gradle settings and included projects.".into()
+        }
         _ => {}
     }
 
     // Use SWUM for code files
-    if matches!(ext, "rs" | "py" | "ts" | "js" | "go" | "java") {
+    if path_utils::is_source_ext(ext) {
         return swum::expand_identifier(stem);
     }
 
     // Config files
-    if matches!(ext, "toml" | "yaml" | "yml" | "json") {
+    if is_config_ext(ext) {
         return format!("Configuration for {stem}.");
     }
 
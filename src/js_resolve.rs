@@ -0,0 +1,146 @@
+//! Resolves JavaScript/TypeScript module specifiers to project files.
+
+use crate::path_utils;
+use crate::tsconfig::{self, TsPaths};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Extensions tried, in order, when a specifier omits one.
+const EXTENSIONS: [&str; 9] = ["ts", "tsx", "mts", "cts", "d.ts", "js", "jsx", "mjs", "cjs"];
+
+/// `package.json` fields that name a package's entry file, in priority order.
+const ENTRY_FIELDS: [&str; 4] = ["types", "module", "main", "typings"];
+
+/// Node-style resolver rooted at the project directory.
+#[derive(Debug, Clone)]
+pub struct JsResolver {
+    root: PathBuf,
+    ts: TsPaths,
+}
+
+impl JsResolver {
+    pub fn load(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            ts: tsconfig::load(root),
+        }
+    }
+
+    /// Resolves `specifier` imported from `importer` (both root-relative).
+    /// Returns `None` for external packages and unresolvable paths.
+    pub fn resolve(&self, importer: &str, specifier: &str) -> Option<String> {
+        if is_relative(specifier) {
+            let dir = path_utils::parent_dir(importer);
+            return self.resolve_path(&path_utils::join_normalized(dir, specifier)?);
+        }
+        if specifier.starts_with('/') {
+            return None;
+        }
+        self.resolve_alias(specifier)
+            .or_else(|| self.resolve_base_url(specifier))
+    }
+
+    fn resolve_alias(&self, specifier: &str) -> Option<String> {
+        let mut matching: Vec<_> = self
+            .ts
+            .aliases
+            .iter()
+            .filter_map(|alias| alias.capture(specifier).map(|star| (alias, star)))
+            .collect();
+        matching.sort_by_key(|(alias, _)| std::cmp::Reverse(alias.specificity()));
+
+        matching.into_iter().find_map(|(alias, star)| {
+            alias.targets.iter().find_map(|target| {
+                let candidate = path_utils::normalize_path(&target.replacen('*', star, 1))?;
+                self.resolve_path(&candidate)
+            })
+        })
+    }
+
+    fn resolve_base_url(&self, specifier: &str) -> Option<String> {
+        let base = self.ts.base_url.as_deref()?;
+        self.resolve_path(&path_utils::join_normalized(base, specifier)?)
+    }
+
+    /// Tries `path` as a file, with added or swapped extensions, then as a directory.
+    fn resolve_path(&self, path: &str) -> Option<String> {
+        self.resolve_file(path)
+            .or_else(|| self.resolve_package_dir(path))
+            .or_else(|| self.resolve_index(path))
+    }
+
+    fn resolve_file(&self, path: &str) -> Option<String> {
+        if self.is_file(path) {
+            return Some(path.to_string());
+        }
+        swapped_extensions(path)
+            .into_iter()
+            .chain(EXTENSIONS.iter().map(|ext| format!("{path}.{ext}")))
+            .find(|candidate| self.is_file(candidate))
+    }
+
+    fn resolve_index(&self, dir: &str) -> Option<String> {
+        EXTENSIONS
+            .iter()
+            .map(|ext| path_utils::prefix_path(dir, &format!("index.{ext}")))
+            .find(|candidate| self.is_file(candidate))
+    }
+
+    /// Follows a directory's `package.json` `exports` or entry fields.
+    fn resolve_package_dir(&self, dir: &str) -> Option<String> {
+        let manifest = path_utils::prefix_path(dir, "package.json");
+        let json = tsconfig::read_jsonc(&self.root.join(&manifest))?;
+        let exported = json.get("exports").and_then(export_entry);
+        let fields = ENTRY_FIELDS
+            .iter()
+            .filter_map(|f| json.get(*f).and_then(Value::as_str));
+
+        exported
+            .into_iter()
+            .chain(fields)
+            .filter_map(|entry| path_utils::join_normalized(dir, entry))
+            .find_map(|entry| {
+                self.resolve_file(&entry)
+                    .or_else(|| self.resolve_index(&entry))
+            })
+    }
+
+    fn is_file(&self, path: &str) -> bool {
+        !path.is_empty() && self.root.join(path).is_file()
+    }
+}
+
+fn is_relative(specifier: &str) -> bool {
+    specifier == "."
+        || specifier == ".."
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+}
+
+/// TypeScript ESM sources import `./x.js` while the file on disk is `./x.ts`.
+fn swapped_extensions(path: &str) -> Vec<String> {
+    let Some((stem, ext)) = path.rsplit_once('.') else {
+        return Vec::new();
+    };
+    let swaps: &[&str] = match ext {
+        "js" => &["ts", "tsx"],
+        "jsx" => &["tsx"],
+        "mjs" => &["mts"],
+        "cjs" => &["cts"],
+        _ => &[],
+    };
+    swaps.iter().map(|s| format!("{stem}.{s}")).collect()
+}
+
+/// Picks the root entry from an `exports` field (string, `"."` map or conditions).
+fn export_entry(exports: &Value) -> Option<&str> {
+    match exports {
+        Value::String(s) => Some(s),
+        Value::Object(map) => map.get(".").and_then(export_entry).or_else(|| {
+            ["types", "import", "require", "default"]
+                .iter()
+                .find_map(|c| map.get(*c).and_then(export_entry))
+        }),
+        _ => None,
+    }
+}
@@ -0,0 +1,114 @@
+//! Resolves Java/Kotlin `import` statements to files via package declarations.
+
+use crate::lang_jvm;
+use crate::types::DepKind;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static IMPORT_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+fn import_regex() -> Option<&'static Regex> {
+    IMPORT_RE
+        .get_or_init(|| {
+            Regex::new(r"(?m)^\s*import\s+(?:static\s+)?([\w.]+?)(\.\*)?\s*(?:as\s+\w+)?\s*;?\s*$")
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Index of fully qualified names and packages to the files declaring them.
+#[derive(Debug, Clone, Default)]
+pub struct JvmResolver {
+    by_name: HashMap<String, String>,
+    by_package: HashMap<String, Vec<String>>,
+}
+
+impl JvmResolver {
+    /// Indexes every mapped `.java`/`.kt` file by its package, file stem and,
+    /// for Kotlin, its top-level declarations.
+    pub fn load(root: &Path, paths: &[&str]) -> Self {
+        let mut resolver = Self::default();
+        for path in paths.iter().filter(|p| is_jvm_source(p)) {
+            if let Ok(content) = fs::read_to_string(root.join(path)) {
+                resolver.index(path, &content);
+            }
+        }
+        resolver
+    }
+
+    fn index(&mut self, path: &str, content: &str) {
+        let package = lang_jvm::package_of(content).unwrap_or_default();
+        let stem = Path::new(path)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        let mut names = vec![stem.to_string()];
+        if has_extension(path, "kt") {
+            names.extend(lang_jvm::kotlin_exports(content));
+        }
+        for name in names {
+            self.by_name
+                .entry(qualify(&package, &name))
+                .or_insert_with(|| path.to_string());
+        }
+        self.by_package
+            .entry(package)
+            .or_default()
+            .push(path.to_string());
+    }
+
+    pub fn extract_imports(&self, content: &str) -> Vec<(String, DepKind)> {
+        let Some(re) = import_regex() else {
+            return Vec::new();
+        };
+        re.captures_iter(content)
+            .filter_map(|cap| {
+                let name = cap.get(1)?.as_str();
+                Some(self.resolve(name, cap.get(2).is_some()))
+            })
+            .flatten()
+            .map(|target| (target, DepKind::Import))
+            .collect()
+    }
+
+    /// Resolves `a.b.C.member` by trying successively shorter prefixes;
+    /// a wildcard on a package yields every file in it.
+    fn resolve(&self, name: &str, wildcard: bool) -> Vec<String> {
+        if wildcard {
+            if let Some(files) = self.by_package.get(name) {
+                return files.clone();
+            }
+        }
+        let mut candidate = name;
+        loop {
+            if let Some(file) = self.by_name.get(candidate) {
+                return vec![file.clone()];
+            }
+            match candidate.rsplit_once('.') {
+                Some((prefix, _)) => candidate = prefix,
+                None => return Vec::new(),
+            }
+        }
+    }
+}
+
+fn is_jvm_source(path: &str) -> bool {
+    has_extension(path, "java") || has_extension(path, "kt")
+}
+
+fn has_extension(path: &str, extension: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+fn qualify(package: &str, name: &str) -> String {
+    if package.is_empty() {
+        name.to_string()
+    } else {
+        format!("{package}.{name}")
+    }
+}
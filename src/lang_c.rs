@@ -0,0 +1,125 @@
+//! C and C++ exported-symbol and `#include` extraction.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+static FUNCTION_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static TYPE_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static TYPEDEF_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static DEFINE_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static GUARD_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static INCLUDE_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+/// Keywords that look like a return type but start statements instead.
+const NON_DECL_PREFIXES: [&str; 8] = [
+    "static ", "return ", "typedef ", "using ", "if ", "while ", "for ", "switch ",
+];
+
+/// A top-level function declaration or definition: `int foo(`, `Foo *ns::bar(`.
+fn function_regex() -> Option<&'static Regex> {
+    FUNCTION_RE
+        .get_or_init(|| {
+            Regex::new(r"^[A-Za-z_][\w\s\*&:<>,]*?[\s\*&]([A-Za-z_]\w*)\s*\([^;{]*\)?").ok()
+        })
+        .as_ref()
+}
+
+/// A class, struct, union or enum definition (not a forward declaration).
+fn type_regex() -> Option<&'static Regex> {
+    TYPE_RE
+        .get_or_init(|| {
+            Regex::new(r"^(?:template\s*<[^>]*>\s*)?(?:class|struct|union|enum(?:\s+class)?)\s+(?:\w+\s+)*?([A-Za-z_]\w*)\s*(?:final\s*)?(?::[^;{]*)?\{?\s*$").ok()
+        })
+        .as_ref()
+}
+
+/// The name closing a `typedef struct { ... } Name;` block or a one-line typedef.
+fn typedef_regex() -> Option<&'static Regex> {
+    TYPEDEF_RE
+        .get_or_init(|| Regex::new(r"^(?:typedef\s.*?|\}\s*)([A-Za-z_]\w*)\s*;$").ok())
+        .as_ref()
+}
+
+fn define_regex() -> Option<&'static Regex> {
+    DEFINE_RE
+        .get_or_init(|| Regex::new(r"^#\s*define\s+([A-Za-z_]\w*)").ok())
+        .as_ref()
+}
+
+fn guard_regex() -> Option<&'static Regex> {
+    GUARD_RE
+        .get_or_init(|| Regex::new(r"(?m)^#\s*ifndef\s+([A-Za-z_]\w*)").ok())
+        .as_ref()
+}
+
+fn include_regex() -> Option<&'static Regex> {
+    INCLUDE_RE
+        .get_or_init(|| Regex::new(r#"(?m)^\s*#\s*include\s*([<"])([^>"]+)[>"]"#).ok())
+        .as_ref()
+}
+
+/// An `#include` directive: `quoted` for `"..."`, false for `<...>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Include {
+    pub path: String,
+    pub quoted: bool,
+}
+
+pub fn extract_includes(content: &str) -> Vec<Include> {
+    let Some(re) = include_regex() else {
+        return Vec::new();
+    };
+    re.captures_iter(content)
+        .filter_map(|cap| {
+            Some(Include {
+                quoted: cap.get(1)?.as_str() == "\"",
+                path: cap.get(2)?.as_str().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Non-static functions, classes, structs, typedefs and macros declared at
+/// file scope. Include guards are not reported as macros.
+pub fn extract_exports(content: &str) -> Vec<String> {
+    let guard = guard_regex()
+        .and_then(|re| re.captures(content))
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str());
+
+    content
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .map(str::trim_end)
+        .filter_map(|line| {
+            if line.starts_with('#') {
+                capture(define_regex(), line).filter(|name| Some(name.as_str()) != guard)
+            } else {
+                declared_name(line)
+            }
+        })
+        .collect()
+}
+
+fn declared_name(line: &str) -> Option<String> {
+    if let Some(name) = capture(type_regex(), line).or_else(|| capture(typedef_regex(), line)) {
+        return Some(name);
+    }
+    if NON_DECL_PREFIXES.iter().any(|p| line.starts_with(p)) || line.starts_with("//") {
+        return None;
+    }
+    capture(function_regex(), line).filter(|name| !is_keyword(name))
+}
+
+fn capture(re: Option<&Regex>, line: &str) -> Option<String> {
+    re?.captures(line)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
+fn is_keyword(name: &str) -> bool {
+    matches!(
+        name,
+        "if" | "while" | "for" | "switch" | "return" | "sizeof" | "defined"
+    )
+}
@@ -0,0 +1,95 @@
+//! JavaScript/TypeScript import extraction.
+
+use crate::js_resolve::JsResolver;
+use crate::types::DepKind;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static FROM_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static BARE_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static CALL_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+/// `import ... from '...'` and `export ... from '...'`, including multi-line clauses.
+fn from_regex() -> Option<&'static Regex> {
+    FROM_RE
+        .get_or_init(|| {
+            Regex::new(
+                r#"(?m)^\s*(import|export)\s+(type\s+)?([^'";]*?)\s*from\s*['"]([^'"]+)['"]"#,
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+/// Side-effect imports: `import './polyfill'`.
+fn bare_regex() -> Option<&'static Regex> {
+    BARE_RE
+        .get_or_init(|| Regex::new(r#"(?m)^\s*import\s*['"]([^'"]+)['"]"#).ok())
+        .as_ref()
+}
+
+/// `require('...')` and dynamic `import('...')` with a literal argument.
+fn call_regex() -> Option<&'static Regex> {
+    CALL_RE
+        .get_or_init(|| Regex::new(r#"\b(?:require|import)\(\s*['"]([^'"]+)['"]\s*\)"#).ok())
+        .as_ref()
+}
+
+pub fn extract_imports(
+    content: &str,
+    source_path: &str,
+    resolver: &JsResolver,
+) -> Vec<(String, DepKind)> {
+    let mut deps = Vec::new();
+
+    for (specifier, kind) in extract_specifiers(content) {
+        if let Some(resolved) = resolver.resolve(source_path, &specifier) {
+            deps.push((resolved, kind));
+        }
+    }
+
+    deps
+}
+
+/// Returns every module specifier in the file with its edge kind.
+pub fn extract_specifiers(content: &str) -> Vec<(String, DepKind)> {
+    let mut specs = Vec::new();
+
+    if let Some(re) = from_regex() {
+        for cap in re.captures_iter(content) {
+            let type_only =
+                cap.get(2).is_some() || cap.get(3).is_some_and(|m| is_type_only_clause(m.as_str()));
+            let kind = if type_only {
+                DepKind::TypeImport
+            } else {
+                DepKind::Import
+            };
+            if let Some(m) = cap.get(4) {
+                specs.push((m.as_str().to_string(), kind));
+            }
+        }
+    }
+
+    for re in [bare_regex(), call_regex()].into_iter().flatten() {
+        for cap in re.captures_iter(content) {
+            if let Some(m) = cap.get(1) {
+                specs.push((m.as_str().to_string(), DepKind::Import));
+            }
+        }
+    }
+
+    specs
+}
+
+/// `{ type A, type B }` imports nothing at runtime.
+fn is_type_only_clause(clause: &str) -> bool {
+    let Some(inner) = clause
+        .trim()
+        .strip_prefix('{')
+        .and_then(|c| c.strip_suffix('}'))
+    else {
+        return false;
+    };
+    let mut names = inner.split(',').map(str::trim).filter(|n| !n.is_empty());
+    names.clone().next().is_some() && names.all(|n| n.starts_with("type "))
+}
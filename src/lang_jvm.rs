@@ -0,0 +1,129 @@
+//! Java and Kotlin export extraction and Maven/Gradle source-set layering.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+static JAVA_TYPE_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static JAVA_METHOD_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static KOTLIN_DECL_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static PACKAGE_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+fn java_type_regex() -> Option<&'static Regex> {
+    JAVA_TYPE_RE
+        .get_or_init(|| {
+            Regex::new(
+                r"^public\s+(?:(?:static|final|abstract|sealed|non-sealed|strictfp)\s+)*(?:class|interface|enum|record|@interface)\s+(\w+)",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+fn java_method_regex() -> Option<&'static Regex> {
+    JAVA_METHOD_RE
+        .get_or_init(|| {
+            Regex::new(
+                r"^public\s+(?:(?:static|final|abstract|synchronized|default|native)\s+)*(?:<[^>]+>\s+)?[\w.]+(?:<[^()]*>)?(?:\[\])*\s+(\w+)\s*\(",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+fn kotlin_decl_regex() -> Option<&'static Regex> {
+    KOTLIN_DECL_RE
+        .get_or_init(|| {
+            Regex::new(
+                r"^(?:@\w+(?:\([^)]*\))?\s+)*((?:(?:public|private|internal|protected|open|abstract|sealed|data|enum|annotation|inline|value|final|suspend|operator|infix|tailrec|external|const|lateinit|actual|expect)\s+)*)(?:class|interface|object|fun|val|var|typealias)\s+(?:<[^>]+>\s*)?(?:[\w.]+\.)?(\w+)",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+fn package_regex() -> Option<&'static Regex> {
+    PACKAGE_RE
+        .get_or_init(|| Regex::new(r"(?m)^\s*package\s+([\w.]+)").ok())
+        .as_ref()
+}
+
+/// Public top-level types and public methods of a Java source file.
+pub fn java_exports(content: &str) -> Vec<String> {
+    let mut exports = Vec::new();
+    for line in content.lines().map(str::trim) {
+        let captured = java_type_regex()
+            .and_then(|re| re.captures(line))
+            .or_else(|| java_method_regex().and_then(|re| re.captures(line)));
+        if let Some(name) = captured.and_then(|c| c.get(1)) {
+            exports.push(name.as_str().to_string());
+        }
+    }
+    exports
+}
+
+/// Top-level Kotlin declarations, which are public unless marked otherwise.
+pub fn kotlin_exports(content: &str) -> Vec<String> {
+    let Some(re) = kotlin_decl_regex() else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| re.captures(line))
+        .filter(|c| {
+            c.get(1).is_none_or(|m| {
+                let modifiers = m.as_str();
+                !modifiers.contains("private") && !modifiers.contains("internal")
+            })
+        })
+        .filter_map(|c| c.get(2).map(|m| m.as_str().to_string()))
+        .collect()
+}
+
+/// The `package` declared at the top of a Java or Kotlin file.
+pub fn package_of(content: &str) -> Option<String> {
+    package_regex()?
+        .captures(content)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().trim_end_matches(';').to_string())
+}
+
+/// Layer implied by Maven/Gradle conventions: build scripts and resources are
+/// configuration, non-main source sets are tests.
+pub fn source_set_layer(rel_path: &str) -> Option<u8> {
+    let file_name = rel_path.rsplit('/').next().unwrap_or(rel_path);
+    if is_build_file(file_name) {
+        return Some(0);
+    }
+    let source_set = source_set_of(rel_path)?;
+    if rel_path.contains(&format!("src/{source_set}/resources/")) {
+        return Some(0);
+    }
+    let is_test_set = source_set.to_lowercase().contains("test");
+    is_test_set.then_some(4)
+}
+
+/// Maven and Gradle build and settings files.
+pub fn is_build_file(file_name: &str) -> bool {
+    matches!(
+        file_name,
+        "pom.xml"
+            | "build.gradle"
+            | "build.gradle.kts"
+            | "settings.gradle"
+            | "settings.gradle.kts"
+            | "gradle.properties"
+    )
+}
+
+/// Name of the `src/<set>/` directory a path lives under (`main`, `test`, ...).
+fn source_set_of(rel_path: &str) -> Option<&str> {
+    let (_, after) = rel_path
+        .split_once("src/")
+        .filter(|(before, _)| before.is_empty() || before.ends_with('/'))?;
+    let (set, rest) = after.split_once('/')?;
+    let is_jvm_set = ["java/", "kotlin/", "resources/"]
+        .iter()
+        .any(|dir| rest.starts_with(dir));
+    is_jvm_set.then_some(set)
+}
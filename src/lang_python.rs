@@ -1,43 +1,170 @@
-use crate::types::DepKind;
-use regex::Regex;
-
-pub fn extract_imports(content: &str) -> Vec<(String, DepKind)> {
-    let mut deps = Vec::new();
-    
-    extract_relative_imports(content, &mut deps);
-    extract_simple_imports(content, &mut deps);
-
-    deps
-}
-
-fn extract_relative_imports(content: &str, deps: &mut Vec<(String, DepKind)>) {
-    let Some(re) = Regex::new(r"from\s+\.(\w+)\s+import").ok() else {
-        return;
-    };
-    
-    for cap in re.captures_iter(content) {
-        if let Some(m) = cap.get(1) {
-            deps.push((format!("{}.py", m.as_str()), DepKind::Import));
-        }
-    }
-}
-
-fn extract_simple_imports(content: &str, deps: &mut Vec<(String, DepKind)>) {
-    let Some(re) = Regex::new(r"import\s+(\w+)").ok() else {
-        return;
-    };
-
-    for cap in re.captures_iter(content) {
-        if let Some(m) = cap.get(1) {
-            let module = m.as_str();
-            if !is_stdlib(module) {
-                deps.push((format!("{module}.py"), DepKind::Import));
-            }
-        }
-    }
-}
-
-fn is_stdlib(module: &str) -> bool {
-    let stdlib = ["os", "sys", "re", "json", "typing", "collections", "pathlib"];
-    stdlib.contains(&module)
-}
\ No newline at end of file
+//! Python import extraction with package-aware resolution.
+
+use crate::path_utils;
+use crate::python_resolve::PythonResolver;
+use crate::python_stdlib;
+use crate::types::DepKind;
+
+/// A parsed `import` or `from ... import` statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PyImport {
+    /// `import a.b` — one entry per comma-separated module.
+    Module(String),
+    /// `from ..pkg import a, b` — leading dots counted in `level`.
+    From {
+        level: usize,
+        module: String,
+        names: Vec<String>,
+    },
+}
+
+pub fn extract_imports(
+    content: &str,
+    source_path: &str,
+    resolver: &PythonResolver,
+) -> Vec<(String, DepKind)> {
+    parse_imports(content)
+        .iter()
+        .flat_map(|import| resolve_import(import, source_path, resolver))
+        .map(|target| (target, DepKind::Import))
+        .collect()
+}
+
+fn resolve_import(import: &PyImport, source_path: &str, resolver: &PythonResolver) -> Vec<String> {
+    match import {
+        PyImport::Module(module) if !python_stdlib::is_stdlib(module) => resolver
+            .resolve_absolute(source_path, module)
+            .into_iter()
+            .collect(),
+        PyImport::Module(_) => Vec::new(),
+        PyImport::From {
+            level: 0,
+            module,
+            names,
+        } => resolve_absolute_from(module, names, source_path, resolver),
+        PyImport::From {
+            level,
+            module,
+            names,
+        } => resolve_relative_from(*level, module, names, source_path, resolver),
+    }
+}
+
+/// `from pkg.sub import x` — `x` may be a submodule or a name in `pkg.sub`.
+fn resolve_absolute_from(
+    module: &str,
+    names: &[String],
+    source_path: &str,
+    resolver: &PythonResolver,
+) -> Vec<String> {
+    if python_stdlib::is_stdlib(module) {
+        return Vec::new();
+    }
+    let mut targets: Vec<String> = names
+        .iter()
+        .filter_map(|n| resolver.resolve_absolute(source_path, &format!("{module}.{n}")))
+        .collect();
+    if names.is_empty() || targets.len() < names.len() {
+        targets.extend(resolver.resolve_absolute(source_path, module));
+    }
+    targets
+}
+
+/// `from ..pkg import x` — resolved against the importer's package directory.
+fn resolve_relative_from(
+    level: usize,
+    module: &str,
+    names: &[String],
+    source_path: &str,
+    resolver: &PythonResolver,
+) -> Vec<String> {
+    let mut base = path_utils::parent_dir(source_path);
+    for _ in 1..level {
+        if base.is_empty() {
+            return Vec::new();
+        }
+        base = path_utils::parent_dir(base);
+    }
+    let package = if module.is_empty() {
+        base.to_string()
+    } else {
+        path_utils::prefix_path(base, &module.replace('.', "/"))
+    };
+
+    let mut targets: Vec<String> = names
+        .iter()
+        .filter_map(|n| resolver.resolve_module(&path_utils::prefix_path(&package, n)))
+        .collect();
+    if names.is_empty() || targets.len() < names.len() {
+        targets.extend(resolver.resolve_module(&package));
+    }
+    targets
+}
+
+/// Parses every import statement, joining parenthesised and continued lines.
+pub fn parse_imports(content: &str) -> Vec<PyImport> {
+    logical_lines(content)
+        .iter()
+        .flat_map(|line| parse_statement(line))
+        .collect()
+}
+
+fn parse_statement(line: &str) -> Vec<PyImport> {
+    if let Some(rest) = line.strip_prefix("import ") {
+        return split_names(rest)
+            .into_iter()
+            .map(PyImport::Module)
+            .collect();
+    }
+    let Some((source, names)) = line
+        .strip_prefix("from ")
+        .and_then(|rest| rest.split_once(" import "))
+    else {
+        return Vec::new();
+    };
+    let source = source.trim();
+    let module = source.trim_start_matches('.');
+    let names = split_names(names.trim().trim_start_matches('(').trim_end_matches(')'));
+    vec![PyImport::From {
+        level: source.len() - module.len(),
+        module: module.to_string(),
+        names: names.into_iter().filter(|n| n != "*").collect(),
+    }]
+}
+
+/// Splits `a as b, c` into `["a", "c"]`.
+fn split_names(list: &str) -> Vec<String> {
+    list.split(',')
+        .filter_map(|item| item.split_whitespace().next())
+        .map(String::from)
+        .collect()
+}
+
+/// Joins physical lines into logical statements and strips comments.
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut depth: i32 = 0;
+
+    for raw in content.lines() {
+        let code = raw.split('#').next().unwrap_or("");
+        let continued = code.trim_end().ends_with('\\');
+        current.push_str(code.trim_end().trim_end_matches('\\').trim());
+        current.push(' ');
+        depth += paren_balance(code);
+        if depth <= 0 && !continued {
+            lines.push(current.trim().to_string());
+            current.clear();
+            depth = 0;
+        }
+    }
+    lines
+}
+
+fn paren_balance(code: &str) -> i32 {
+    code.chars().fold(0, |acc, c| match c {
+        '(' => acc + 1,
+        ')' => acc - 1,
+        _ => acc,
+    })
+}
@@ -0,0 +1,99 @@
+//! Swift export extraction and module-level import resolution.
+
+use crate::swift_package::SwiftPackage;
+use crate::types::DepKind;
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static DECL_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static IMPORT_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+/// `public`/`open` declarations, allowing attributes and other modifiers.
+fn decl_regex() -> Option<&'static Regex> {
+    DECL_RE
+        .get_or_init(|| {
+            Regex::new(
+                r"^(?:@\w+(?:\([^)]*\))?\s+)*(?:(?:final|static|class|override|mutating|nonmutating|indirect|convenience|required|nonisolated|lazy|dynamic)\s+)*(?:public|open)\s+(?:(?:final|static|class|override|mutating|nonmutating|indirect|convenience|required|nonisolated|lazy|dynamic)\s+)*(?:class|struct|enum|protocol|actor|func|var|let|typealias)\s+([A-Za-z_]\w*)",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+fn import_regex() -> Option<&'static Regex> {
+    IMPORT_RE
+        .get_or_init(|| {
+            Regex::new(r"(?m)^\s*(?:@\w+\s+)*import\s+(?:(?:class|struct|enum|protocol|func|typealias|let|var)\s+)?(\w+)").ok()
+        })
+        .as_ref()
+}
+
+/// Names declared `public` or `open`.
+pub fn extract_exports(content: &str) -> Vec<String> {
+    let Some(re) = decl_regex() else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| re.captures(line.trim()))
+        .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
+        .collect()
+}
+
+/// Module names brought in with `import`, `@testable import` or `import struct M.T`.
+pub fn imported_modules(content: &str) -> Vec<String> {
+    let Some(re) = import_regex() else {
+        return Vec::new();
+    };
+    re.captures_iter(content)
+        .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
+        .collect()
+}
+
+/// Resolves `import Module` to the files of a local `SwiftPM` target.
+#[derive(Debug, Clone, Default)]
+pub struct SwiftResolver {
+    package: SwiftPackage,
+    files: Vec<String>,
+}
+
+impl SwiftResolver {
+    pub fn load(root: &Path, paths: &[&str]) -> Self {
+        Self {
+            package: SwiftPackage::load(root).unwrap_or_default(),
+            files: paths
+                .iter()
+                .filter(|p| {
+                    Path::new(p)
+                        .extension()
+                        .is_some_and(|e| e.eq_ignore_ascii_case("swift"))
+                })
+                .map(|p| (*p).to_string())
+                .collect(),
+        }
+    }
+
+    /// An import becomes edges to every file of the imported target, provided
+    /// the importing file's target declares that target as a dependency.
+    pub fn extract_imports(&self, content: &str, source_path: &str) -> Vec<(String, DepKind)> {
+        let declared = self
+            .package
+            .target_of(source_path)
+            .map(|t| t.dependencies.as_slice());
+
+        imported_modules(content)
+            .iter()
+            .filter(|module| declared.is_none_or(|deps| deps.contains(module)))
+            .filter_map(|module| self.package.target_named(module))
+            .flat_map(|target| {
+                let prefix = format!("{}/", target.dir);
+                self.files
+                    .iter()
+                    .filter(move |f| f.starts_with(&prefix))
+                    .cloned()
+            })
+            .map(|file| (file, DepKind::Import))
+            .collect()
+    }
+}
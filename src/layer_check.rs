@@ -0,0 +1,219 @@
+//! Layer violation detection, honouring `→ Allow:` exceptions on entries and
+//! looking through facade files.
+
+use crate::path_utils;
+use crate::types::{AllowedDep, DepKind, DependencyMap, FileEntry, SemmapFile};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::BuildHasher;
+
+/// Rule id of the built-in check: no file may depend on a higher layer.
+pub const LAYER_ORDER_RULE: &str = "layer-order";
+
+/// A dependency on a higher layer than the depending file's own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayerViolation {
+    pub from: String,
+    pub to: String,
+    pub from_layer: u8,
+    pub to_layer: u8,
+    /// Kind of the edge leaving `from`.
+    pub kind: DepKind,
+    pub rule: String,
+    /// Facade the dependency was traced through, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+    pub suggestion: String,
+}
+
+impl fmt::Display for LayerViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Layer violation: {} (L{}) depends on {} (L{})",
+            self.from, self.from_layer, self.to, self.to_layer
+        )?;
+        if let Some(via) = &self.via {
+            write!(f, " via {via}")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn check_layer_violations(depmap: &DependencyMap, semmap: &SemmapFile) -> Vec<LayerViolation> {
+    check_layer_violations_with(depmap, semmap, &HashSet::new())
+}
+
+/// Checks every edge except those leaving a facade. Edges into a facade are
+/// followed through it, so `a -> lib.rs -> b` is checked as `a -> b`.
+pub fn check_layer_violations_with<S: BuildHasher>(
+    depmap: &DependencyMap,
+    semmap: &SemmapFile,
+    facades: &HashSet<String, S>,
+) -> Vec<LayerViolation> {
+    let path_to_layer = semmap.path_to_layer();
+    let mut violations = Vec::new();
+    for checked in checked_edges(depmap, semmap, facades) {
+        let from_layer = path_to_layer.get(checked.from).copied();
+        let to_layer = path_to_layer.get(checked.to).copied();
+        if let (Some(fl), Some(tl)) = (from_layer, to_layer) {
+            if tl > fl {
+                let (from, to) = (checked.from, checked.to);
+                violations.push(LayerViolation {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    from_layer: fl,
+                    to_layer: tl,
+                    kind: checked.kind,
+                    rule: LAYER_ORDER_RULE.to_string(),
+                    via: checked.via.map(String::from),
+                    suggestion: format!(
+                        "move {to} to layer {fl} or below, or add `→ Allow: {to} -- <reason>` to {from}"
+                    ),
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// Every `(from, to)` pair the layer check holds to `layer(to) <= layer(from)`,
+/// after allows and facades.
+pub fn layer_constraints<S: BuildHasher>(
+    depmap: &DependencyMap,
+    semmap: &SemmapFile,
+    facades: &HashSet<String, S>,
+) -> Vec<(String, String)> {
+    checked_edges(depmap, semmap, facades)
+        .into_iter()
+        .map(|c| (c.from.to_string(), c.to.to_string()))
+        .collect()
+}
+
+/// A dependency the layer check applies to, once per `(from, to)` pair.
+struct Checked<'a> {
+    from: &'a str,
+    to: &'a str,
+    via: Option<&'a str>,
+    kind: DepKind,
+}
+
+fn checked_edges<'a, S: BuildHasher>(
+    depmap: &'a DependencyMap,
+    semmap: &'a SemmapFile,
+    facades: &HashSet<String, S>,
+) -> Vec<Checked<'a>> {
+    let entries = entries_by_path(semmap);
+    let mut seen = HashSet::new();
+    let mut checked = Vec::new();
+    for edge in &depmap.edges {
+        if facades.contains(&edge.from) {
+            continue;
+        }
+        let targets = if facades.contains(&edge.to) {
+            through_facade(&edge.to, depmap, facades)
+        } else {
+            vec![(edge.to.as_str(), None)]
+        };
+        for (to, via) in targets {
+            let allowed = entries
+                .get(edge.from.as_str())
+                .is_some_and(|e| e.allows.iter().any(|a| allow_matches(a, to)));
+            if !allowed && seen.insert((edge.from.as_str(), to)) {
+                checked.push(Checked {
+                    from: &edge.from,
+                    to,
+                    via,
+                    kind: edge.kind,
+                });
+            }
+        }
+    }
+    checked
+}
+
+/// Non-facade files reachable from `facade` through facades only, each with
+/// the facade it was first reached through.
+fn through_facade<'a, S: BuildHasher>(
+    facade: &'a str,
+    depmap: &'a DependencyMap,
+    facades: &HashSet<String, S>,
+) -> Vec<(&'a str, Option<&'a str>)> {
+    let mut targets = Vec::new();
+    let mut visited = HashSet::from([facade]);
+    let mut queue = VecDeque::from([facade]);
+    while let Some(current) = queue.pop_front() {
+        for edge in depmap.edges.iter().filter(|e| e.from == current) {
+            if !visited.insert(edge.to.as_str()) {
+                continue;
+            }
+            if facades.contains(&edge.to) {
+                queue.push_back(&edge.to);
+            } else {
+                targets.push((edge.to.as_str(), Some(facade)));
+            }
+        }
+    }
+    targets
+}
+
+/// The drawn `(from, to)` edge of each violation: the edge into the facade
+/// for traced violations.
+pub fn violation_edges(violations: &[LayerViolation]) -> HashSet<(String, String)> {
+    violations
+        .iter()
+        .map(|v| {
+            let to = v.via.as_ref().unwrap_or(&v.to);
+            (v.from.clone(), to.clone())
+        })
+        .collect()
+}
+
+/// `→ Allow:` lines whose target no longer matches any dependency of the
+/// entry, direct or through a facade.
+pub fn stale_allows<S: BuildHasher>(
+    depmap: &DependencyMap,
+    semmap: &SemmapFile,
+    facades: &HashSet<String, S>,
+) -> Vec<String> {
+    let mut stale = Vec::new();
+    for entry in semmap.layers.iter().flat_map(|l| &l.entries) {
+        if entry.allows.is_empty() {
+            continue;
+        }
+        let mut targets: Vec<&str> = Vec::new();
+        for edge in depmap.edges.iter().filter(|e| e.from == entry.path) {
+            targets.push(&edge.to);
+            if facades.contains(&edge.to) {
+                targets.extend(
+                    through_facade(&edge.to, depmap, facades)
+                        .iter()
+                        .map(|t| t.0),
+                );
+            }
+        }
+        for allow in &entry.allows {
+            if !targets.iter().any(|t| allow_matches(allow, t)) {
+                stale.push(format!(
+                    "Stale allow: {} -> {} (no such dependency)",
+                    entry.path, allow.target
+                ));
+            }
+        }
+    }
+    stale
+}
+
+fn entries_by_path(semmap: &SemmapFile) -> HashMap<&str, &FileEntry> {
+    semmap
+        .layers
+        .iter()
+        .flat_map(|l| &l.entries)
+        .map(|e| (e.path.as_str(), e))
+        .collect()
+}
+
+fn allow_matches(allow: &AllowedDep, path: &str) -> bool {
+    allow.target == path || path_utils::glob_match(&allow.target, path)
+}
@@ -0,0 +1,144 @@
+//! The fewest file layer moves that clear every layer violation.
+//!
+//! Files that keep their layer must never depend, even transitively, on a
+//! kept file of a higher layer: they form an antichain of that order, and the
+//! largest one leaves the fewest files to move. Moved files then take layers
+//! between their kept dependencies and dependents, cycles sharing one layer.
+
+mod antichain;
+mod graph;
+mod output;
+
+use crate::layer_check;
+use crate::path_utils;
+use crate::types::{DependencyMap, SemmapFile};
+use graph::{assign, flag, path, reachable, reached};
+pub use output::{apply, render_text};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::hash::BuildHasher;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayerMove {
+    pub path: String,
+    pub from: u8,
+    pub to: u8,
+}
+
+/// Two pinned files no assignment can satisfy: `lower` depends on `upper`,
+/// which sits in a higher layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Infeasible {
+    pub lower: String,
+    pub upper: String,
+    /// Dependency chain from `lower` to `upper`.
+    pub chain: Vec<String>,
+    /// Whether `upper` depends back on `lower`.
+    pub cycle: bool,
+}
+
+impl fmt::Display for Infeasible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "No layer moves fix every violation: pinned {} depends on pinned {} ({})",
+            self.lower,
+            self.upper,
+            self.chain.join(" -> ")
+        )?;
+        if self.cycle {
+            write!(f, " and they are in a dependency cycle")?;
+        }
+        Ok(())
+    }
+}
+
+/// Moves, sorted by path, that leave no violation; files matching a `pinned`
+/// path or glob keep their layer.
+pub fn solve<S: BuildHasher>(
+    depmap: &DependencyMap,
+    semmap: &SemmapFile,
+    facades: &HashSet<String, S>,
+    pinned: &[String],
+) -> Result<Vec<LayerMove>, Infeasible> {
+    let layers = semmap.path_to_layer();
+    let constraints = layer_check::layer_constraints(depmap, semmap, facades);
+    let nodes: Vec<&str> = constraints
+        .iter()
+        .flat_map(|(from, to)| [from.as_str(), to.as_str()])
+        .filter(|p| layers.contains_key(p))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let index: HashMap<&str, usize> = nodes.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+    let label: Vec<u8> = nodes
+        .iter()
+        .map(|p| layers.get(p).copied().unwrap_or(0))
+        .collect();
+    let mut adjacent = vec![Vec::new(); nodes.len()];
+    for (from, to) in &constraints {
+        if let (Some(&f), Some(&t)) = (index.get(from.as_str()), index.get(to.as_str())) {
+            if let Some(successors) = adjacent.get_mut(f) {
+                successors.push(t);
+            }
+        }
+    }
+    let reach: Vec<Vec<bool>> = (0..nodes.len()).map(|n| reachable(&adjacent, n)).collect();
+    let below =
+        |u: usize, v: usize| reached(&reach, u, v) && label.get(u).copied() < label.get(v).copied();
+
+    let is_pinned: Vec<bool> = nodes
+        .iter()
+        .map(|p| {
+            pinned
+                .iter()
+                .any(|g| g == p || path_utils::glob_match(g, p))
+        })
+        .collect();
+    let pins: Vec<usize> = (0..nodes.len()).filter(|&n| flag(&is_pinned, n)).collect();
+    for &u in &pins {
+        if let Some(&v) = pins.iter().find(|&&v| below(u, v)) {
+            return Err(Infeasible {
+                lower: nodes.get(u).copied().unwrap_or_default().to_string(),
+                upper: nodes.get(v).copied().unwrap_or_default().to_string(),
+                chain: path(&adjacent, u, v)
+                    .iter()
+                    .filter_map(|&n| nodes.get(n).map(|p| (*p).to_string()))
+                    .collect(),
+                cycle: reached(&reach, v, u),
+            });
+        }
+    }
+
+    // Files unrelated to every pin compete for the largest antichain
+    let free: Vec<usize> = (0..nodes.len())
+        .filter(|&n| !flag(&is_pinned, n) && !pins.iter().any(|&p| below(p, n) || below(n, p)))
+        .collect();
+    let above: Vec<Vec<usize>> = free
+        .iter()
+        .map(|&u| {
+            (0..free.len())
+                .filter(|&j| free.get(j).is_some_and(|&v| below(u, v)))
+                .collect()
+        })
+        .collect();
+    let mut kept = is_pinned;
+    for j in antichain::max_antichain(&above) {
+        if let Some(slot) = free.get(j).and_then(|&n| kept.get_mut(n)) {
+            *slot = true;
+        }
+    }
+
+    let assigned = assign(&adjacent, &label, &kept);
+    Ok(nodes
+        .iter()
+        .zip(label.iter().zip(&assigned))
+        .filter(|(_, (from, to))| from != to)
+        .map(|(path, (&from, &to))| LayerMove {
+            path: (*path).to_string(),
+            from,
+            to,
+        })
+        .collect())
+}
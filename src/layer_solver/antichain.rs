@@ -0,0 +1,100 @@
+//! Largest antichain of a strict partial order, through Dilworth's theorem:
+//! a maximum bipartite matching and König's vertex cover.
+
+/// The most nodes no two of which are related, where `above[u]` lists every
+/// `v` with `u < v` (the order must be transitive).
+pub fn max_antichain(above: &[Vec<usize>]) -> Vec<usize> {
+    let size = above.len();
+    let mut match_right: Vec<Option<usize>> = vec![None; size];
+    for left in 0..size {
+        let mut visited = vec![false; size];
+        augment(left, above, &mut match_right, &mut visited);
+    }
+    let mut match_left: Vec<Option<usize>> = vec![None; size];
+    for (right, left) in match_right.iter().enumerate() {
+        if let Some(slot) = left.and_then(|l| match_left.get_mut(l)) {
+            *slot = Some(right);
+        }
+    }
+
+    // Alternating search from the unmatched left nodes; the cover is the
+    // unreached left nodes plus the reached right ones.
+    let mut left_seen = vec![false; size];
+    let mut right_seen = vec![false; size];
+    let mut queue: Vec<usize> = (0..size)
+        .filter(|&l| match_left.get(l).copied().flatten().is_none())
+        .collect();
+    for &l in &queue {
+        set(&mut left_seen, l);
+    }
+    while let Some(left) = queue.pop() {
+        for &right in above.get(left).into_iter().flatten() {
+            if get(&right_seen, right) || match_left.get(left).copied().flatten() == Some(right) {
+                continue;
+            }
+            set(&mut right_seen, right);
+            if let Some(next) = match_right.get(right).copied().flatten() {
+                if !get(&left_seen, next) {
+                    set(&mut left_seen, next);
+                    queue.push(next);
+                }
+            }
+        }
+    }
+    (0..size)
+        .filter(|&n| get(&left_seen, n) && !get(&right_seen, n))
+        .collect()
+}
+
+/// Kuhn's augmenting path search from `left`, with an explicit stack.
+fn augment(
+    left: usize,
+    above: &[Vec<usize>],
+    match_right: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    // Each frame is (left node, next candidate index, right node taken to reach it)
+    let mut stack: Vec<(usize, usize, Option<usize>)> = vec![(left, 0, None)];
+    while let Some(&(node, index, _)) = stack.last() {
+        let candidate = above.get(node).and_then(|a| a.get(index)).copied();
+        let Some(right) = candidate else {
+            stack.pop();
+            continue;
+        };
+        if let Some(top) = stack.last_mut() {
+            top.1 += 1;
+        }
+        if get(visited, right) {
+            continue;
+        }
+        set(visited, right);
+        if let Some(owner) = match_right.get(right).copied().flatten() {
+            stack.push((owner, 0, Some(right)));
+            continue;
+        }
+        // Flip the path: each frame's left node takes the right node the next
+        // frame was reached through.
+        let mut taken = right;
+        while let Some((node, _, via)) = stack.pop() {
+            if let Some(slot) = match_right.get_mut(taken) {
+                *slot = Some(node);
+            }
+            match via {
+                Some(via) => taken = via,
+                None => break,
+            }
+        }
+        return true;
+    }
+    false
+}
+
+fn get(flags: &[bool], index: usize) -> bool {
+    flags.get(index).copied().unwrap_or(false)
+}
+
+fn set(flags: &mut [bool], index: usize) {
+    if let Some(flag) = flags.get_mut(index) {
+        *flag = true;
+    }
+}
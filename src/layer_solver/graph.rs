@@ -0,0 +1,115 @@
+//! Reachability in the constraint graph, and the layers moved files take.
+
+use crate::scc;
+use std::collections::{HashMap, VecDeque};
+
+/// New layers: kept files stay, every other strongly connected component
+/// takes its first file's layer clamped between its dependencies' layers and
+/// the lowest kept layer depending on it.
+pub fn assign(adjacent: &[Vec<usize>], label: &[u8], kept: &[bool]) -> Vec<u8> {
+    // Dependencies come first
+    let components = scc::strongly_connected(adjacent);
+    let mut component_of = vec![0; label.len()];
+    for (c, members) in components.iter().enumerate() {
+        for &n in members {
+            if let Some(slot) = component_of.get_mut(n) {
+                *slot = c;
+            }
+        }
+    }
+    let kept_label = |members: &[usize]| {
+        members
+            .iter()
+            .filter(|&&n| flag(kept, n))
+            .find_map(|&n| label.get(n).copied())
+    };
+
+    let mut ceiling = vec![u8::MAX; components.len()];
+    for (c, members) in components.iter().enumerate().rev() {
+        let own = kept_label(members).unwrap_or(u8::MAX);
+        let limit = ceiling.get(c).copied().unwrap_or(u8::MAX).min(own);
+        for &m in members
+            .iter()
+            .flat_map(|&n| adjacent.get(n).into_iter().flatten())
+        {
+            if let Some(slot) = component_of.get(m).and_then(|&d| ceiling.get_mut(d)) {
+                *slot = (*slot).min(limit);
+            }
+        }
+        if let Some(slot) = ceiling.get_mut(c) {
+            *slot = limit;
+        }
+    }
+
+    let mut value = vec![0u8; components.len()];
+    for (c, members) in components.iter().enumerate() {
+        let floor = members
+            .iter()
+            .flat_map(|&n| adjacent.get(n).into_iter().flatten())
+            .filter_map(|&m| component_of.get(m).and_then(|&d| value.get(d)).copied())
+            .max()
+            .unwrap_or(0);
+        let first = members
+            .first()
+            .and_then(|&n| label.get(n))
+            .copied()
+            .unwrap_or(0);
+        let ceiling = ceiling.get(c).copied().unwrap_or(u8::MAX);
+        let chosen = kept_label(members).unwrap_or_else(|| first.max(floor).min(ceiling));
+        if let Some(slot) = value.get_mut(c) {
+            *slot = chosen;
+        }
+    }
+    component_of
+        .iter()
+        .map(|&c| value.get(c).copied().unwrap_or(0))
+        .collect()
+}
+
+/// Nodes reachable from `start` in one or more steps.
+pub fn reachable(adjacent: &[Vec<usize>], start: usize) -> Vec<bool> {
+    let mut seen = vec![false; adjacent.len()];
+    let mut queue = VecDeque::from([start]);
+    while let Some(current) = queue.pop_front() {
+        for &next in adjacent.get(current).into_iter().flatten() {
+            if let Some(slot) = seen.get_mut(next).filter(|s| !**s) {
+                *slot = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    seen
+}
+
+/// A shortest dependency path from `from` to `to`, both included.
+pub fn path(adjacent: &[Vec<usize>], from: usize, to: usize) -> Vec<usize> {
+    let mut parent: HashMap<usize, usize> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(current) = queue.pop_front() {
+        for &next in adjacent.get(current).into_iter().flatten() {
+            if next != from && !parent.contains_key(&next) {
+                parent.insert(next, current);
+                queue.push_back(next);
+            }
+        }
+        if parent.contains_key(&to) {
+            break;
+        }
+    }
+    let mut chain = vec![to];
+    let mut current = to;
+    while let Some(&previous) = parent.get(&current) {
+        chain.push(previous);
+        current = previous;
+    }
+    chain.reverse();
+    chain
+}
+
+pub fn reached(reach: &[Vec<bool>], from: usize, to: usize) -> bool {
+    reach.get(from).is_some_and(|r| flag(r, to))
+}
+
+pub fn flag(flags: &[bool], index: usize) -> bool {
+    flags.get(index).copied().unwrap_or(false)
+}
@@ -0,0 +1,70 @@
+//! Applying and printing the proposed moves.
+
+use super::LayerMove;
+use crate::generator::GeneratorConfig;
+use crate::types::{Layer, SemmapFile};
+use std::fmt::Write;
+
+/// Moves each file's entry to its new layer, keeping layers sorted by path.
+/// A target layer the map lacks is added with its default name.
+pub fn apply(semmap: &mut SemmapFile, moves: &[LayerMove]) {
+    for layer_move in moves {
+        let mapped = semmap
+            .layers
+            .iter()
+            .any(|layer| layer.entries.iter().any(|e| e.path == layer_move.path));
+        if !mapped {
+            continue;
+        }
+        let target = layer_index(semmap, layer_move.to);
+        let entry = semmap.layers.iter_mut().find_map(|layer| {
+            let position = layer
+                .entries
+                .iter()
+                .position(|e| e.path == layer_move.path)?;
+            Some(layer.entries.remove(position))
+        });
+        if let (Some(entry), Some(target)) = (entry, semmap.layers.get_mut(target)) {
+            let position = target.entries.partition_point(|e| e.path < entry.path);
+            target.entries.insert(position, entry);
+        }
+    }
+}
+
+/// The index of layer `number`, inserted in number order if missing.
+fn layer_index(semmap: &mut SemmapFile, number: u8) -> usize {
+    if let Some(index) = semmap.layers.iter().position(|l| l.number == number) {
+        return index;
+    }
+    let index = semmap.layers.partition_point(|l| l.number < number);
+    let name = GeneratorConfig::default().layer_name(number).to_string();
+    semmap.layers.insert(index, Layer::new(number, name));
+    index
+}
+
+pub fn render_text(moves: &[LayerMove], violations: usize, semmap: &SemmapFile) -> String {
+    let mut out = String::new();
+    if violations == 0 {
+        let _ = writeln!(out, "* No layer violations");
+        return out;
+    }
+    let _ = writeln!(
+        out,
+        "{violations} layer violations; moving {} files fixes them all:",
+        moves.len()
+    );
+    let name = |number: u8| match semmap.layers.iter().find(|l| l.number == number) {
+        Some(layer) if !layer.name.is_empty() => format!("Layer {number} ({})", layer.name),
+        _ => format!("Layer {number}"),
+    };
+    for layer_move in moves {
+        let _ = writeln!(
+            out,
+            "  {}: {} -> {}",
+            layer_move.path,
+            name(layer_move.from),
+            name(layer_move.to)
+        );
+    }
+    out
+}
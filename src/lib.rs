@@ -1,17 +1,48 @@
+pub mod baseline;
+pub mod c_resolve;
+pub mod calls;
 pub mod commands;
+pub mod config;
+pub mod cycles;
+pub mod dead;
+pub mod declared_deps;
 pub mod deps;
 pub mod doc_extractor;
 pub mod error;
 pub mod exports;
+pub mod externals;
+pub mod facades;
 pub mod formatter;
 pub mod generator;
+pub mod graph_diff;
+pub mod graph_layers;
+pub mod impact;
 pub mod inference;
+pub mod js_resolve;
+pub mod jvm_resolve;
+pub mod lang_c;
+pub mod lang_js;
+pub mod lang_jvm;
 pub mod lang_python;
+pub mod lang_swift;
+pub mod layer_check;
+pub mod layer_solver;
+pub mod metrics;
 pub mod parse_entries;
 pub mod parser;
 pub mod path_utils;
+pub mod python_resolve;
+pub mod python_stdlib;
+pub mod render;
+pub mod report;
+pub mod rules;
+pub mod scc;
 pub mod stereotype;
+pub mod swift_package;
 pub mod swum;
+pub mod symbols;
+pub mod trait_edges;
+pub mod tsconfig;
 pub mod types;
 pub mod validator;
 
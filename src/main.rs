@@ -1,7 +1,11 @@
+mod cli;
+
+use cli::Commands;
 use semmap::commands;
+use semmap::config;
 
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use clap::Parser;
+use std::path::Path;
 use std::process::ExitCode;
 
 #[derive(Parser)]
@@ -13,62 +17,66 @@ struct Cli {
     command: Commands,
 }
 
-#[derive(Subcommand)]
-enum Commands {
-    /// Validate a SEMMAP file
-    Validate {
-        #[arg(short, long, default_value = "SEMMAP.md")]
-        file: PathBuf,
-        #[arg(short, long, default_value = ".")]
-        root: PathBuf,
-        #[arg(long)]
-        strict: bool,
-    },
-    /// Generate a new SEMMAP from a codebase
-    Generate {
-        #[arg(short, long, default_value = ".")]
-        root: PathBuf,
-        #[arg(short, long, default_value = "SEMMAP.md")]
-        output: PathBuf,
-        #[arg(long)]
-        name: Option<String>,
-        #[arg(long)]
-        purpose: Option<String>,
-        #[arg(long, default_value = "md")]
-        format: String,
-    },
-    /// Analyze dependencies and generate a dependency map
-    Deps {
-        #[arg(short, long, default_value = "SEMMAP.md")]
-        file: PathBuf,
-        #[arg(short, long, default_value = ".")]
-        root: PathBuf,
-        #[arg(long, default_value = "mermaid")]
-        format: String,
-        #[arg(long)]
-        check: bool,
-    },
-    /// Update an existing SEMMAP with new/removed files
-    Update {
-        #[arg(short, long, default_value = "SEMMAP.md")]
-        file: PathBuf,
-        #[arg(short, long, default_value = ".")]
-        root: PathBuf,
-    },
-}
-
 fn main() -> ExitCode {
     let cli = Cli::parse();
+    let found = match config::discover(cli.command.root().unwrap_or(Path::new("."))) {
+        Ok(found) => found,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let config = found.as_ref().map(|f| f.config.clone()).unwrap_or_default().effective();
 
     let result = match cli.command {
-        Commands::Validate { file, root, strict } => commands::validate(&file, &root, strict),
-        Commands::Generate { root, output, name, purpose, format } => {
-            commands::generate(&root, &output, name, purpose, &format)
+        Commands::Validate { location, strict, baseline } => {
+            let (file, root) = location.resolve(&config);
+            let options = commands::ValidateOptions { strict, baseline: baseline.into(), config };
+            commands::validate(&file, &root, &options)
+        }
+        Commands::Generate(args) => {
+            let (root, output, options) = args.resolve(config);
+            commands::generate(&root, &output, &options)
+        }
+        Commands::Deps(args) => {
+            let (file, root, options) = args.resolve(config);
+            commands::deps(&file, &root, &options)
+        }
+        Commands::Metrics(args) => {
+            let (file, root, options) = args.resolve(config);
+            commands::metrics(&file, &root, &options)
+        }
+        Commands::Externals { location, format, by_file } => {
+            let (file, root) = location.resolve(&config);
+            commands::externals(&file, &root, &commands::ExternalsOptions { format, by_file })
+        }
+        Commands::Report { location, html, strict } => {
+            let (file, root) = location.resolve(&config);
+            commands::report(&file, &root, &commands::ReportOptions { html, strict, config })
+        }
+        Commands::Dead { location, format, check, counts } => {
+            let (file, root) = location.resolve(&config);
+            let options = commands::DeadOptions { format, check, counts, config };
+            commands::dead(&file, &root, &options)
+        }
+        Commands::Impact { paths, location, format, tests } => {
+            let (file, root) = location.resolve(&config);
+            let options = commands::ImpactOptions { paths, format, tests, config };
+            commands::impact(&file, &root, &options)
+        }
+        Commands::FixLayers { location, pin, format, apply } => {
+            let (file, root) = location.resolve(&config);
+            let options = commands::FixLayersOptions { pin, format, apply, config };
+            commands::fix_layers(&file, &root, &options)
+        }
+        Commands::Update { location } => {
+            let (file, root) = location.resolve(&config);
+            commands::update(&file, &root, &config)
         }
-        Commands::Deps { file, root, format, check } => {
-            commands::deps(&file, &root, &format, check)
+        Commands::Config { location, format } => {
+            let options = commands::ConfigOptions { file: location.file, root: location.root, format };
+            commands::config(found.as_ref(), &options)
         }
-        Commands::Update { file, root } => commands::update(&file, &root),
     };
 
     match result {
@@ -0,0 +1,220 @@
+//! Coupling metrics over a dependency map: fan-in/fan-out, instability,
+//! abstractness, distance from the main sequence, hubs and god modules.
+
+use crate::symbols;
+use crate::types::DependencyMap;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Limits a file is checked against. Defaults mirror `slopchop.toml`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thresholds {
+    /// Maximum distinct files a file may be coupled to (either direction).
+    pub max_cbo: usize,
+    /// A hub has at least `hub_threshold` times more dependents than dependencies.
+    pub hub_threshold: f64,
+    /// Minimum dependents before a file can count as a hub.
+    pub min_hub_afferent: usize,
+    /// Total coupling (Ca + Ce) at which a non-hub file is a god module.
+    pub god_module_threshold: usize,
+    /// Maximum distance from the main sequence, if checked.
+    pub max_distance: Option<f64>,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            max_cbo: 9,
+            hub_threshold: 1.0,
+            min_hub_afferent: 5,
+            god_module_threshold: 20,
+            max_distance: None,
+        }
+    }
+}
+
+impl Thresholds {
+    /// Reads `[rules] max_cbo` and `[rules.locality]` from `<root>/slopchop.toml`,
+    /// falling back to the defaults for anything missing.
+    pub fn load(root: &Path) -> Self {
+        let mut thresholds = Self::default();
+        let Some(doc) = fs::read_to_string(root.join("slopchop.toml"))
+            .ok()
+            .and_then(|c| c.parse::<toml::Value>().ok())
+        else {
+            return thresholds;
+        };
+        let rules = doc.get("rules");
+        let locality = rules.and_then(|r| r.get("locality"));
+        let int = |table: Option<&toml::Value>, key: &str| {
+            table?
+                .get(key)?
+                .as_integer()
+                .and_then(|v| usize::try_from(v).ok())
+        };
+        if let Some(v) = int(rules, "max_cbo") {
+            thresholds.max_cbo = v;
+        }
+        if let Some(v) = int(locality, "min_hub_afferent") {
+            thresholds.min_hub_afferent = v;
+        }
+        if let Some(v) = int(locality, "god_module_threshold") {
+            thresholds.god_module_threshold = v;
+        }
+        if let Some(v) = locality.and_then(|l| l.get("hub_threshold")?.as_float()) {
+            thresholds.hub_threshold = v;
+        }
+        thresholds
+    }
+}
+
+/// Metrics for one mapped file.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileMetrics {
+    pub path: String,
+    pub layer: u8,
+    /// Afferent coupling: files that depend on this one.
+    pub afferent: usize,
+    /// Efferent coupling: files this one depends on.
+    pub efferent: usize,
+    /// Coupling between objects: distinct files on either side.
+    pub cbo: usize,
+    /// Ce / (Ca + Ce); 0 for isolated files.
+    pub instability: f64,
+    /// Abstract types (traits, interfaces, protocols) over all declared types.
+    pub abstractness: f64,
+    /// |A + I - 1|.
+    pub distance: f64,
+    pub hub: bool,
+    pub god_module: bool,
+}
+
+/// Computes metrics for every node, reading sources under `root` for abstractness.
+pub fn compute(root: &Path, depmap: &DependencyMap, thresholds: &Thresholds) -> Vec<FileMetrics> {
+    let mut incoming: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    let mut outgoing: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    for edge in &depmap.edges {
+        incoming.entry(&edge.to).or_default().insert(&edge.from);
+        outgoing.entry(&edge.from).or_default().insert(&edge.to);
+    }
+
+    depmap
+        .nodes
+        .iter()
+        .map(|node| {
+            let path = node.path.as_str();
+            let ins = incoming.get(path);
+            let outs = outgoing.get(path);
+            let afferent = ins.map_or(0, BTreeSet::len);
+            let efferent = outs.map_or(0, BTreeSet::len);
+            let cbo = ins
+                .into_iter()
+                .chain(outs)
+                .flatten()
+                .collect::<BTreeSet<_>>()
+                .len();
+            let instability = ratio(efferent, afferent + efferent);
+            let abstractness = abstractness(root, path);
+            let hub = is_hub(afferent, efferent, thresholds);
+            FileMetrics {
+                path: node.path.clone(),
+                layer: node.layer,
+                afferent,
+                efferent,
+                cbo,
+                instability,
+                abstractness,
+                distance: (abstractness + instability - 1.0).abs(),
+                hub,
+                god_module: !hub && afferent + efferent >= thresholds.god_module_threshold,
+            }
+        })
+        .collect()
+}
+
+fn is_hub(afferent: usize, efferent: usize, thresholds: &Thresholds) -> bool {
+    afferent >= thresholds.min_hub_afferent
+        && to_f64(afferent) >= thresholds.hub_threshold * to_f64(efferent)
+}
+
+fn abstractness(root: &Path, path: &str) -> f64 {
+    let Ok(content) = fs::read_to_string(root.join(path)) else {
+        return 0.0;
+    };
+    let types = symbols::type_definitions(&content, path);
+    let abstract_count = types.iter().filter(|t| t.is_abstract).count();
+    ratio(abstract_count, types.len())
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        to_f64(part) / to_f64(whole)
+    }
+}
+
+fn to_f64(n: usize) -> f64 {
+    u32::try_from(n).map_or(f64::from(u32::MAX), f64::from)
+}
+
+/// Threshold breaches that fail `metrics --check`. Hubs are expected to be
+/// heavily depended upon, so they are exempt from the coupling limit;
+/// uncoupled files are exempt from the distance limit.
+pub fn issues(metrics: &[FileMetrics], thresholds: &Thresholds) -> Vec<String> {
+    let mut issues = Vec::new();
+    for m in metrics {
+        if m.god_module {
+            issues.push(format!(
+                "God module: {} (Ca {} + Ce {} >= {})",
+                m.path, m.afferent, m.efferent, thresholds.god_module_threshold
+            ));
+        } else if !m.hub && m.cbo > thresholds.max_cbo {
+            issues.push(format!(
+                "High coupling: {} (CBO {} > {})",
+                m.path, m.cbo, thresholds.max_cbo
+            ));
+        }
+        let coupled = m.afferent + m.efferent > 0;
+        if let Some(max) = thresholds
+            .max_distance
+            .filter(|&max| coupled && m.distance > max)
+        {
+            issues.push(format!(
+                "Far from main sequence: {} (D {:.2} > {max:.2})",
+                m.path, m.distance
+            ));
+        }
+    }
+    issues
+}
+
+/// Fixed-width table, one row per file.
+pub fn render_table(metrics: &[FileMetrics]) -> String {
+    let width = metrics
+        .iter()
+        .map(|m| m.path.len())
+        .max()
+        .unwrap_or(4)
+        .max(4);
+    let mut out = format!(
+        "{:<width$}  {:>3}  {:>3}  {:>3}  {:>4}  {:>4}  {:>4}  Flags\n",
+        "Path", "Ca", "Ce", "CBO", "I", "A", "D"
+    );
+    for m in metrics {
+        let flags = match (m.hub, m.god_module) {
+            (true, _) => "hub",
+            (_, true) => "god",
+            _ => "",
+        };
+        let row = format!(
+            "{:<width$}  {:>3}  {:>3}  {:>3}  {:>4.2}  {:>4.2}  {:>4.2}  {flags}",
+            m.path, m.afferent, m.efferent, m.cbo, m.instability, m.abstractness, m.distance
+        );
+        let _ = writeln!(out, "{}", row.trim_end());
+    }
+    out
+}
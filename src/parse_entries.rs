@@ -1,8 +1,9 @@
-use crate::types::{Description, FileEntry};
+use crate::types::{AllowedDep, FileEntry};
 use regex::Regex;
 use std::sync::OnceLock;
 
 static PATH_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static TAG_RE: OnceLock<Option<Regex>> = OnceLock::new();
 
 fn path_regex() -> Option<&'static Regex> {
     PATH_RE.get_or_init(|| {
@@ -12,6 +13,10 @@ fn path_regex() -> Option<&'static Regex> {
     }).as_ref()
 }
 
+fn tag_regex() -> Option<&'static Regex> {
+    TAG_RE.get_or_init(|| Regex::new(r"`\[([^\]`]+)\]`").ok()).as_ref()
+}
+
 pub fn parse_layer_entries(lines: &[&str], idx: &mut usize) -> Vec<FileEntry> {
     let mut entries = Vec::new();
     let Some(path_re) = path_regex() else { return entries };
@@ -20,8 +25,11 @@ pub fn parse_layer_entries(lines: &[&str], idx: &mut usize) -> Vec<FileEntry> {
         if line.starts_with("## Layer") || line.starts_with("# ") { break }
         if let Some(caps) = path_re.captures(line) {
             let path = caps.get(1).map_or(String::new(), |m| m.as_str().into());
+            let rest = caps.get(0).map_or("", |m| line.get(m.end()..).unwrap_or(""));
             *idx += 1;
-            entries.push(parse_file_entry(path, lines, idx));
+            let mut entry = parse_file_entry(path, lines, idx);
+            entry.tags = parse_tags(rest);
+            entries.push(entry);
         } else {
             *idx += 1;
         }
@@ -33,6 +41,8 @@ fn parse_file_entry(path: String, lines: &[&str], idx: &mut usize) -> FileEntry
     let mut desc_parts: Vec<&str> = Vec::new();
     let mut exports = None;
     let mut touch = None;
+    let mut depends = None;
+    let mut allows = Vec::new();
     while *idx < lines.len() {
         let Some(&line) = lines.get(*idx) else { break };
         let trimmed = line.trim();
@@ -43,6 +53,12 @@ fn parse_file_entry(path: String, lines: &[&str], idx: &mut usize) -> FileEntry
         } else if let Some(rest) = trimmed.strip_prefix("→ Touch:")
             .or_else(|| trimmed.strip_prefix("Touch:")) {
             touch = Some(rest.trim().into());
+        } else if let Some(rest) = trimmed.strip_prefix("→ Depends:")
+            .or_else(|| trimmed.strip_prefix("Depends:")) {
+            depends = Some(parse_depends(rest));
+        } else if let Some(rest) = trimmed.strip_prefix("→ Allow:")
+            .or_else(|| trimmed.strip_prefix("Allow:")) {
+            allows.push(parse_allow(rest));
         } else {
             desc_parts.push(trimmed);
         }
@@ -50,13 +66,27 @@ fn parse_file_entry(path: String, lines: &[&str], idx: &mut usize) -> FileEntry
     }
     let full_desc = desc_parts.join(" ");
     let (what, why) = split_description(&full_desc);
-    FileEntry { path, description: Description { what, why }, exports, touch }
+    FileEntry { exports, touch, depends, allows, ..FileEntry::new(path, what, why) }
 }
 
 fn parse_exports(rest: &str) -> Vec<String> {
     rest.trim().split(',').map(|s| s.trim().into()).collect()
 }
 
+fn parse_tags(rest: &str) -> Vec<String> {
+    let Some(re) = tag_regex() else { return Vec::new() };
+    re.captures_iter(rest).filter_map(|c| c.get(1)).map(|m| m.as_str().into()).collect()
+}
+
+fn parse_depends(rest: &str) -> Vec<String> {
+    rest.split(',').map(|s| s.trim().trim_matches('`').into()).filter(|s: &String| !s.is_empty()).collect()
+}
+
+fn parse_allow(rest: &str) -> AllowedDep {
+    let (target, reason) = rest.split_once(" -- ").unwrap_or((rest, ""));
+    AllowedDep { target: target.trim().trim_matches('`').into(), reason: reason.trim().into() }
+}
+
 fn split_description(desc: &str) -> (String, String) {
     match desc.split_once(". ") {
         Some((first, rest)) => (format!("{first}."), rest.into()),
@@ -1,5 +1,16 @@
 use std::path::Path;
 
+/// Extensions of source files whose content semmap analyzes.
+pub const SOURCE_EXTS: [&str; 22] = [
+    "rs", "ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "py", "go", "java", "kt", "c", "h",
+    "cc", "cpp", "cxx", "hpp", "hh", "hxx", "swift",
+];
+
+/// Returns true if `ext` names an analyzed source language.
+pub fn is_source_ext(ext: &str) -> bool {
+    SOURCE_EXTS.contains(&ext)
+}
+
 /// Computes the path prefix for entries based on root's position relative to `semmap_dir`.
 /// If root is the same as `semmap_dir`, returns empty string.
 /// If root is a subdirectory of `semmap_dir`, returns the relative path.
@@ -51,3 +62,73 @@ pub fn strip_prefix_for_lookup(prefix: &str, path: &str) -> String {
             .to_string()
     }
 }
+
+/// Lexically normalizes a forward-slash relative path, resolving `.` and `..`.
+/// Returns `None` if the path escapes above its starting directory.
+pub fn normalize_path(path: &str) -> Option<String> {
+    let unified = path.replace('\\', "/");
+    let mut parts: Vec<&str> = Vec::new();
+    for part in unified.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            other => parts.push(other),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Joins a relative path onto a directory and normalizes the result.
+pub fn join_normalized(dir: &str, relative: &str) -> Option<String> {
+    if dir.is_empty() {
+        normalize_path(relative)
+    } else {
+        normalize_path(&format!("{dir}/{relative}"))
+    }
+}
+
+/// Returns the forward-slash parent directory of a relative path ("" at the top).
+pub fn parent_dir(path: &str) -> &str {
+    path.rfind('/').and_then(|i| path.get(..i)).unwrap_or("")
+}
+
+/// Matches a forward-slash path against a glob: `**` spans directories
+/// (`a/**/b` also matches `a/b`), `*` and `?` stay within one segment.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    glob_bytes(pattern.as_bytes(), path.as_bytes())
+}
+
+fn glob_bytes(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => {
+            glob_bytes(rest, path)
+                || (0..path.len()).any(|i| {
+                    path.get(i) == Some(&b'/')
+                        && glob_bytes(rest, path.get(i + 1..).unwrap_or(&[]))
+                })
+        }
+        [b'*', b'*', rest @ ..] => {
+            (0..=path.len()).any(|i| glob_bytes(rest, path.get(i..).unwrap_or(&[])))
+        }
+        [b'*', rest @ ..] => {
+            for i in 0..=path.len() {
+                if glob_bytes(rest, path.get(i..).unwrap_or(&[])) {
+                    return true;
+                }
+                if path.get(i) == Some(&b'/') {
+                    break;
+                }
+            }
+            false
+        }
+        [b'?', rest @ ..] => path
+            .split_first()
+            .is_some_and(|(c, tail)| *c != b'/' && glob_bytes(rest, tail)),
+        [c, rest @ ..] => path
+            .split_first()
+            .is_some_and(|(d, tail)| d == c && glob_bytes(rest, tail)),
+    }
+}
@@ -0,0 +1,132 @@
+//! Resolves Python module names to files using discovered package roots.
+
+use crate::path_utils;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maps dotted module names onto `.py` files or package `__init__.py` files.
+#[derive(Debug, Clone)]
+pub struct PythonResolver {
+    root: PathBuf,
+    roots: Vec<String>,
+}
+
+impl PythonResolver {
+    /// Discovers import roots: the project root, a `src/` layout, and any
+    /// package directories declared in `pyproject.toml`.
+    pub fn load(root: &Path) -> Self {
+        let mut roots = vec![String::new()];
+        if root.join("src").is_dir() {
+            roots.push("src".into());
+        }
+        for dir in pyproject_roots(root) {
+            if !roots.contains(&dir) {
+                roots.push(dir);
+            }
+        }
+        Self {
+            root: root.to_path_buf(),
+            roots,
+        }
+    }
+
+    /// Resolves an absolute dotted module (`pkg.sub.mod`) imported from `importer`.
+    pub fn resolve_absolute(&self, importer: &str, module: &str) -> Option<String> {
+        let rel = module.replace('.', "/");
+        let importer_root = self.package_root_of(importer);
+        importer_root
+            .iter()
+            .chain(self.roots.iter())
+            .find_map(|base| self.resolve_module(&path_utils::prefix_path(base, &rel)))
+    }
+
+    /// Resolves a module path (`pkg/sub/mod`) to `mod.py` or `mod/__init__.py`.
+    pub fn resolve_module(&self, rel: &str) -> Option<String> {
+        if rel.is_empty() {
+            return None;
+        }
+        let candidates = [
+            format!("{rel}.py"),
+            path_utils::prefix_path(rel, "__init__.py"),
+        ];
+        candidates.into_iter().find(|c| self.root.join(c).is_file())
+    }
+
+    /// The directory containing the outermost package that holds `importer`.
+    fn package_root_of(&self, importer: &str) -> Option<String> {
+        let mut dir = path_utils::parent_dir(importer);
+        if !self.is_package(dir) {
+            return None;
+        }
+        while self.is_package(path_utils::parent_dir(dir)) && !dir.is_empty() {
+            dir = path_utils::parent_dir(dir);
+        }
+        Some(path_utils::parent_dir(dir).to_string())
+    }
+
+    fn is_package(&self, dir: &str) -> bool {
+        !dir.is_empty()
+            && self
+                .root
+                .join(path_utils::prefix_path(dir, "__init__.py"))
+                .is_file()
+    }
+}
+
+/// Package directories from setuptools, poetry and hatch configuration.
+fn pyproject_roots(root: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(root.join("pyproject.toml")) else {
+        return Vec::new();
+    };
+    let Ok(doc) = content.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+    let tool = doc.get("tool");
+    let setuptools = tool.and_then(|t| t.get("setuptools"));
+    let mut dirs = Vec::new();
+
+    let find_where = setuptools
+        .and_then(|s| s.get("packages"))
+        .and_then(|p| p.get("find"))
+        .and_then(|f| f.get("where"));
+    dirs.extend(string_list(find_where));
+
+    let package_dir = setuptools
+        .and_then(|s| s.get("package-dir"))
+        .and_then(|d| d.get(""));
+    dirs.extend(string_list(package_dir));
+
+    let poetry_packages = tool
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("packages"))
+        .and_then(toml::Value::as_array);
+    for package in poetry_packages.into_iter().flatten() {
+        dirs.extend(string_list(package.get("from")));
+    }
+
+    let hatch_packages = tool
+        .and_then(|t| t.get("hatch"))
+        .and_then(|h| h.get("build"))
+        .and_then(|b| b.get("targets"))
+        .and_then(|t| t.get("wheel"))
+        .and_then(|w| w.get("packages"));
+    for package in string_list(hatch_packages) {
+        dirs.push(path_utils::parent_dir(&package).to_string());
+    }
+
+    dirs.into_iter()
+        .filter_map(|d| path_utils::normalize_path(&d))
+        .collect()
+}
+
+fn string_list(value: Option<&toml::Value>) -> Vec<String> {
+    match value {
+        Some(toml::Value::String(s)) => vec![s.clone()],
+        Some(toml::Value::Array(items)) => items
+            .iter()
+            .filter_map(toml::Value::as_str)
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,177 @@
+//! Dependency graph renderers: Mermaid, Graphviz DOT, `PlantUML`, `D2` and `GraphML`.
+//! Every format keeps each node's layer and each edge's kind.
+
+mod d2;
+mod dot;
+mod graphml;
+mod mermaid;
+mod plantuml;
+mod view;
+
+use crate::types::{DepNode, DependencyMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub use d2::{render_d2, render_d2_with};
+pub use dot::{render_dot, render_dot_with};
+pub use graphml::{render_graphml, render_graphml_with};
+pub use mermaid::{render_mermaid, render_mermaid_highlighted, render_mermaid_with};
+pub use plantuml::{render_plantuml, render_plantuml_with};
+pub use view::{view, view_edges, ViewOptions};
+
+/// `(from, to)` edges to draw in red, e.g. the edges of dependency cycles.
+pub type Highlighted = HashSet<(String, String)>;
+
+/// Formats accepted by [`render`].
+pub const FORMATS: [&str; 5] = ["mermaid", "dot", "plantuml", "d2", "graphml"];
+
+/// How nodes are clustered in a diagram.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Grouping {
+    #[default]
+    None,
+    Layer,
+    Directory,
+}
+
+impl Grouping {
+    /// Parses `none`, `layer` or `directory`; an empty name means none.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "" | "none" => Some(Self::None),
+            "layer" => Some(Self::Layer),
+            "directory" | "dir" => Some(Self::Directory),
+            _ => None,
+        }
+    }
+}
+
+/// Styling shared by all renderers.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Cycle edges, drawn in red.
+    pub highlighted: Highlighted,
+    /// Layer-violation edges, drawn in red.
+    pub violations: Highlighted,
+    pub grouping: Grouping,
+    /// Layer titles for layer clusters, e.g. `1 -> "Core"`.
+    pub layer_names: HashMap<u8, String>,
+    /// Edges new since a compared revision, drawn in green (Mermaid only).
+    pub added: Highlighted,
+    /// Edges gone since a compared revision, drawn dashed (Mermaid only).
+    pub removed: Highlighted,
+}
+
+impl RenderOptions {
+    fn is_red(&self, from: &str, to: &str) -> bool {
+        let key = (from.to_string(), to.to_string());
+        self.highlighted.contains(&key) || self.violations.contains(&key)
+    }
+}
+
+/// Renders `depmap` in the named format; an empty name means Mermaid.
+pub fn render(format: &str, depmap: &DependencyMap, options: &RenderOptions) -> Option<String> {
+    match format {
+        "" | "mermaid" => Some(render_mermaid_with(depmap, options)),
+        "dot" => Some(render_dot_with(depmap, options)),
+        "plantuml" | "puml" => Some(render_plantuml_with(depmap, options)),
+        "d2" => Some(render_d2_with(depmap, options)),
+        "graphml" => Some(render_graphml_with(depmap, options)),
+        _ => None,
+    }
+}
+
+fn highlight_only(highlighted: &Highlighted) -> RenderOptions {
+    RenderOptions {
+        highlighted: highlighted.clone(),
+        ..RenderOptions::default()
+    }
+}
+
+/// A cluster of nodes; `id` is empty when nodes are not grouped.
+struct Group<'a> {
+    id: String,
+    title: String,
+    nodes: Vec<&'a DepNode>,
+}
+
+fn groups<'a>(depmap: &'a DependencyMap, options: &RenderOptions) -> Vec<Group<'a>> {
+    let mut clusters: BTreeMap<(u8, String), Vec<&DepNode>> = BTreeMap::new();
+    for node in &depmap.nodes {
+        let key = match options.grouping {
+            Grouping::None => (0, String::new()),
+            Grouping::Layer => (node.layer, String::new()),
+            Grouping::Directory => (0, parent_dir(&node.path).to_string()),
+        };
+        clusters.entry(key).or_default().push(node);
+    }
+    clusters
+        .into_iter()
+        .map(|((layer, dir), nodes)| {
+            let (id, title) = match options.grouping {
+                Grouping::None => (String::new(), String::new()),
+                Grouping::Layer => (
+                    format!("layer_{layer}"),
+                    match options.layer_names.get(&layer) {
+                        Some(name) => format!("Layer {layer} -- {name}"),
+                        None => format!("Layer {layer}"),
+                    },
+                ),
+                Grouping::Directory => (format!("dir_{}", sanitize_id(&dir)), dir),
+            };
+            Group { id, title, nodes }
+        })
+        .collect()
+}
+
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map_or(".", |(dir, _)| dir)
+}
+
+/// Shortest path suffix for each node that no other node shares, so two
+/// `mod.rs` files become `a/mod.rs` and `b/mod.rs`.
+pub(crate) fn unique_labels(depmap: &DependencyMap) -> HashMap<&str, String> {
+    let segments: Vec<Vec<&str>> = depmap
+        .nodes
+        .iter()
+        .map(|n| n.path.split('/').collect())
+        .collect();
+    let mut depth = vec![1; segments.len()];
+    loop {
+        let labels: Vec<String> = segments
+            .iter()
+            .zip(&depth)
+            .map(|(segs, &d)| {
+                segs.get(segs.len().saturating_sub(d)..)
+                    .unwrap_or_default()
+                    .join("/")
+            })
+            .collect();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for label in &labels {
+            *counts.entry(label).or_default() += 1;
+        }
+        let mut changed = false;
+        for ((label, segs), d) in labels.iter().zip(&segments).zip(depth.iter_mut()) {
+            if counts.get(label.as_str()).copied().unwrap_or(0) > 1 && *d < segs.len() {
+                *d += 1;
+                changed = true;
+            }
+        }
+        if !changed {
+            return depmap
+                .nodes
+                .iter()
+                .map(|n| n.path.as_str())
+                .zip(labels)
+                .collect();
+        }
+    }
+}
+
+fn label<'a>(labels: &'a HashMap<&str, String>, path: &'a str) -> &'a str {
+    labels.get(path).map_or(path, String::as_str)
+}
+
+fn sanitize_id(path: &str) -> String {
+    path.replace(['/', '.', '-'], "_")
+}
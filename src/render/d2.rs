@@ -0,0 +1,95 @@
+use super::{groups, highlight_only, label, unique_labels, Highlighted, RenderOptions};
+use crate::types::{DepKind, DependencyMap};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+const LAYER_FILLS: [&str; 5] = ["#eef4fb", "#eaf6ee", "#fdf6e7", "#f4eef8", "#f2f2f2"];
+
+/// D2 diagram. Layers and edge kinds are D2 classes (`layer-N`, `import`,
+/// `type-import`, `trait`, `call`).
+pub fn render_d2(depmap: &DependencyMap, highlighted: &Highlighted) -> String {
+    render_d2_with(depmap, &highlight_only(highlighted))
+}
+
+/// D2 diagram with one container per group.
+pub fn render_d2_with(depmap: &DependencyMap, options: &RenderOptions) -> String {
+    let mut out = String::from("direction: down\n\nclasses: {\n");
+    let layers: BTreeSet<u8> = depmap.nodes.iter().map(|n| n.layer).collect();
+    for layer in layers {
+        let fill = LAYER_FILLS
+            .get(usize::from(layer) % LAYER_FILLS.len())
+            .copied()
+            .unwrap_or("#ffffff");
+        let _ = writeln!(out, "  layer-{layer}: {{style.fill: \"{fill}\"}}");
+    }
+    for kind in [
+        DepKind::Import,
+        DepKind::TypeImport,
+        DepKind::Trait,
+        DepKind::Call,
+    ] {
+        let style = match kind {
+            DepKind::Import => "style.stroke-dash: 0",
+            DepKind::TypeImport => "style.stroke-dash: 2",
+            DepKind::Trait => "style.stroke-dash: 5",
+            DepKind::Call => "style.stroke-width: 3",
+        };
+        let _ = writeln!(out, "  {}: {{{style}}}", kind.name());
+    }
+    out.push_str("}\n\n");
+
+    let labels = unique_labels(depmap);
+    let mut keys: HashMap<&str, String> = HashMap::new();
+    for group in groups(depmap, options) {
+        let indent = if group.id.is_empty() {
+            ""
+        } else {
+            let _ = writeln!(out, "{}: \"{}\" {{", group.id, group.title);
+            "  "
+        };
+        for node in &group.nodes {
+            let key = if group.id.is_empty() {
+                format!("\"{}\"", node.path)
+            } else {
+                format!("{}.\"{}\"", group.id, node.path)
+            };
+            let _ = writeln!(
+                out,
+                "{indent}\"{}\": \"{}\" {{class: layer-{}}}",
+                node.path,
+                label(&labels, &node.path),
+                node.layer
+            );
+            keys.insert(&node.path, key);
+        }
+        if !group.id.is_empty() {
+            out.push_str("}\n");
+        }
+    }
+
+    for edge in &depmap.edges {
+        let key = |path: &str| {
+            keys.get(path)
+                .cloned()
+                .unwrap_or_else(|| format!("\"{path}\""))
+        };
+        let label = if edge.symbols.is_empty() {
+            String::new()
+        } else {
+            format!(": \"{}\"", edge.symbols.join(", "))
+        };
+        let red = if options.is_red(&edge.from, &edge.to) {
+            "; style.stroke: \"#dd3333\""
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "{} -> {}{label} {{class: {}{red}}}",
+            key(&edge.from),
+            key(&edge.to),
+            edge.kind.name()
+        );
+    }
+    out
+}
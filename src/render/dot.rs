@@ -0,0 +1,62 @@
+use super::{groups, highlight_only, label, unique_labels, Highlighted, RenderOptions};
+use crate::types::{DepKind, DependencyMap};
+use std::fmt::Write;
+
+/// Graphviz DOT graph; highlighted edges are drawn in red.
+pub fn render_dot(depmap: &DependencyMap, highlighted: &Highlighted) -> String {
+    render_dot_with(depmap, &highlight_only(highlighted))
+}
+
+/// Graphviz DOT graph with one `cluster_*` subgraph per group.
+pub fn render_dot_with(depmap: &DependencyMap, options: &RenderOptions) -> String {
+    let mut out = String::from("digraph deps {\n    rankdir=TB;\n    node [shape=box];\n");
+    let labels = unique_labels(depmap);
+
+    for group in groups(depmap, options) {
+        let indent = if group.id.is_empty() {
+            "    "
+        } else {
+            let _ = writeln!(out, "    subgraph cluster_{} {{", group.id);
+            let _ = writeln!(out, "        label=\"{}\";", group.title);
+            "        "
+        };
+        for node in &group.nodes {
+            let _ = writeln!(
+                out,
+                "{indent}\"{}\" [label=\"{}\", layer={}];",
+                node.path,
+                label(&labels, &node.path),
+                node.layer
+            );
+        }
+        if !group.id.is_empty() {
+            out.push_str("    }\n");
+        }
+    }
+
+    for edge in &depmap.edges {
+        let style = match edge.kind {
+            DepKind::Import => "style=solid",
+            DepKind::TypeImport => "arrowhead=odot",
+            DepKind::Trait => "style=dashed",
+            DepKind::Call => "style=bold",
+        };
+        let mut attrs = vec![format!("kind=\"{}\"", edge.kind.name()), style.to_string()];
+        if !edge.symbols.is_empty() {
+            attrs.push(format!("label=\"{}\"", edge.symbols.join(", ")));
+        }
+        if options.is_red(&edge.from, &edge.to) {
+            attrs.push("color=\"#dd3333\"".to_string());
+        }
+        let _ = writeln!(
+            out,
+            "    \"{}\" -> \"{}\" [{}];",
+            edge.from,
+            edge.to,
+            attrs.join(", ")
+        );
+    }
+
+    out.push_str("}\n");
+    out
+}
@@ -0,0 +1,67 @@
+use super::{highlight_only, label, unique_labels, Highlighted, RenderOptions};
+use crate::types::DependencyMap;
+use std::fmt::Write;
+
+const KEYS: &str = r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>
+  <key id="layer" for="node" attr.name="layer" attr.type="int"/>
+  <key id="kind" for="edge" attr.name="kind" attr.type="string"/>
+  <key id="symbols" for="edge" attr.name="symbols" attr.type="string"/>
+  <key id="highlighted" for="edge" attr.name="highlighted" attr.type="boolean"/>
+"#;
+
+/// `GraphML` for yEd, Gephi and other graph tools; node ids are the paths.
+pub fn render_graphml(depmap: &DependencyMap, highlighted: &Highlighted) -> String {
+    render_graphml_with(depmap, &highlight_only(highlighted))
+}
+
+/// `GraphML` stays flat whatever the grouping: tools cluster on the `layer` key.
+pub fn render_graphml_with(depmap: &DependencyMap, options: &RenderOptions) -> String {
+    let labels = unique_labels(depmap);
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n"
+    ));
+    out.push_str(KEYS);
+    out.push_str("  <graph id=\"deps\" edgedefault=\"directed\">\n");
+
+    for node in &depmap.nodes {
+        let _ = writeln!(
+            out,
+            "    <node id=\"{}\"><data key=\"label\">{}</data><data key=\"layer\">{}</data></node>",
+            escape(&node.path),
+            escape(label(&labels, &node.path)),
+            node.layer
+        );
+    }
+
+    for (i, edge) in depmap.edges.iter().enumerate() {
+        let _ = write!(
+            out,
+            "    <edge id=\"e{i}\" source=\"{}\" target=\"{}\"><data key=\"kind\">{}</data>",
+            escape(&edge.from),
+            escape(&edge.to),
+            edge.kind.name()
+        );
+        if !edge.symbols.is_empty() {
+            let _ = write!(
+                out,
+                "<data key=\"symbols\">{}</data>",
+                escape(&edge.symbols.join(", "))
+            );
+        }
+        if options.is_red(&edge.from, &edge.to) {
+            out.push_str("<data key=\"highlighted\">true</data>");
+        }
+        out.push_str("</edge>\n");
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
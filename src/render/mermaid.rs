@@ -0,0 +1,110 @@
+use super::{
+    groups, highlight_only, label, sanitize_id, unique_labels, Highlighted, RenderOptions,
+};
+use crate::types::{DepKind, DependencyMap};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+pub fn render_mermaid(depmap: &DependencyMap) -> String {
+    render_mermaid_with(depmap, &RenderOptions::default())
+}
+
+/// Mermaid graph with the given `(from, to)` edges and their endpoints drawn
+/// in red, e.g. the edges of dependency cycles.
+pub fn render_mermaid_highlighted(depmap: &DependencyMap, highlighted: &Highlighted) -> String {
+    render_mermaid_with(depmap, &highlight_only(highlighted))
+}
+
+/// Mermaid graph with one subgraph per group; cycle and violation edges are red.
+pub fn render_mermaid_with(depmap: &DependencyMap, options: &RenderOptions) -> String {
+    let mut out = String::from("graph TD\n");
+    let labels = unique_labels(depmap);
+
+    for group in groups(depmap, options) {
+        let indent = if group.id.is_empty() {
+            "    "
+        } else {
+            let _ = writeln!(out, "    subgraph {}[\"{}\"]", group.id, group.title);
+            "        "
+        };
+        for node in &group.nodes {
+            let id = sanitize_id(&node.path);
+            let _ = writeln!(out, "{indent}{id}[\"{}\"]", label(&labels, &node.path));
+        }
+        if !group.id.is_empty() {
+            out.push_str("    end\n");
+        }
+    }
+
+    let mut red_links = Vec::new();
+    let mut added_links = Vec::new();
+    let mut removed_links = Vec::new();
+    let mut cycle_nodes = BTreeSet::new();
+    let mut violation_nodes = BTreeSet::new();
+    for (i, edge) in depmap.edges.iter().enumerate() {
+        let from_id = sanitize_id(&edge.from);
+        let to_id = sanitize_id(&edge.to);
+        let arrow = match edge.kind {
+            DepKind::Import => "-->",
+            DepKind::TypeImport => "--o",
+            DepKind::Trait => "-.->",
+            DepKind::Call => "==>",
+        };
+        if edge.symbols.is_empty() {
+            let _ = writeln!(out, "    {from_id} {arrow} {to_id}");
+        } else {
+            let label = edge.symbols.join(", ");
+            let _ = writeln!(out, "    {from_id} {arrow}|\"{label}\"| {to_id}");
+        }
+        let key = (edge.from.clone(), edge.to.clone());
+        if options.highlighted.contains(&key) {
+            cycle_nodes.extend([from_id.clone(), to_id.clone()]);
+        }
+        if options.violations.contains(&key) {
+            violation_nodes.insert(from_id);
+        }
+        if options.added.contains(&key) {
+            added_links.push(i.to_string());
+        }
+        if options.removed.contains(&key) {
+            removed_links.push(i.to_string());
+        }
+        if options.is_red(&edge.from, &edge.to) {
+            red_links.push(i.to_string());
+        }
+    }
+
+    // Red goes last so a new violation or cycle edge stays red
+    write_link_style(&mut out, &added_links, "stroke:#2a2,stroke-width:2px");
+    write_link_style(&mut out, &removed_links, "stroke:#999,stroke-dasharray:4 4");
+    write_link_style(&mut out, &red_links, "stroke:#d33,stroke-width:2px");
+    write_class(
+        &mut out,
+        "cycle",
+        "stroke:#d33,stroke-width:2px",
+        cycle_nodes,
+    );
+    write_class(
+        &mut out,
+        "violation",
+        "fill:#fdd,stroke:#d33",
+        violation_nodes,
+    );
+
+    out
+}
+
+fn write_link_style(out: &mut String, links: &[String], style: &str) {
+    if !links.is_empty() {
+        let _ = writeln!(out, "    linkStyle {} {style}", links.join(","));
+    }
+}
+
+fn write_class(out: &mut String, class: &str, style: &str, nodes: BTreeSet<String>) {
+    if nodes.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "    classDef {class} {style}");
+    let nodes: Vec<_> = nodes.into_iter().collect();
+    let _ = writeln!(out, "    class {} {class}", nodes.join(","));
+}
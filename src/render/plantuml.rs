@@ -0,0 +1,69 @@
+use super::{
+    groups, highlight_only, label, sanitize_id, unique_labels, Highlighted, RenderOptions,
+};
+use crate::types::{DepKind, DependencyMap};
+use std::fmt::Write;
+
+/// `PlantUML` component diagram. Layers become `<<Ln>>` stereotypes and edge
+/// kinds become line styles plus a label.
+pub fn render_plantuml(depmap: &DependencyMap, highlighted: &Highlighted) -> String {
+    render_plantuml_with(depmap, &highlight_only(highlighted))
+}
+
+/// `PlantUML` component diagram with one package per group.
+pub fn render_plantuml_with(depmap: &DependencyMap, options: &RenderOptions) -> String {
+    let mut out = String::from("@startuml\nskinparam componentStyle rectangle\n");
+    let labels = unique_labels(depmap);
+
+    for group in groups(depmap, options) {
+        let indent = if group.id.is_empty() {
+            ""
+        } else {
+            let _ = writeln!(out, "package \"{}\" {{", group.title);
+            "  "
+        };
+        for node in &group.nodes {
+            let _ = writeln!(
+                out,
+                "{indent}component \"{}\" as {} <<L{}>>",
+                label(&labels, &node.path),
+                sanitize_id(&node.path),
+                node.layer
+            );
+        }
+        if !group.id.is_empty() {
+            out.push_str("}\n");
+        }
+    }
+
+    for edge in &depmap.edges {
+        let mut style: Vec<&str> = Vec::new();
+        if options.is_red(&edge.from, &edge.to) {
+            style.push("#dd3333");
+        }
+        match edge.kind {
+            DepKind::Import => {}
+            DepKind::TypeImport => style.push("dotted"),
+            DepKind::Trait => style.push("dashed"),
+            DepKind::Call => style.push("bold"),
+        }
+        let arrow = if style.is_empty() {
+            "-->".to_string()
+        } else {
+            format!("-[{}]->", style.join(","))
+        };
+        let mut label = edge.kind.name().to_string();
+        if !edge.symbols.is_empty() {
+            let _ = write!(label, " ({})", edge.symbols.join(", "));
+        }
+        let _ = writeln!(
+            out,
+            "{} {arrow} {} : {label}",
+            sanitize_id(&edge.from),
+            sanitize_id(&edge.to)
+        );
+    }
+
+    out.push_str("@enduml\n");
+    out
+}
@@ -0,0 +1,159 @@
+use super::{parent_dir, Highlighted};
+use crate::path_utils;
+use crate::types::{DepEdge, DepNode, DependencyMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Which part of the graph a diagram shows.
+#[derive(Debug, Clone, Default)]
+pub struct ViewOptions {
+    /// Keep only files matching one of these globs or directory prefixes.
+    pub include: Vec<String>,
+    /// Drop files matching one of these globs or directory prefixes.
+    pub exclude: Vec<String>,
+    /// Show only files within `depth` edges of this file, in either direction.
+    pub focus: Option<String>,
+    /// Hops around `focus`; defaults to 1.
+    pub depth: Option<usize>,
+    /// Merge the files of each directory into one node.
+    pub collapse: bool,
+}
+
+impl ViewOptions {
+    /// The node a file is drawn as: itself, or its directory when collapsing.
+    pub fn node_key(&self, path: &str) -> String {
+        if self.collapse {
+            parent_dir(path).to_string()
+        } else {
+            path.to_string()
+        }
+    }
+
+    fn keeps(&self, path: &str) -> bool {
+        let matches = |patterns: &[String]| patterns.iter().any(|p| path_matches(p, path));
+        (self.include.is_empty() || matches(&self.include)) && !matches(&self.exclude)
+    }
+}
+
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let prefix = pattern.trim_end_matches('/');
+    path_utils::glob_match(pattern, path)
+        || path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Applies filters, then focus, then directory collapsing.
+pub fn view(depmap: &DependencyMap, options: &ViewOptions) -> Result<DependencyMap, String> {
+    let mut kept: HashSet<&str> = depmap
+        .nodes
+        .iter()
+        .map(|n| n.path.as_str())
+        .filter(|p| options.keeps(p))
+        .collect();
+    if let Some(focus) = &options.focus {
+        if !kept.contains(focus.as_str()) {
+            return Err(format!("Focus file {focus} is not in the graph"));
+        }
+        kept = neighbourhood(depmap, &kept, focus, options.depth.unwrap_or(1));
+    }
+    let nodes: Vec<&DepNode> = depmap
+        .nodes
+        .iter()
+        .filter(|n| kept.contains(n.path.as_str()))
+        .collect();
+    let edges: Vec<&DepEdge> = depmap
+        .edges
+        .iter()
+        .filter(|e| kept.contains(e.from.as_str()) && kept.contains(e.to.as_str()))
+        .collect();
+    if options.collapse {
+        return Ok(collapse(&nodes, &edges, options));
+    }
+    Ok(DependencyMap {
+        nodes: nodes.into_iter().cloned().collect(),
+        edges: edges.into_iter().cloned().collect(),
+    })
+}
+
+/// Maps highlighted `(from, to)` pairs onto the nodes of a view.
+pub fn view_edges(highlighted: &Highlighted, options: &ViewOptions) -> Highlighted {
+    highlighted
+        .iter()
+        .map(|(from, to)| (options.node_key(from), options.node_key(to)))
+        .filter(|(from, to)| from != to)
+        .collect()
+}
+
+fn neighbourhood<'a>(
+    depmap: &'a DependencyMap,
+    kept: &HashSet<&str>,
+    focus: &'a str,
+    depth: usize,
+) -> HashSet<&'a str> {
+    let mut adjacent: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in &depmap.edges {
+        if kept.contains(edge.from.as_str()) && kept.contains(edge.to.as_str()) {
+            adjacent.entry(&edge.from).or_default().push(&edge.to);
+            adjacent.entry(&edge.to).or_default().push(&edge.from);
+        }
+    }
+    let mut seen = HashSet::from([focus]);
+    let mut queue = VecDeque::from([(focus, 0)]);
+    while let Some((path, distance)) = queue.pop_front() {
+        if distance == depth {
+            continue;
+        }
+        for &next in adjacent.get(path).into_iter().flatten() {
+            if seen.insert(next) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    seen
+}
+
+/// One node per directory, on the lowest layer of its files; edges between
+/// directories keep their kind and merge their symbols.
+fn collapse(nodes: &[&DepNode], edges: &[&DepEdge], options: &ViewOptions) -> DependencyMap {
+    let mut layers: BTreeMap<String, u8> = BTreeMap::new();
+    for node in nodes {
+        let layer = layers
+            .entry(options.node_key(&node.path))
+            .or_insert(node.layer);
+        *layer = (*layer).min(node.layer);
+    }
+    let mut merged: BTreeMap<(String, String, &str), (DepEdge, BTreeSet<String>)> = BTreeMap::new();
+    for edge in edges {
+        let from = options.node_key(&edge.from);
+        let to = options.node_key(&edge.to);
+        if from == to {
+            continue;
+        }
+        let (_, symbols) = merged
+            .entry((from.clone(), to.clone(), edge.kind.name()))
+            .or_insert_with(|| {
+                let edge = DepEdge {
+                    from,
+                    to,
+                    kind: edge.kind,
+                    symbols: Vec::new(),
+                };
+                (edge, BTreeSet::new())
+            });
+        symbols.extend(edge.symbols.iter().cloned());
+    }
+    DependencyMap {
+        nodes: layers
+            .into_iter()
+            .map(|(path, layer)| DepNode { path, layer })
+            .collect(),
+        edges: merged
+            .into_values()
+            .map(|(edge, symbols)| DepEdge {
+                symbols: symbols.into_iter().collect(),
+                ..edge
+            })
+            .collect(),
+    }
+}
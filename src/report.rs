@@ -0,0 +1,170 @@
+//! Self-contained HTML report: the map, an interactive dependency graph,
+//! validation issues and layer violations, with no network assets.
+
+mod graph_svg;
+
+use crate::error::Severity;
+use crate::layer_check::LayerViolation;
+use crate::types::{DependencyMap, FileEntry, SemmapFile};
+use crate::validator::ValidationResult;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+const STYLE: &str = include_str!("report/report.css");
+const SCRIPT: &str = include_str!("report/report.js");
+
+/// Renders the whole report as one HTML document.
+pub fn render_html(
+    semmap: &SemmapFile,
+    depmap: &DependencyMap,
+    validation: &ValidationResult,
+    violations: &[LayerViolation],
+) -> String {
+    let title = escape(&semmap.project_name);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title} -- Semantic Map</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n"
+    );
+    write_header(&mut out, semmap, depmap, validation, violations.len());
+
+    let red: HashSet<(String, String)> = violations
+        .iter()
+        .map(|v| {
+            (
+                v.from.clone(),
+                v.via.clone().unwrap_or_else(|| v.to.clone()),
+            )
+        })
+        .collect();
+    let _ = writeln!(
+        out,
+        "<section id=\"graph\">\n<h2>Dependencies</h2>\n<div id=\"graph-box\">\n{}\n</div>\n\
+         <p class=\"meta\">Scroll to zoom, drag to pan, click a file to highlight its neighbours.</p>\n</section>",
+        graph_svg::render_svg(depmap, &red)
+    );
+
+    write_issues(&mut out, validation, violations);
+    write_map(&mut out, semmap);
+    let _ = write!(out, "<script>\n{SCRIPT}</script>\n</body>\n</html>\n");
+    out
+}
+
+fn write_header(
+    out: &mut String,
+    semmap: &SemmapFile,
+    depmap: &DependencyMap,
+    validation: &ValidationResult,
+    violations: usize,
+) {
+    let _ = writeln!(out, "<header>\n<h1>{}</h1>", escape(&semmap.project_name));
+    if !semmap.purpose.is_empty() {
+        let _ = writeln!(out, "<p>{}</p>", escape(&semmap.purpose));
+    }
+    let _ = writeln!(
+        out,
+        "<div class=\"stats\"><span>{} files</span><span>{} dependencies</span>\
+         <span>{} errors</span><span>{} warnings</span><span>{violations} layer violations</span></div>",
+        semmap.all_paths().len(),
+        depmap.edges.len(),
+        validation.error_count(),
+        validation.warning_count()
+    );
+    out.push_str(
+        "<input id=\"search\" type=\"search\" placeholder=\"Search files, descriptions, exports, issues\">\n</header>\n",
+    );
+}
+
+fn write_issues(out: &mut String, validation: &ValidationResult, violations: &[LayerViolation]) {
+    out.push_str("<section id=\"issues\">\n<h2>Issues</h2>\n");
+    if validation.issues.is_empty() && violations.is_empty() {
+        out.push_str("<p>No validation issues or layer violations.</p>\n</section>\n");
+        return;
+    }
+    out.push_str("<table>\n<tr><th>Severity</th><th>File</th><th>Issue</th></tr>\n");
+    for issue in &validation.issues {
+        let (class, name) = match issue.severity {
+            Severity::Error => ("error", "error"),
+            Severity::Warning => ("warning", "warning"),
+        };
+        let path = issue.path.as_deref().unwrap_or("");
+        write_issue_row(out, class, name, path, &issue.message);
+    }
+    for v in violations {
+        let message = format!("{v}. Fix: {}", v.suggestion);
+        write_issue_row(out, "error", "violation", &v.from, &message);
+    }
+    out.push_str("</table>\n</section>\n");
+}
+
+fn write_issue_row(out: &mut String, class: &str, name: &str, path: &str, message: &str) {
+    let search = format!("{path} {message}").to_lowercase();
+    let _ = writeln!(
+        out,
+        "<tr data-search=\"{}\"><td class=\"{class}\">{name}</td><td><code>{}</code></td><td>{}</td></tr>",
+        escape(&search),
+        escape(path),
+        escape(message)
+    );
+}
+
+fn write_map(out: &mut String, semmap: &SemmapFile) {
+    out.push_str("<section id=\"map\">\n<h2>Map</h2>\n");
+    for layer in &semmap.layers {
+        let _ = writeln!(
+            out,
+            "<h3>Layer {} -- {}</h3>",
+            layer.number,
+            escape(&layer.name)
+        );
+        for entry in &layer.entries {
+            write_entry(out, entry);
+        }
+    }
+    out.push_str("</section>\n");
+}
+
+fn write_entry(out: &mut String, entry: &FileEntry) {
+    let what = &entry.description.what;
+    let why = &entry.description.why;
+    let exports = entry.exports.as_deref().unwrap_or_default().join(", ");
+    let search = format!(
+        "{} {} {what} {why} {exports}",
+        entry.path,
+        entry.tags.join(" ")
+    );
+    let _ = write!(
+        out,
+        "<div class=\"entry\" data-path=\"{}\" data-search=\"{}\"><code>{}</code>",
+        escape(&entry.path),
+        escape(&search.to_lowercase()),
+        escape(&entry.path)
+    );
+    for tag in &entry.tags {
+        let _ = write!(out, "<span class=\"tag\">{}</span>", escape(tag));
+    }
+    let _ = write!(out, "<div>{} {}</div>", escape(what), escape(why));
+    let mut meta = Vec::new();
+    if !exports.is_empty() {
+        meta.push(format!("Exports: {exports}"));
+    }
+    if let Some(touch) = &entry.touch {
+        meta.push(format!("Touch: {touch}"));
+    }
+    if let Some(depends) = &entry.depends {
+        meta.push(format!("Depends: {}", depends.join(", ")));
+    }
+    for allow in &entry.allows {
+        meta.push(format!("Allow: {} -- {}", allow.target, allow.reason));
+    }
+    for line in meta {
+        let _ = write!(out, "<div class=\"meta\">{}</div>", escape(&line));
+    }
+    out.push_str("</div>\n");
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
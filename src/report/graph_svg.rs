@@ -0,0 +1,112 @@
+use super::escape;
+use crate::render;
+use crate::types::{DepNode, DependencyMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+const NODE_W: u32 = 170;
+const NODE_H: u32 = 28;
+const GAP_X: u32 = 20;
+const ROW_H: u32 = 80;
+const LEFT: u32 = 56;
+const PER_ROW: usize = 8;
+
+/// Layered SVG: one band per layer (wrapped every `PER_ROW` files), edges
+/// drawn as straight arrows; `violations` edges get the `violation` class.
+pub fn render_svg(depmap: &DependencyMap, violations: &HashSet<(String, String)>) -> String {
+    let labels = render::unique_labels(depmap);
+    let mut by_layer: BTreeMap<u8, Vec<&DepNode>> = BTreeMap::new();
+    for node in &depmap.nodes {
+        by_layer.entry(node.layer).or_default().push(node);
+    }
+
+    let mut nodes = String::new();
+    let mut positions: HashMap<&str, (u32, u32)> = HashMap::new();
+    let mut row = 0u32;
+    let mut widest = 0usize;
+    for (layer, mut members) in by_layer {
+        members.sort_by(|a, b| a.path.cmp(&b.path));
+        let _ = writeln!(
+            nodes,
+            "<text class=\"band\" x=\"8\" y=\"{}\">L{layer}</text>",
+            row * ROW_H + 38
+        );
+        for chunk in members.chunks(PER_ROW) {
+            widest = widest.max(chunk.len());
+            for (x, node) in (0u32..).zip(chunk) {
+                let pos = (LEFT + x * (NODE_W + GAP_X), row * ROW_H + 20);
+                positions.insert(&node.path, pos);
+                let label = labels.get(node.path.as_str()).map_or("", String::as_str);
+                let _ = writeln!(
+                    nodes,
+                    "<g class=\"node layer-{layer}\" data-path=\"{path}\" transform=\"translate({},{})\">\
+                     <rect width=\"{NODE_W}\" height=\"{NODE_H}\" rx=\"4\"/>\
+                     <text x=\"8\" y=\"18\">{}</text><title>{path}</title></g>",
+                    pos.0,
+                    pos.1,
+                    escape(label),
+                    path = escape(&node.path)
+                );
+            }
+            row += 1;
+        }
+    }
+
+    let mut edges = String::new();
+    for edge in &depmap.edges {
+        let (Some(&from), Some(&to)) = (
+            positions.get(edge.from.as_str()),
+            positions.get(edge.to.as_str()),
+        ) else {
+            continue;
+        };
+        let ((x1, y1), (x2, y2)) = anchors(from, to);
+        let red = if violations.contains(&(edge.from.clone(), edge.to.clone())) {
+            " violation"
+        } else {
+            ""
+        };
+        let mut title = format!("{} -> {} ({})", edge.from, edge.to, edge.kind.name());
+        if !edge.symbols.is_empty() {
+            let _ = write!(title, ": {}", edge.symbols.join(", "));
+        }
+        let _ = writeln!(
+            edges,
+            "<line class=\"edge kind-{}{red}\" data-from=\"{}\" data-to=\"{}\" x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\"><title>{}</title></line>",
+            edge.kind.name(),
+            escape(&edge.from),
+            escape(&edge.to),
+            escape(&title)
+        );
+    }
+
+    let width = LEFT + u32::try_from(widest).unwrap_or(1) * (NODE_W + GAP_X) + 20;
+    let height = row * ROW_H + 20;
+    format!(
+        "<svg id=\"graph-svg\" viewBox=\"0 0 {width} {height}\" data-width=\"{width}\" data-height=\"{height}\">\n\
+         <defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"7\" markerHeight=\"7\" orient=\"auto\">\
+         <path d=\"M0,0 L10,5 L0,10 z\"/></marker></defs>\n\
+         <g id=\"viewport\">\n{edges}{nodes}</g>\n</svg>"
+    )
+}
+
+/// Connection points: bottom to top when the target is lower, top to bottom
+/// when it is higher, side to side within a row.
+fn anchors(from: (u32, u32), to: (u32, u32)) -> ((u32, u32), (u32, u32)) {
+    let cx = |p: (u32, u32)| p.0 + NODE_W / 2;
+    if to.1 > from.1 {
+        ((cx(from), from.1 + NODE_H), (cx(to), to.1))
+    } else if to.1 < from.1 {
+        ((cx(from), from.1), (cx(to), to.1 + NODE_H))
+    } else if to.0 > from.0 {
+        (
+            (from.0 + NODE_W, from.1 + NODE_H / 2),
+            (to.0, to.1 + NODE_H / 2),
+        )
+    } else {
+        (
+            (from.0, from.1 + NODE_H / 2),
+            (to.0 + NODE_W, to.1 + NODE_H / 2),
+        )
+    }
+}
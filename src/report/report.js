@@ -0,0 +1,70 @@
+(function () {
+  var svg = document.getElementById('graph-svg');
+  var box = document.getElementById('graph-box');
+  var view = { x: 0, y: 0, w: +svg.dataset.width, h: +svg.dataset.height };
+  function apply() { svg.setAttribute('viewBox', [view.x, view.y, view.w, view.h].join(' ')); }
+
+  box.addEventListener('wheel', function (e) {
+    e.preventDefault();
+    var r = svg.getBoundingClientRect();
+    var px = view.x + (e.clientX - r.left) / r.width * view.w;
+    var py = view.y + (e.clientY - r.top) / r.height * view.h;
+    var k = e.deltaY < 0 ? 0.85 : 1.18;
+    view.x = px - (px - view.x) * k; view.y = py - (py - view.y) * k;
+    view.w *= k; view.h *= k; apply();
+  }, { passive: false });
+
+  var drag = null;
+  box.addEventListener('mousedown', function (e) { drag = { x: e.clientX, y: e.clientY, moved: false }; });
+  window.addEventListener('mouseup', function () { setTimeout(function () { drag = null; }, 0); });
+  window.addEventListener('mousemove', function (e) {
+    if (!drag) return;
+    var r = svg.getBoundingClientRect();
+    var dx = e.clientX - drag.x, dy = e.clientY - drag.y;
+    if (Math.abs(dx) + Math.abs(dy) > 2) drag.moved = true;
+    view.x -= dx / r.width * view.w; view.y -= dy / r.height * view.h;
+    drag.x = e.clientX; drag.y = e.clientY; apply();
+  });
+
+  var nodes = Array.prototype.slice.call(svg.querySelectorAll('.node'));
+  var edges = Array.prototype.slice.call(svg.querySelectorAll('.edge'));
+  function select(path) {
+    var keep = {};
+    keep[path] = true;
+    edges.forEach(function (l) {
+      var on = l.dataset.from === path || l.dataset.to === path;
+      if (on) { keep[l.dataset.from] = true; keep[l.dataset.to] = true; }
+      l.classList.toggle('dim', !!path && !on);
+    });
+    nodes.forEach(function (n) {
+      n.classList.toggle('dim', !!path && !keep[n.dataset.path]);
+      n.classList.toggle('selected', n.dataset.path === path);
+    });
+  }
+  nodes.forEach(function (n) {
+    n.addEventListener('click', function (e) {
+      e.stopPropagation();
+      if (drag && drag.moved) return;
+      select(n.dataset.path);
+      var entry = document.querySelector('.entry[data-path="' + CSS.escape(n.dataset.path) + '"]');
+      if (entry) entry.scrollIntoView({ block: 'nearest' });
+    });
+  });
+  svg.addEventListener('click', function () { if (!drag || !drag.moved) select(''); });
+  document.querySelectorAll('.entry').forEach(function (entry) {
+    entry.addEventListener('click', function () {
+      select(entry.dataset.path);
+      box.scrollIntoView({ block: 'nearest' });
+    });
+  });
+
+  document.getElementById('search').addEventListener('input', function (e) {
+    var q = e.target.value.trim().toLowerCase();
+    document.querySelectorAll('[data-search]').forEach(function (el) {
+      el.classList.toggle('hidden', !!q && el.dataset.search.indexOf(q) < 0);
+    });
+    nodes.forEach(function (n) {
+      n.classList.toggle('match', !!q && n.dataset.path.toLowerCase().indexOf(q) >= 0);
+    });
+  });
+})();
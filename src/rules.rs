@@ -0,0 +1,256 @@
+//! Declarative architecture rules loaded from `[[rules]]` in `semmap.toml`
+//! (or wherever `config` finds the project configuration).
+//!
+//! ```toml
+//! [[rules]]
+//! name = "domain-deps"
+//! message = "Domain code may only use config and utilities"
+//! layer = 2
+//! may_depend_on = [0, 3]
+//!
+//! [[rules]]
+//! name = "db-through-repo"
+//! to = "src/db/**"
+//! only_from = ["src/repo/**"]
+//!
+//! [[rules]]
+//! name = "no-test-imports"
+//! to = "tests/**"
+//!
+//! [[rules]]
+//! name = "sql-in-storage"
+//! external = ["sqlx", "diesel"]
+//! layers = [3]
+//! ```
+
+use crate::externals::Inventory;
+use crate::path_utils;
+use crate::types::{DepEdge, DependencyMap};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// One glob or a list of globs.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Globs {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Globs {
+    pub fn matches(&self, path: &str) -> bool {
+        match self {
+            Self::One(glob) => path_utils::glob_match(glob, path),
+            Self::Many(globs) => globs.iter().any(|g| path_utils::glob_match(g, path)),
+        }
+    }
+}
+
+/// A named constraint on dependency edges.
+///
+/// Layer rules (`layer` + `may_depend_on`) restrict which layers a layer may
+/// use; its own layer is always allowed. Path rules (`to`, optionally `from`)
+/// forbid matching edges, or with `only_from` allow them only from matching
+/// files. Files matching `to` may always depend on each other.
+///
+/// External rules (`external`, with `layers` and/or `only_from`) restrict
+/// which files may use matching third-party packages.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub name: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub layer: Option<u8>,
+    #[serde(default)]
+    pub may_depend_on: Option<Vec<u8>>,
+    #[serde(default)]
+    pub from: Option<Globs>,
+    #[serde(default)]
+    pub to: Option<Globs>,
+    #[serde(default)]
+    pub only_from: Option<Globs>,
+    #[serde(default)]
+    pub external: Option<Globs>,
+    #[serde(default)]
+    pub layers: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesFile {
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+/// An edge that breaks a rule; `to` is the package name for external rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleViolation {
+    pub rule: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Loads rules from `explicit` if given, else the `[[rules]]` tables of the
+/// project configuration (see `config`).
+pub fn load(configured: &[toml::Value], explicit: Option<&Path>) -> Result<Vec<Rule>, String> {
+    let Some(path) = explicit else {
+        return from_values(configured.to_vec());
+    };
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    parse(&content).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+pub fn parse(content: &str) -> Result<Vec<Rule>, String> {
+    let file: RulesFile = toml::from_str(content).map_err(|e| e.to_string())?;
+    check(&file.rules)?;
+    Ok(file.rules)
+}
+
+/// Rules from already parsed `[[rules]]` tables.
+pub fn from_values(values: Vec<toml::Value>) -> Result<Vec<Rule>, String> {
+    let rules = values
+        .into_iter()
+        .map(|value| value.try_into().map_err(|e: toml::de::Error| e.to_string()))
+        .collect::<Result<Vec<Rule>, String>>()?;
+    check(&rules)?;
+    Ok(rules)
+}
+
+fn check(rules: &[Rule]) -> Result<(), String> {
+    let mut names = HashSet::new();
+    for rule in rules {
+        if !names.insert(rule.name.as_str()) {
+            return Err(format!("rule '{}' is declared more than once", rule.name));
+        }
+        if rule.layer.is_some() != rule.may_depend_on.is_some() {
+            return Err(format!(
+                "rule '{}' needs both `layer` and `may_depend_on`",
+                rule.name
+            ));
+        }
+        let layer_rule = rule.layer.is_some() && rule.may_depend_on.is_some();
+        let external_rule =
+            rule.external.is_some() && (rule.layers.is_some() || rule.only_from.is_some());
+        if !layer_rule && !external_rule && rule.to.is_none() {
+            return Err(format!(
+                "rule '{}' needs `layer` with `may_depend_on`, `to`, \
+                 or `external` with `layers` or `only_from`",
+                rule.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Every edge that breaks a rule, in rule order.
+pub fn evaluate(rules: &[Rule], depmap: &DependencyMap) -> Vec<RuleViolation> {
+    let layers: HashMap<&str, u8> = depmap
+        .nodes
+        .iter()
+        .map(|n| (n.path.as_str(), n.layer))
+        .collect();
+    let mut violations = Vec::new();
+    for rule in rules {
+        let mut seen = HashSet::new();
+        for edge in &depmap.edges {
+            if breaks(rule, edge, &layers) && seen.insert((&edge.from, &edge.to)) {
+                violations.push(RuleViolation {
+                    rule: rule.name.clone(),
+                    from: edge.from.clone(),
+                    to: edge.to.clone(),
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// Every use of an external package that breaks a rule, in rule order.
+pub fn evaluate_externals(
+    rules: &[Rule],
+    inventory: &Inventory,
+    depmap: &DependencyMap,
+) -> Vec<RuleViolation> {
+    let layers: HashMap<&str, u8> = depmap
+        .nodes
+        .iter()
+        .map(|n| (n.path.as_str(), n.layer))
+        .collect();
+    let mut violations = Vec::new();
+    for rule in rules {
+        let Some(external) = &rule.external else {
+            continue;
+        };
+        for package in inventory
+            .packages
+            .iter()
+            .filter(|p| external.matches(&p.name))
+        {
+            for file in &package.files {
+                let layer = layers.get(file.as_str());
+                let in_layer = rule
+                    .layers
+                    .as_ref()
+                    .is_none_or(|allowed| layer.is_some_and(|l| allowed.contains(l)));
+                let from_allowed = rule.only_from.as_ref().is_none_or(|g| g.matches(file));
+                if !(in_layer && from_allowed) {
+                    violations.push(RuleViolation {
+                        rule: rule.name.clone(),
+                        from: file.clone(),
+                        to: package.name.clone(),
+                    });
+                }
+            }
+        }
+    }
+    violations
+}
+
+fn breaks(rule: &Rule, edge: &DepEdge, layers: &HashMap<&str, u8>) -> bool {
+    if let (Some(layer), Some(allowed)) = (rule.layer, &rule.may_depend_on) {
+        let from = layers.get(edge.from.as_str()).copied();
+        let to = layers.get(edge.to.as_str()).copied();
+        if let (Some(from), Some(to)) = (from, to) {
+            if from == layer && to != layer && !allowed.contains(&to) {
+                return true;
+            }
+        }
+    }
+    let Some(to) = &rule.to else {
+        return false;
+    };
+    if !to.matches(&edge.to) || to.matches(&edge.from) {
+        return false;
+    }
+    if rule
+        .from
+        .as_ref()
+        .is_some_and(|from| !from.matches(&edge.from))
+    {
+        return false;
+    }
+    rule.only_from
+        .as_ref()
+        .is_none_or(|allowed| !allowed.matches(&edge.from))
+}
+
+/// Groups violations under their rule's name and message.
+pub fn report(rules: &[Rule], violations: &[RuleViolation]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for rule in rules {
+        let broken: Vec<&RuleViolation> =
+            violations.iter().filter(|v| v.rule == rule.name).collect();
+        if broken.is_empty() {
+            continue;
+        }
+        match &rule.message {
+            Some(message) => lines.push(format!("Rule {}: {message}", rule.name)),
+            None => lines.push(format!("Rule {}", rule.name)),
+        }
+        lines.extend(broken.iter().map(|v| format!("    {} -> {}", v.from, v.to)));
+    }
+    lines
+}
@@ -0,0 +1,113 @@
+//! Strongly connected components (Tarjan), over adjacency lists.
+
+/// Components of the graph whose node `i` has successors `adjacent[i]`.
+/// Components come out in reverse topological order.
+pub fn strongly_connected(adjacent: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut tarjan = Tarjan::new(adjacent);
+    for root in 0..adjacent.len() {
+        if tarjan.index_of(root).is_none() {
+            tarjan.visit(root);
+        }
+    }
+    tarjan.components
+}
+
+struct Tarjan<'a> {
+    adjacent: &'a [Vec<usize>],
+    next_index: usize,
+    index: Vec<Option<usize>>,
+    lowlink: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn new(adjacent: &'a [Vec<usize>]) -> Self {
+        let size = adjacent.len();
+        Self {
+            adjacent,
+            next_index: 0,
+            index: vec![None; size],
+            lowlink: vec![0; size],
+            on_stack: vec![false; size],
+            stack: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+
+    fn index_of(&self, node: usize) -> Option<usize> {
+        self.index.get(node).copied().flatten()
+    }
+
+    fn low(&self, node: usize) -> usize {
+        self.lowlink.get(node).copied().unwrap_or(0)
+    }
+
+    /// Depth-first search with an explicit stack of `(node, next child)`, so
+    /// deep graphs cannot overflow the call stack.
+    fn visit(&mut self, root: usize) {
+        let mut work = vec![(root, 0usize)];
+        self.open(root);
+        while let Some(&(node, child)) = work.last() {
+            let next = self.adjacent.get(node).and_then(|s| s.get(child)).copied();
+            if let Some(next) = next {
+                if let Some(top) = work.last_mut() {
+                    top.1 += 1;
+                }
+                match self.index_of(next) {
+                    None => {
+                        self.open(next);
+                        work.push((next, 0));
+                    }
+                    Some(i) if self.on_stack.get(next).copied().unwrap_or(false) => {
+                        self.lower(node, i);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                self.lower(parent, self.low(node));
+            }
+            if Some(self.low(node)) == self.index_of(node) {
+                self.close(node);
+            }
+        }
+    }
+
+    fn open(&mut self, node: usize) {
+        if let Some(slot) = self.index.get_mut(node) {
+            *slot = Some(self.next_index);
+        }
+        if let Some(slot) = self.lowlink.get_mut(node) {
+            *slot = self.next_index;
+        }
+        if let Some(slot) = self.on_stack.get_mut(node) {
+            *slot = true;
+        }
+        self.next_index += 1;
+        self.stack.push(node);
+    }
+
+    fn lower(&mut self, node: usize, value: usize) {
+        if let Some(slot) = self.lowlink.get_mut(node) {
+            *slot = (*slot).min(value);
+        }
+    }
+
+    fn close(&mut self, root: usize) {
+        let mut component = Vec::new();
+        while let Some(member) = self.stack.pop() {
+            if let Some(slot) = self.on_stack.get_mut(member) {
+                *slot = false;
+            }
+            component.push(member);
+            if member == root {
+                break;
+            }
+        }
+        self.components.push(component);
+    }
+}
@@ -1,5 +1,6 @@
 //! Stereotype classification for architectural role detection.
 
+use crate::lang_jvm;
 use std::path::Path;
 
 /// Architectural stereotypes for code classification.
@@ -27,6 +28,9 @@ pub fn classify(path: &str, content: &str) -> Stereotype {
     if let Some(s) = classify_by_filename(&lower, path) {
         return s;
     }
+    if let Some(s) = classify_by_annotations(content) {
+        return s;
+    }
     if let Some(s) = classify_by_imports(content) {
         return s;
     }
@@ -47,7 +51,7 @@ fn classify_by_filename(lower: &str, path: &str) -> Option<Stereotype> {
     if lower.contains("test") || lower.contains("spec") {
         return Some(Stereotype::Test);
     }
-    if lower.ends_with("main.rs") || lower.ends_with("lib.rs") {
+    if lower.ends_with("main.rs") || lower.ends_with("lib.rs") || lower.ends_with("main.swift") {
         return Some(Stereotype::Entrypoint);
     }
     if lower.contains("error") {
@@ -56,6 +60,36 @@ fn classify_by_filename(lower: &str, path: &str) -> Option<Stereotype> {
     None
 }
 
+/// Spring and `JUnit` markers on Java/Kotlin classes.
+fn classify_by_annotations(content: &str) -> Option<Stereotype> {
+    const MARKERS: [(&str, Stereotype); 12] = [
+        ("@SpringBootApplication", Stereotype::Entrypoint),
+        ("@RestController", Stereotype::Handler),
+        ("@Controller", Stereotype::Handler),
+        ("@ControllerAdvice", Stereotype::Handler),
+        ("@Service", Stereotype::Service),
+        ("@Component", Stereotype::Service),
+        ("@Repository", Stereotype::Repository),
+        ("@Entity", Stereotype::Entity),
+        ("@Embeddable", Stereotype::Entity),
+        ("@Configuration", Stereotype::Config),
+        ("import org.junit", Stereotype::Test),
+        ("import io.kotest", Stereotype::Test),
+    ];
+
+    for line in content.lines() {
+        let t = line.trim();
+        let marker = MARKERS.iter().find(|(m, _)| {
+            t.strip_prefix(m)
+                .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric()))
+        });
+        if let Some((_, stereotype)) = marker {
+            return Some(*stereotype);
+        }
+    }
+    None
+}
+
 fn classify_by_imports(content: &str) -> Option<Stereotype> {
     for line in content.lines() {
         let t = line.trim();
@@ -68,10 +102,25 @@ fn classify_by_imports(content: &str) -> Option<Stereotype> {
         if t.starts_with("use diesel") || t.starts_with("use sqlx") {
             return Some(Stereotype::Repository);
         }
+        if let Some(module) = t.strip_prefix("import ") {
+            if let Some(s) = classify_swift_module(module.trim()) {
+                return Some(s);
+            }
+        }
     }
     None
 }
 
+fn classify_swift_module(module: &str) -> Option<Stereotype> {
+    match module {
+        "ArgumentParser" => Some(Stereotype::Cli),
+        "Vapor" | "Hummingbird" => Some(Stereotype::Handler),
+        "CoreData" | "GRDB" | "SwiftData" => Some(Stereotype::Repository),
+        "XCTest" | "Testing" => Some(Stereotype::Test),
+        _ => None,
+    }
+}
+
 fn classify_by_name_pattern(lower: &str, content: &str) -> Option<Stereotype> {
     let has_regex = content.lines().any(|l| l.trim().starts_with("use regex"));
     if lower.contains("parse") || has_regex {
@@ -113,12 +162,18 @@ pub fn stereotype_to_why(s: Stereotype) -> &'static str {
 
 fn is_config_file(lower: &str, path: &str) -> bool {
     let p = Path::new(path);
-    let is_config_ext = p
-        .extension()
-        .and_then(|e| e.to_str())
-        .is_some_and(|e| matches!(e.to_lowercase().as_str(), "toml" | "yaml" | "yml" | "json"));
+    let is_config_ext = p.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+        matches!(
+            e.to_lowercase().as_str(),
+            "toml" | "yaml" | "yml" | "json" | "gradle" | "properties"
+        )
+    });
+    let is_build_file = p
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| lang_jvm::is_build_file(n) || n == "Package.swift");
 
-    is_config_ext || lower.contains("config") || lower.contains("cargo")
+    is_config_ext || is_build_file || lower.contains("config") || lower.contains("cargo")
 }
 
 fn is_mostly_structs(content: &str) -> bool {
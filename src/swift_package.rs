@@ -0,0 +1,194 @@
+//! Reads targets from a `SwiftPM` `Package.swift` manifest.
+
+use crate::path_utils;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static TARGET_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static PATH_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static DEP_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+fn target_regex() -> Option<&'static Regex> {
+    TARGET_RE
+        .get_or_init(|| {
+            Regex::new(
+                r#"\.(executableTarget|testTarget|target|macro|plugin)\(\s*name:\s*"([^"]+)""#,
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+fn path_regex() -> Option<&'static Regex> {
+    PATH_RE
+        .get_or_init(|| Regex::new(r#"\bpath:\s*"([^"]+)""#).ok())
+        .as_ref()
+}
+
+/// Plain `"Name"`, `.target(name: "Name")` and `.byName(name: "Name")` entries;
+/// `.product(...)` dependencies come from other packages and are skipped.
+fn dep_regex() -> Option<&'static Regex> {
+    DEP_RE
+        .get_or_init(|| {
+            Regex::new(r#"(\.product\([^)]*\))|(?:\.(?:target|byName)\(\s*name:\s*)?"([^"]+)""#)
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Kind of `SwiftPM` target, which determines its layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Executable,
+    Library,
+    Test,
+    Macro,
+    Plugin,
+}
+
+#[derive(Debug, Clone)]
+pub struct SwiftTarget {
+    pub name: String,
+    pub kind: TargetKind,
+    /// Source directory relative to the package root.
+    pub dir: String,
+    /// Names of local targets this target depends on.
+    pub dependencies: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SwiftPackage {
+    pub targets: Vec<SwiftTarget>,
+}
+
+impl SwiftPackage {
+    /// Loads `Package.swift` from `root`, if present.
+    pub fn load(root: &Path) -> Option<Self> {
+        let content = fs::read_to_string(root.join("Package.swift")).ok()?;
+        Some(Self::parse(&content))
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let Some(re) = target_regex() else {
+            return Self::default();
+        };
+        let mut targets = Vec::new();
+        let mut consumed = 0;
+        for cap in re.captures_iter(content) {
+            let Some(whole) = cap.get(0) else { continue };
+            // `.target(name:)` also appears inside another target's dependencies.
+            if whole.start() < consumed {
+                continue;
+            }
+            let Some(body) = call_body(content, whole.start()) else {
+                continue;
+            };
+            consumed = whole.start() + body.len();
+            let kind = target_kind(cap.get(1).map_or("", |m| m.as_str()));
+            let name = cap.get(2).map_or("", |m| m.as_str()).to_string();
+            targets.push(SwiftTarget {
+                dir: target_dir(body, &name, kind),
+                dependencies: target_dependencies(body),
+                name,
+                kind,
+            });
+        }
+        Self { targets }
+    }
+
+    /// The target whose source directory contains `rel_path`.
+    pub fn target_of(&self, rel_path: &str) -> Option<&SwiftTarget> {
+        self.targets
+            .iter()
+            .filter(|t| rel_path.starts_with(&format!("{}/", t.dir)))
+            .max_by_key(|t| t.dir.len())
+    }
+
+    pub fn target_named(&self, name: &str) -> Option<&SwiftTarget> {
+        self.targets.iter().find(|t| t.name == name)
+    }
+
+    /// Layer for a file inside the package: the manifest is configuration,
+    /// executables are entry points, libraries are domain code, tests are tests.
+    pub fn layer_for(&self, rel_path: &str) -> Option<u8> {
+        if rel_path == "Package.swift" {
+            return Some(0);
+        }
+        let layer = match self.target_of(rel_path)?.kind {
+            TargetKind::Plugin => 0,
+            TargetKind::Executable => 1,
+            TargetKind::Library => 2,
+            TargetKind::Macro => 3,
+            TargetKind::Test => 4,
+        };
+        Some(layer)
+    }
+}
+
+fn target_kind(call: &str) -> TargetKind {
+    match call {
+        "executableTarget" => TargetKind::Executable,
+        "testTarget" => TargetKind::Test,
+        "macro" => TargetKind::Macro,
+        "plugin" => TargetKind::Plugin,
+        _ => TargetKind::Library,
+    }
+}
+
+fn target_dir(body: &str, name: &str, kind: TargetKind) -> String {
+    let explicit = path_regex()
+        .and_then(|re| re.captures(body))
+        .and_then(|c| c.get(1))
+        .and_then(|m| path_utils::normalize_path(m.as_str()));
+    explicit.unwrap_or_else(|| {
+        let base = match kind {
+            TargetKind::Test => "Tests",
+            TargetKind::Plugin => "Plugins",
+            _ => "Sources",
+        };
+        format!("{base}/{name}")
+    })
+}
+
+fn target_dependencies(body: &str) -> Vec<String> {
+    let Some(list) = bracket_after(body, "dependencies:") else {
+        return Vec::new();
+    };
+    let Some(re) = dep_regex() else {
+        return Vec::new();
+    };
+    re.captures_iter(list)
+        .filter(|c| c.get(1).is_none())
+        .filter_map(|c| c.get(2).map(|m| m.as_str().to_string()))
+        .collect()
+}
+
+/// The text inside the parentheses of the call starting at `start`.
+fn call_body(content: &str, start: usize) -> Option<&str> {
+    let rest = content.get(start..)?;
+    let open = rest.find('(')?;
+    balanced(rest, open, '(', ')')
+}
+
+fn bracket_after<'a>(body: &'a str, label: &str) -> Option<&'a str> {
+    let at = body.find(label)?;
+    let open = at + body.get(at..)?.find('[')?;
+    balanced(body, open, '[', ']')
+}
+
+fn balanced(text: &str, open: usize, left: char, right: char) -> Option<&str> {
+    let mut depth = 0usize;
+    for (i, c) in text.get(open..)?.char_indices() {
+        if c == left {
+            depth += 1;
+        } else if c == right {
+            depth -= 1;
+            if depth == 0 {
+                return text.get(open + 1..open + i);
+            }
+        }
+    }
+    None
+}
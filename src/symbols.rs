@@ -0,0 +1,244 @@
+//! Type declarations and implemented supertypes, per language.
+
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static RUST_DEF_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static RUST_IMPL_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static CLASS_DEF_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static CLASS_HEAD_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static COLON_HEAD_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static RUST_FN_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static PY_DEF_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static JS_FN_RE: OnceLock<Option<Regex>> = OnceLock::new();
+static JS_METHOD_RE: OnceLock<Option<Regex>> = OnceLock::new();
+
+/// Words that look like `name(...) {` in a class body but are statements.
+const JS_STATEMENTS: [&str; 7] = [
+    "if", "for", "while", "switch", "catch", "return", "function",
+];
+
+fn rust_def_regex() -> Option<&'static Regex> {
+    RUST_DEF_RE
+        .get_or_init(|| {
+            Regex::new(
+                r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?(?:unsafe\s+)?(trait|struct|enum)\s+(\w+)",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+/// `impl<T> path::Trait<T> for Type` — captures the trait path.
+fn rust_impl_regex() -> Option<&'static Regex> {
+    RUST_IMPL_RE
+        .get_or_init(|| {
+            Regex::new(r"(?m)^\s*(?:unsafe\s+)?impl(?:<[^>]*>)?\s+!?([\w:]+)(?:<[^{]*?>)?\s+for\s")
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Class-like declarations in TS/JS, Java, Kotlin and Swift.
+fn class_def_regex() -> Option<&'static Regex> {
+    CLASS_DEF_RE
+        .get_or_init(|| {
+            Regex::new(r"(?m)^\s*(?:(?:export|default|public|private|protected|internal|open|final|sealed|data|static|declare)\s+)*(abstract\s+)?(interface|protocol|class|struct|enum|record|actor|object)\s+(\w+)")
+                .ok()
+        })
+        .as_ref()
+}
+
+/// `class X extends A implements B, C {` (TS/JS, Java).
+fn class_head_regex() -> Option<&'static Regex> {
+    CLASS_HEAD_RE
+        .get_or_init(|| {
+            Regex::new(r"\b(?:class|interface|record)\s+\w+(?:<[^{]*?>)?(?:\([^)]*\))?\s+((?:extends|implements)[^{]*)\{").ok()
+        })
+        .as_ref()
+}
+
+/// `class X(...) : A(), B {` (Kotlin) and `struct X: A, B {` (Swift).
+fn colon_head_regex() -> Option<&'static Regex> {
+    COLON_HEAD_RE
+        .get_or_init(|| {
+            Regex::new(r"\b(?:class|interface|object|struct|enum|actor|extension|protocol)\s+\w+(?:<[^{]*?>)?(?:\([^)]*\))?\s*:\s*([^{=]+)").ok()
+        })
+        .as_ref()
+}
+
+fn rust_fn_regex() -> Option<&'static Regex> {
+    RUST_FN_RE
+        .get_or_init(|| {
+            Regex::new(r#"(?m)^([ \t]*)(?:pub(?:\([^)]*\))?\s+)?(?:(?:const|async|unsafe|extern\s+"[^"]*")\s+)*fn\s+(\w+)"#)
+                .ok()
+        })
+        .as_ref()
+}
+
+fn py_def_regex() -> Option<&'static Regex> {
+    PY_DEF_RE
+        .get_or_init(|| Regex::new(r"(?m)^([ \t]*)(?:async\s+)?def\s+(\w+)").ok())
+        .as_ref()
+}
+
+/// `function name(` and `const name = (...) =>` / `= function`.
+fn js_fn_regex() -> Option<&'static Regex> {
+    JS_FN_RE
+        .get_or_init(|| {
+            Regex::new(r"(?m)^([ \t]*)(?:export\s+)?(?:default\s+)?(?:(?:async\s+)?function\*?\s+(\w+)|(?:const|let|var)\s+(\w+)\s*=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*(?::[^=]*)?=>|\w+\s*=>))")
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Indented `name(args) {` inside a class body.
+fn js_method_regex() -> Option<&'static Regex> {
+    JS_METHOD_RE
+        .get_or_init(|| {
+            Regex::new(r"(?m)^[ \t]+(?:(?:public|private|protected|static|async|readonly|override|get|set)\s+)*(\w+)\s*(?:<[^>]*>)?\([^)]*\)\s*(?::[^{;]*)?\{")
+                .ok()
+        })
+        .as_ref()
+}
+
+/// A declared function; `is_method` when it sits inside a type body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FnDef {
+    pub name: String,
+    pub is_method: bool,
+}
+
+/// A declared type and whether it is abstract (trait, interface, protocol,
+/// abstract class).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDef {
+    pub name: String,
+    pub is_abstract: bool,
+}
+
+fn ext_of(path: &str) -> &str {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+}
+
+/// Types declared in a file, for languages with supertype edges.
+pub fn type_definitions(content: &str, path: &str) -> Vec<TypeDef> {
+    match ext_of(path) {
+        "rs" => captures(rust_def_regex(), content, |c| {
+            Some(TypeDef {
+                is_abstract: c.get(1)?.as_str() == "trait",
+                name: c.get(2)?.as_str().to_string(),
+            })
+        }),
+        "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" | "java" | "kt" | "swift"
+        | "py" => captures(class_def_regex(), content, |c| {
+            let kind = c.get(2)?.as_str();
+            Some(TypeDef {
+                is_abstract: c.get(1).is_some() || kind == "interface" || kind == "protocol",
+                name: c.get(3)?.as_str().to_string(),
+            })
+        }),
+        _ => Vec::new(),
+    }
+}
+
+/// Functions declared in a Rust, Python or JS/TS file. Indentation tells
+/// methods (inside `impl`/`class`) apart from free functions.
+pub fn function_definitions(content: &str, path: &str) -> Vec<FnDef> {
+    let indented = |c: &regex::Captures, name: usize| {
+        Some(FnDef {
+            is_method: !c.get(1)?.as_str().is_empty(),
+            name: c.get(name)?.as_str().to_string(),
+        })
+    };
+    match ext_of(path) {
+        "rs" => captures(rust_fn_regex(), content, |c| indented(c, 2)),
+        "py" => captures(py_def_regex(), content, |c| indented(c, 2)),
+        "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => {
+            let mut defs = captures(js_fn_regex(), content, |c| {
+                indented(c, 2).or_else(|| indented(c, 3))
+            });
+            defs.extend(captures(js_method_regex(), content, |c| {
+                let name = c.get(1)?.as_str();
+                (!JS_STATEMENTS.contains(&name)).then(|| FnDef {
+                    name: name.to_string(),
+                    is_method: true,
+                })
+            }));
+            defs
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Names of traits implemented and classes/interfaces extended in a file.
+pub fn supertypes(content: &str, path: &str) -> Vec<String> {
+    supertype_paths(content, path)
+        .iter()
+        .map(|p| split_qualifier(p).1.to_string())
+        .collect()
+}
+
+/// Supertypes as written, qualifier included (`std::error::Error`,
+/// `java.io.Serializable`).
+pub fn supertype_paths(content: &str, path: &str) -> Vec<String> {
+    match ext_of(path) {
+        "rs" => captures(rust_impl_regex(), content, |c| {
+            Some(c.get(1)?.as_str().to_string())
+        }),
+        "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" | "java" => {
+            supertype_lists(class_head_regex(), content, &["extends", "implements"])
+        }
+        "kt" | "swift" => supertype_lists(colon_head_regex(), content, &[]),
+        _ => Vec::new(),
+    }
+}
+
+fn captures<T>(
+    re: Option<&Regex>,
+    content: &str,
+    map: impl Fn(&regex::Captures) -> Option<T>,
+) -> Vec<T> {
+    re.map_or_else(Vec::new, |re| {
+        re.captures_iter(content).filter_map(|c| map(&c)).collect()
+    })
+}
+
+/// Splits `extends A<T> implements B, pkg.C` into `["A", "B", "C"]`.
+fn supertype_lists(re: Option<&Regex>, content: &str, keywords: &[&str]) -> Vec<String> {
+    captures(re, content, |c| Some(c.get(1)?.as_str().to_string()))
+        .iter()
+        .flat_map(|list| split_type_list(list, keywords))
+        .collect()
+}
+
+fn split_type_list(list: &str, keywords: &[&str]) -> Vec<String> {
+    let mut depth = 0i32;
+    let mut cleaned = String::new();
+    for c in list.chars() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            _ if depth == 0 => cleaned.push(c),
+            _ => {}
+        }
+    }
+    cleaned
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|word| !word.is_empty() && !keywords.contains(word))
+        .filter(|word| word.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+        .map(String::from)
+        .collect()
+}
+
+/// `a::b::C` or `a.b.C` into `(Some("a::b"), "C")`; `C` into `(None, "C")`.
+pub fn split_qualifier(path: &str) -> (Option<&str>, &str) {
+    match path.rsplit_once("::").or_else(|| path.rsplit_once('.')) {
+        Some((qualifier, name)) => (Some(qualifier), name),
+        None => (None, path),
+    }
+}
@@ -0,0 +1,92 @@
+//! Trait-implementation edges: files implementing or extending an abstraction
+//! depend on the file that defines it.
+
+use crate::symbols;
+use crate::types::{DepEdge, DepKind};
+use std::collections::{HashMap, HashSet};
+
+/// Builds `DepKind::Trait` edges from `(path, content)` pairs.
+///
+/// Only abstract types (traits, interfaces, protocols, abstract classes) are
+/// targets. When several files declare the same name, the one this file
+/// already imports wins; otherwise an ambiguous name produces no edge. A
+/// qualified supertype (`fmt::Display`, `java.io.Serializable`) needs a
+/// `crate`/`self`/`super` path or an import of the defining file, so library
+/// traits never link to a local namesake.
+pub fn trait_edges(sources: &[(&str, String)], import_edges: &[DepEdge]) -> Vec<DepEdge> {
+    let mut defined_in: HashMap<String, Vec<&str>> = HashMap::new();
+    for (path, content) in sources {
+        for def in symbols::type_definitions(content, path) {
+            if def.is_abstract {
+                defined_in.entry(def.name).or_default().push(path);
+            }
+        }
+    }
+
+    let imports: HashSet<(&str, &str)> = import_edges
+        .iter()
+        .map(|e| (e.from.as_str(), e.to.as_str()))
+        .collect();
+
+    let mut edges: Vec<DepEdge> = Vec::new();
+    let mut index: HashMap<(&str, &str), usize> = HashMap::new();
+    for (path, content) in sources {
+        for written in symbols::supertype_paths(content, path) {
+            let (qualifier, name) = symbols::split_qualifier(&written);
+            let supertype = name.to_string();
+            let Some(candidates) = defined_in.get(&supertype) else {
+                continue;
+            };
+            let target = if qualifier.is_some_and(|q| !is_local_path(q)) {
+                candidates
+                    .iter()
+                    .find(|c| imports.contains(&(*path, **c)))
+                    .copied()
+            } else {
+                pick_definition(path, candidates, &imports)
+            };
+            let Some(target) = target else {
+                continue;
+            };
+            match index.get(&(*path, target)).and_then(|&i| edges.get_mut(i)) {
+                Some(edge) if edge.symbols.contains(&supertype) => {}
+                Some(edge) => edge.symbols.push(supertype),
+                None => {
+                    index.insert((*path, target), edges.len());
+                    edges.push(DepEdge {
+                        from: (*path).to_string(),
+                        to: target.to_string(),
+                        kind: DepKind::Trait,
+                        symbols: vec![supertype],
+                    });
+                }
+            }
+        }
+    }
+    edges
+}
+
+fn pick_definition<'a>(
+    from: &str,
+    candidates: &[&'a str],
+    imports: &HashSet<(&str, &str)>,
+) -> Option<&'a str> {
+    if candidates.contains(&from) {
+        return None;
+    }
+    let imported = candidates
+        .iter()
+        .find(|c| imports.contains(&(from, **c)))
+        .copied();
+    match (imported, candidates) {
+        (Some(target), _) => Some(target),
+        (None, [only]) => Some(only),
+        _ => None,
+    }
+}
+
+/// `crate::x`, `self::x` or `super::x`: a path inside this crate.
+fn is_local_path(qualifier: &str) -> bool {
+    let first = qualifier.split("::").next().unwrap_or_default();
+    matches!(first, "crate" | "self" | "super")
+}
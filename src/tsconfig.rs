@@ -0,0 +1,209 @@
+//! Loads `baseUrl` and `paths` aliases from `tsconfig.json`, following `extends`.
+
+use crate::path_utils;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Maximum `extends` chain length, guarding against cyclic configs.
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Module resolution settings merged from a tsconfig chain.
+/// All paths are relative to the project root.
+#[derive(Debug, Clone, Default)]
+pub struct TsPaths {
+    pub base_url: Option<String>,
+    pub aliases: Vec<PathAlias>,
+}
+
+/// A single `paths` entry such as `"@app/*": ["src/app/*"]`.
+#[derive(Debug, Clone)]
+pub struct PathAlias {
+    pub pattern: String,
+    pub targets: Vec<String>,
+}
+
+impl PathAlias {
+    /// Returns the text captured by `*`, or `""` for an exact match.
+    pub fn capture<'a>(&self, specifier: &'a str) -> Option<&'a str> {
+        match self.pattern.split_once('*') {
+            Some((prefix, suffix)) => specifier
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(suffix)),
+            None => (self.pattern == specifier).then_some(""),
+        }
+    }
+
+    /// Length of the literal prefix, used to prefer the most specific alias.
+    pub fn specificity(&self) -> usize {
+        self.pattern.find('*').unwrap_or(usize::MAX)
+    }
+}
+
+pub fn load(root: &Path) -> TsPaths {
+    let mut paths = TsPaths::default();
+    for name in ["tsconfig.json", "jsconfig.json"] {
+        if root.join(name).is_file() {
+            merge_chain(root, name, &mut paths, 0);
+            break;
+        }
+    }
+    paths
+}
+
+/// Applies `config` on top of `paths`, after first applying whatever it extends.
+fn merge_chain(root: &Path, config: &str, paths: &mut TsPaths, depth: usize) {
+    if depth > MAX_EXTENDS_DEPTH {
+        return;
+    }
+    let Some(json) = read_jsonc(&root.join(config)) else {
+        return;
+    };
+    let config_dir = path_utils::parent_dir(config);
+
+    for parent in extends_list(&json) {
+        if let Some(parent_path) = resolve_extends(root, config_dir, &parent) {
+            merge_chain(root, &parent_path, paths, depth + 1);
+        }
+    }
+
+    let Some(options) = json.get("compilerOptions") else {
+        return;
+    };
+    if let Some(base) = options.get("baseUrl").and_then(Value::as_str) {
+        paths.base_url = path_utils::join_normalized(config_dir, base);
+    }
+    if let Some(map) = options.get("paths").and_then(Value::as_object) {
+        let base = paths
+            .base_url
+            .clone()
+            .unwrap_or_else(|| config_dir.to_string());
+        paths.aliases = map
+            .iter()
+            .map(|(pattern, targets)| PathAlias {
+                pattern: pattern.clone(),
+                targets: alias_targets(&base, targets),
+            })
+            .collect();
+    }
+}
+
+fn extends_list(json: &Value) -> Vec<String> {
+    match json.get("extends") {
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Resolves an `extends` value to a root-relative config path.
+fn resolve_extends(root: &Path, config_dir: &str, parent: &str) -> Option<String> {
+    let base = if parent.starts_with('.') {
+        path_utils::join_normalized(config_dir, parent)?
+    } else {
+        format!("node_modules/{parent}")
+    };
+    [
+        base.clone(),
+        format!("{base}.json"),
+        format!("{base}/tsconfig.json"),
+    ]
+    .into_iter()
+    .find(|candidate| root.join(candidate).is_file())
+}
+
+fn alias_targets(base: &str, targets: &Value) -> Vec<String> {
+    targets
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .filter_map(|t| path_utils::join_normalized(base, t))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads a JSON file that may contain comments and trailing commas.
+pub fn read_jsonc(path: &Path) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_jsonc(&content)).ok()
+}
+
+/// Removes `//` and `/* */` comments and trailing commas outside of strings.
+pub fn strip_jsonc(content: &str) -> String {
+    let without_comments = strip_comments(content);
+    strip_trailing_commas(&without_comments)
+}
+
+fn strip_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|&n| n != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for n in chars.by_ref() {
+                    if prev == '*' && n == '/' {
+                        break;
+                    }
+                    prev = n;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn strip_trailing_commas(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in content.char_indices() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' && next_significant(content, i + 1).is_some_and(|n| n == '}' || n == ']')
+        {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn next_significant(content: &str, from: usize) -> Option<char> {
+    content.get(from..)?.chars().find(|c| !c.is_whitespace())
+}
@@ -25,9 +25,26 @@ pub struct Layer {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileEntry {
     pub path: String,
+    /// Legend tags written after the path, e.g. `ENTRY` for `` `[ENTRY]` ``.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     pub description: Description,
     pub exports: Option<Vec<String>>,
     pub touch: Option<String>,
+    /// Intended dependencies from a `→ Depends:` line, checked against the code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends: Option<Vec<String>>,
+    /// Deliberate layer exceptions from `→ Allow:` lines.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allows: Vec<AllowedDep>,
+}
+
+/// A dependency the layer check should accept, with the reason it is allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllowedDep {
+    /// Path or glob of the allowed dependency.
+    pub target: String,
+    pub reason: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,15 +70,43 @@ pub struct DepEdge {
     pub from: String,
     pub to: String,
     pub kind: DepKind,
+    /// Called functions for `Call` edges, implemented types for `Trait` edges.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<String>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum DepKind {
     Import,
+    /// Type-only import (`import type`), erased at runtime.
+    TypeImport,
     Trait,
     Call,
 }
 
+impl DepKind {
+    /// Parses a `--kinds` entry: `import`, `type-import`, `trait` or `call`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "import" => Some(Self::Import),
+            "type-import" | "typeimport" | "type" => Some(Self::TypeImport),
+            "trait" => Some(Self::Trait),
+            "call" => Some(Self::Call),
+            _ => None,
+        }
+    }
+
+    /// Name accepted by [`DepKind::parse`], used in rendered output.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Import => "import",
+            Self::TypeImport => "type-import",
+            Self::Trait => "trait",
+            Self::Call => "call",
+        }
+    }
+}
+
 impl SemmapFile {
     pub fn new(project_name: String, purpose: String) -> Self {
         Self {
@@ -111,9 +156,12 @@ impl FileEntry {
     pub fn new(path: String, what: String, why: String) -> Self {
         Self {
             path,
+            tags: Vec::new(),
             description: Description { what, why },
             exports: None,
             touch: None,
+            depends: None,
+            allows: Vec::new(),
         }
     }
 }
@@ -1,7 +1,10 @@
 //! Validates SEMMAP files for correctness and completeness.
 
+use crate::deps::AnalyzeOptions;
 use crate::error::{Severity, ValidationIssue};
+use crate::path_utils;
 use crate::types::SemmapFile;
+use crate::{declared_deps, deps};
 use std::collections::HashSet;
 use std::path::Path;
 
@@ -83,6 +86,10 @@ fn check_entries(semmap: &SemmapFile, issues: &mut Vec<ValidationIssue>) {
             if is_generic_description(&entry.description.what, &entry.path) {
                 issues.push(ValidationIssue::warning("Add //! doc comment").for_path(&entry.path));
             }
+            for allow in entry.allows.iter().filter(|a| a.reason.is_empty()) {
+                let message = format!("Allow {} has no justification", allow.target);
+                issues.push(ValidationIssue::warning(message).for_path(&entry.path));
+            }
         }
     }
 }
@@ -121,8 +128,24 @@ fn check_files_exist(semmap: &SemmapFile, root: &Path, issues: &mut Vec<Validati
     }
 }
 
-pub fn validate_against_codebase(semmap: &SemmapFile, root: &Path) -> ValidationResult {
+pub fn validate_against_codebase(
+    semmap: &SemmapFile,
+    root: &Path,
+    options: &AnalyzeOptions,
+) -> ValidationResult {
     let mut issues = validate(semmap, Some(root)).issues;
+    issues.extend(codebase_issues(semmap, root, options));
+    ValidationResult { issues }
+}
+
+/// The checks of `validate_against_codebase` beyond `validate`: unmapped
+/// source files and declared dependencies.
+pub fn codebase_issues(
+    semmap: &SemmapFile,
+    root: &Path,
+    options: &AnalyzeOptions,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
     let documented: HashSet<_> = semmap.all_paths().into_iter().collect();
 
     for file in collect_source_files(root) {
@@ -130,7 +153,11 @@ pub fn validate_against_codebase(semmap: &SemmapFile, root: &Path) -> Validation
             issues.push(ValidationIssue::warning("Not in SEMMAP").for_path(&file));
         }
     }
-    ValidationResult { issues }
+    if declared_deps::any_declared(semmap) {
+        let depmap = deps::analyze_with(root, semmap, options);
+        issues.extend(declared_deps::check(semmap, &depmap));
+    }
+    issues
 }
 
 fn collect_source_files(root: &Path) -> Vec<String> {
@@ -146,7 +173,7 @@ fn collect_source_files(root: &Path) -> Vec<String> {
                 .path()
                 .extension()
                 .and_then(|e| e.to_str())
-                .is_some_and(|e| matches!(e, "rs" | "ts" | "js" | "py" | "go"));
+                .is_some_and(path_utils::is_source_ext);
             if is_src {
                 if let Ok(rel) = entry.path().strip_prefix(root) {
                     files.push(rel.to_string_lossy().replace('\\', "/"));
@@ -0,0 +1,129 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::commands::{self, DepsOptions};
+use semmap::types::{AllowedDep, DepEdge, DepKind, DependencyMap};
+use semmap::{deps, formatter, layer_check, parser, validator};
+use std::collections::HashSet;
+use std::fs;
+
+const MAP: &str = "# t -- Semantic Map
+
+## Layer 2 -- Domain
+
+`src/parser.rs`
+Parses input.
+→ Allow: src/util/** -- tokenizer is shared with the CLI
+→ Allow: `src/gone.rs` -- legacy shim
+
+## Layer 3 -- Utilities
+
+`src/util/tokens.rs`
+Splits tokens.
+
+`src/fmt.rs`
+Formats output.
+";
+
+fn edge(from: &str, to: &str) -> DepEdge {
+    DepEdge {
+        from: from.into(),
+        to: to.into(),
+        kind: DepKind::Import,
+        symbols: vec![],
+    }
+}
+
+fn depmap(edges: Vec<DepEdge>) -> DependencyMap {
+    DependencyMap {
+        nodes: vec![],
+        edges,
+    }
+}
+
+#[test]
+fn parses_allow_lines_with_reasons() {
+    let semmap = parser::parse(MAP).unwrap();
+    let entry = semmap.find_entry("src/parser.rs").unwrap();
+    assert_eq!(
+        entry.allows,
+        vec![
+            AllowedDep {
+                target: "src/util/**".into(),
+                reason: "tokenizer is shared with the CLI".into(),
+            },
+            AllowedDep {
+                target: "src/gone.rs".into(),
+                reason: "legacy shim".into(),
+            },
+        ]
+    );
+    assert_eq!(entry.description.what, "Parses input.");
+}
+
+#[test]
+fn formatter_round_trips_allow_lines() {
+    let semmap = parser::parse(MAP).unwrap();
+    let markdown = formatter::to_markdown(&semmap);
+    assert!(markdown.contains("→ Allow: src/util/** -- tokenizer is shared with the CLI\n"));
+
+    let reparsed = parser::parse(&markdown).unwrap();
+    let entry = reparsed.find_entry("src/parser.rs").unwrap();
+    assert_eq!(entry.allows.len(), 2);
+}
+
+#[test]
+fn allowed_edges_are_not_violations() {
+    let semmap = parser::parse(MAP).unwrap();
+    let map = depmap(vec![
+        edge("src/parser.rs", "src/util/tokens.rs"),
+        edge("src/parser.rs", "src/fmt.rs"),
+    ]);
+    assert_eq!(
+        deps::check_layer_violations(&map, &semmap)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["Layer violation: src/parser.rs (L2) depends on src/fmt.rs (L3)"]
+    );
+}
+
+#[test]
+fn allows_without_a_matching_edge_are_stale() {
+    let semmap = parser::parse(MAP).unwrap();
+    let map = depmap(vec![edge("src/parser.rs", "src/util/tokens.rs")]);
+    assert_eq!(
+        layer_check::stale_allows(&map, &semmap, &HashSet::new()),
+        vec!["Stale allow: src/parser.rs -> src/gone.rs (no such dependency)"]
+    );
+}
+
+#[test]
+fn validator_warns_on_unjustified_allow() {
+    let semmap = parser::parse(&MAP.replace(" -- legacy shim", "")).unwrap();
+    let result = validator::validate(&semmap, None);
+    assert!(result
+        .issues
+        .iter()
+        .any(|i| i.message == "Allow src/gone.rs has no justification"));
+}
+
+#[test]
+fn deps_check_fails_on_stale_allow() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/parser.rs"), "use crate::util;\n").unwrap();
+    fs::write(root.join("src/util.rs"), "").unwrap();
+    fs::write(root.join("src/fmt.rs"), "").unwrap();
+    let map = MAP
+        .replace("src/util/**", "src/util.rs")
+        .replace("src/util/tokens.rs", "src/util.rs");
+    fs::write(root.join("SEMMAP.md"), map).unwrap();
+
+    let options = DepsOptions {
+        check: true,
+        ..DepsOptions::default()
+    };
+    let err = commands::deps(&root.join("SEMMAP.md"), root, &options).unwrap_err();
+    assert_eq!(err, "1 stale allows");
+}
@@ -41,3 +41,55 @@ fn analyze_finds_rust_imports() {
         .iter()
         .any(|e| e.from == "src/main.rs" && e.to == "src/utils.rs" && e.kind == DepKind::Import));
 }
+
+#[test]
+fn analyze_resolves_mod_declarations_below_non_root_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+
+    fs::create_dir_all(root.join("src/commands/deep")).unwrap();
+    fs::create_dir_all(root.join("tests/common")).unwrap();
+    fs::write(root.join("src/lib.rs"), "pub mod commands;").unwrap();
+    fs::write(root.join("src/commands.rs"), "mod helpers;\nmod deep;").unwrap();
+    fs::write(root.join("src/commands/helpers.rs"), "").unwrap();
+    fs::write(root.join("src/commands/deep/mod.rs"), "").unwrap();
+    fs::write(root.join("tests/it.rs"), "mod common;").unwrap();
+    fs::write(root.join("tests/common/mod.rs"), "").unwrap();
+
+    let mut layer = Layer::new(1, "Core".into());
+    for path in [
+        "src/lib.rs",
+        "src/commands.rs",
+        "src/commands/helpers.rs",
+        "src/commands/deep/mod.rs",
+        "tests/it.rs",
+        "tests/common/mod.rs",
+    ] {
+        layer
+            .entries
+            .push(FileEntry::new(path.into(), "desc".into(), String::new()));
+    }
+    let semmap = SemmapFile {
+        project_name: "test".into(),
+        purpose: String::new(),
+        legend: vec![],
+        layers: vec![layer],
+    };
+
+    let depmap = deps::analyze(root, &semmap);
+    let mut edges: Vec<_> = depmap
+        .edges
+        .iter()
+        .map(|e| (e.from.as_str(), e.to.as_str()))
+        .collect();
+    edges.sort_unstable();
+    assert_eq!(
+        edges,
+        vec![
+            ("src/commands.rs", "src/commands/deep/mod.rs"),
+            ("src/commands.rs", "src/commands/helpers.rs"),
+            ("src/lib.rs", "src/commands.rs"),
+            ("tests/it.rs", "tests/common/mod.rs"),
+        ]
+    );
+}
@@ -0,0 +1,234 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::baseline::{Baseline, BaselineOptions};
+use semmap::commands::{self, DepsOptions, ValidateOptions};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn options(path: &Path, update: bool, allow_growth: bool) -> BaselineOptions {
+    BaselineOptions {
+        path: Some(path.to_path_buf()),
+        update,
+        allow_growth,
+    }
+}
+
+fn findings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| (*s).to_string()).collect()
+}
+
+fn record(
+    path: &Path,
+    items: &[&str],
+    update: bool,
+    allow_growth: bool,
+) -> Result<Vec<String>, String> {
+    let mut baseline = Baseline::open(&options(path, update, allow_growth))?;
+    let new = baseline.retain_new(findings(items), Clone::clone);
+    let failures: Vec<String> = new.iter().map(|f| format!("new: {f}")).collect();
+    baseline.finish(&failures)?;
+    Ok(new)
+}
+
+#[test]
+fn fails_only_on_findings_missing_from_the_baseline() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("baseline.json");
+    record(&path, &["a", "b"], true, false).unwrap();
+
+    assert_eq!(
+        record(&path, &["a", "b"], false, false).unwrap(),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        record(&path, &["a", "c"], false, false).unwrap_err(),
+        "new: c"
+    );
+}
+
+#[test]
+fn fixed_findings_are_reported_and_ratcheted_out() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("baseline.json");
+    record(&path, &["a", "b"], true, false).unwrap();
+
+    let mut baseline = Baseline::open(&options(&path, false, false)).unwrap();
+    baseline.retain_new(findings(&["a"]), Clone::clone);
+    assert_eq!(baseline.fixed(), vec!["b"]);
+
+    record(&path, &["a"], true, false).unwrap();
+    let saved = fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("\"a\""));
+    assert!(!saved.contains("\"b\""));
+}
+
+#[test]
+fn update_refuses_to_grow_unless_allowed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("baseline.json");
+    record(&path, &["a"], true, false).unwrap();
+
+    let err = record(&path, &["a", "b"], true, false).unwrap_err();
+    assert!(err.contains("Refusing to grow baseline by 1"));
+    assert!(!fs::read_to_string(&path).unwrap().contains("\"b\""));
+
+    record(&path, &["a", "b"], true, true).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("\"b\""));
+}
+
+#[test]
+fn missing_baseline_is_an_error_without_update() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("missing.json");
+    let err = Baseline::open(&options(&path, false, false)).unwrap_err();
+    assert!(err.contains("--update-baseline"));
+    assert!(!Baseline::open(&BaselineOptions::default())
+        .unwrap()
+        .is_enabled());
+}
+
+fn project(root: &Path) -> PathBuf {
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/core.rs"), "use crate::app;\n").unwrap();
+    fs::write(root.join("src/app.rs"), "").unwrap();
+    let semmap = root.join("SEMMAP.md");
+    fs::write(
+        &semmap,
+        "# t -- Semantic Map\n\n## Layer 0 -- Core\n\n`src/core.rs`\nCore.\n\n## Layer 1 -- App\n\n`src/app.rs`\nApp.\n",
+    )
+    .unwrap();
+    semmap
+}
+
+#[test]
+fn deps_check_passes_with_baselined_layer_violation() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let semmap = project(root);
+    let path = root.join("baseline.json");
+
+    let check = |update| DepsOptions {
+        check: true,
+        baseline: options(&path, update, false),
+        ..DepsOptions::default()
+    };
+    assert!(commands::deps(
+        &semmap,
+        root,
+        &DepsOptions {
+            check: true,
+            ..DepsOptions::default()
+        }
+    )
+    .is_err());
+    commands::deps(&semmap, root, &check(true)).unwrap();
+    commands::deps(&semmap, root, &check(false)).unwrap();
+
+    fs::write(root.join("src/app.rs"), "use crate::core;\n").unwrap();
+    fs::write(root.join("src/core.rs"), "").unwrap();
+    commands::deps(&semmap, root, &check(false)).unwrap();
+}
+
+#[test]
+fn validate_ignores_baselined_errors() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let semmap = project(root);
+    fs::remove_file(root.join("src/app.rs")).unwrap();
+    let path = root.join("baseline.json");
+
+    let validate = |update| ValidateOptions {
+        strict: false,
+        baseline: options(&path, update, false),
+        ..ValidateOptions::default()
+    };
+    assert!(commands::validate(&semmap, root, &ValidateOptions::default()).is_err());
+    commands::validate(&semmap, root, &validate(true)).unwrap();
+    commands::validate(&semmap, root, &validate(false)).unwrap();
+
+    fs::remove_file(root.join("src/core.rs")).unwrap();
+    assert!(commands::validate(&semmap, root, &validate(false)).is_err());
+}
+
+#[test]
+fn validate_ratchets_out_errors_fixed_outside_strict_mode() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    project(root);
+    fs::remove_file(root.join("src/app.rs")).unwrap();
+    fs::remove_file(root.join("src/core.rs")).unwrap();
+    let validate = |update: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_semmap"));
+        command
+            .args(["validate", "--baseline", "baseline.json"])
+            .current_dir(root);
+        if update {
+            command.arg("--update-baseline");
+        }
+        let output = command.output().unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+    validate(true);
+
+    fs::write(root.join("src/app.rs"), "").unwrap();
+    let out = validate(false);
+    assert!(
+        out.contains("* Fixed since baseline: Validate [src/app.rs]"),
+        "{out}"
+    );
+    validate(true);
+    let saved = fs::read_to_string(root.join("baseline.json")).unwrap();
+    assert!(saved.contains("src/core.rs"), "{saved}");
+    assert!(!saved.contains("src/app.rs"), "{saved}");
+}
+
+#[test]
+fn skipped_categories_are_kept_on_update() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("baseline.json");
+    record(&path, &["Cycle: a, b", "Rule r: a -> b"], true, false).unwrap();
+
+    let mut baseline = Baseline::open(&options(&path, true, false)).unwrap();
+    baseline.skip("Cycle");
+    baseline.retain_new(Vec::<String>::new(), Clone::clone);
+    assert_eq!(baseline.fixed(), vec!["Rule r: a -> b"]);
+    baseline.finish(&[]).unwrap();
+    let saved = fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("Cycle: a, b"));
+    assert!(!saved.contains("Rule r"));
+}
+
+#[test]
+fn deps_refuses_to_update_a_baseline_without_checks() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let semmap = project(root);
+    let path = root.join("baseline.json");
+
+    let err = commands::deps(
+        &semmap,
+        root,
+        &DepsOptions {
+            baseline: options(&path, true, false),
+            ..DepsOptions::default()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, "--update-baseline needs --check or --check-cycles");
+    assert!(!path.exists());
+
+    // Cycle checks alone keep the recorded layer violation
+    let run = |check, check_cycles| DepsOptions {
+        check,
+        check_cycles,
+        baseline: options(&path, true, false),
+        ..DepsOptions::default()
+    };
+    commands::deps(&semmap, root, &run(true, false)).unwrap();
+    commands::deps(&semmap, root, &run(false, true)).unwrap();
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .contains("Layer violation: src/core.rs"));
+}
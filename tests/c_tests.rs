@@ -0,0 +1,156 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::{semmap_for, write};
+use semmap::deps::{self, AnalyzeOptions};
+use semmap::doc_extractor;
+use semmap::formatter;
+use semmap::lang_c;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn edges(depmap: &semmap::DependencyMap) -> Vec<(String, String)> {
+    let mut pairs: Vec<_> = depmap
+        .edges
+        .iter()
+        .map(|e| (e.from.clone(), e.to.clone()))
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+#[test]
+fn header_exports_skip_static_and_include_guard() {
+    let header = r"#ifndef UART_H
+#define UART_H
+#define UART_BAUD 115200
+
+typedef struct {
+    int port;
+} uart_config;
+
+struct ring_buffer {
+    int head;
+};
+
+class Driver : public Base {
+public:
+    void open();
+};
+
+int uart_init(const uart_config *cfg);
+static int helper(void);
+void *uart_alloc(size_t n);
+#endif
+";
+    let exports = lang_c::extract_exports(header);
+    assert_eq!(
+        exports,
+        vec![
+            "UART_BAUD",
+            "uart_config",
+            "ring_buffer",
+            "Driver",
+            "uart_init",
+            "uart_alloc"
+        ]
+    );
+}
+
+#[test]
+fn doxygen_comments_are_extracted() {
+    let triple = "/// Ring buffer for UART receive.\nstruct ring;";
+    let block = "#include <stdint.h>\n/**\n * @brief Configures the clock tree.\n * @param x unused\n */\nvoid clk(void);";
+    assert_eq!(
+        doc_extractor::extract_doc_comment(triple),
+        Some("Ring buffer for UART receive.".into())
+    );
+    assert_eq!(
+        doc_extractor::extract_doc_comment(block),
+        Some("Configures the clock tree.".into())
+    );
+}
+
+#[test]
+fn includes_resolve_locally_then_via_include_paths() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "src/main.c",
+        "#include \"uart.h\"\n#include <hal/gpio.h>\n#include <stdio.h>\n",
+    );
+    write(root, "src/uart.h", "");
+    write(root, "include/hal/gpio.h", "");
+
+    let paths = ["src/main.c", "src/uart.h", "include/hal/gpio.h"];
+    let without = deps::analyze(root, &semmap_for(&paths));
+    assert_eq!(
+        edges(&without),
+        vec![("src/main.c".into(), "src/uart.h".into())]
+    );
+
+    let options = AnalyzeOptions {
+        include_paths: vec![PathBuf::from("include")],
+    };
+    let with = deps::analyze_with(root, &semmap_for(&paths), &options);
+    assert_eq!(
+        edges(&with),
+        vec![
+            ("src/main.c".into(), "include/hal/gpio.h".into()),
+            ("src/main.c".into(), "src/uart.h".into()),
+        ]
+    );
+}
+
+#[test]
+fn configured_include_paths_reach_every_command() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/main.c", "#include <hal/gpio.h>\n");
+    write(root, "include/hal/gpio.h", "");
+    write(
+        root,
+        "semmap.toml",
+        "[analysis]\ninclude_paths = [\"include\"]\n",
+    );
+    let paths = ["src/main.c", "include/hal/gpio.h"];
+    write(
+        root,
+        "SEMMAP.md",
+        &formatter::to_markdown(&semmap_for(&paths)),
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_semmap"))
+        .args(["impact", "include/hal/gpio.h", "--format", "json"])
+        .current_dir(root)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("src/main.c"), "{stdout}");
+}
+
+#[test]
+fn compile_commands_supply_include_dirs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let abs = root.canonicalize().unwrap();
+    write(root, "app/app.cpp", "#include \"config.hpp\"\n");
+    write(root, "generated/config.hpp", "");
+    write(
+        root,
+        "compile_commands.json",
+        &format!(
+            r#"[{{"directory": "{dir}", "file": "app/app.cpp", "command": "c++ -I generated -c app/app.cpp"}}]"#,
+            dir = abs.display()
+        ),
+    );
+
+    let depmap = deps::analyze(root, &semmap_for(&["app/app.cpp", "generated/config.hpp"]));
+    assert_eq!(
+        edges(&depmap),
+        vec![("app/app.cpp".into(), "generated/config.hpp".into())]
+    );
+}
@@ -0,0 +1,166 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::{semmap_for, write};
+use semmap::commands::{self, DepsOptions};
+use semmap::deps;
+use semmap::types::{DepEdge, DepKind, DependencyMap};
+
+fn call_edge<'a>(depmap: &'a DependencyMap, from: &str, to: &str) -> Option<&'a DepEdge> {
+    depmap
+        .edges
+        .iter()
+        .find(|e| e.from == from && e.to == to && e.kind == DepKind::Call)
+}
+
+#[test]
+fn rust_qualified_imported_and_associated_calls() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "src/parser.rs",
+        "pub fn parse(s: &str) {}\npub fn tokenize() {}\nfn unused() {}\n\npub struct Parser;\n\nimpl Parser {\n    pub fn new() -> Self { Parser }\n    pub fn feed(&self) {}\n}\n",
+    );
+    write(
+        root,
+        "src/main.rs",
+        "use crate::parser::{self, tokenize, Parser};\n\nfn main() {\n    parser::parse(\"x\");\n    tokenize();\n    let p = Parser::new();\n    p.feed();\n}\n",
+    );
+
+    let semmap = semmap_for(&["src/parser.rs", "src/main.rs"]);
+    let depmap = deps::analyze(root, &semmap);
+
+    let edge = call_edge(&depmap, "src/main.rs", "src/parser.rs").unwrap();
+    assert_eq!(
+        edge.symbols,
+        vec!["Parser::new", "feed", "parse", "tokenize"]
+    );
+}
+
+#[test]
+fn calls_require_an_import_edge() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/util.rs", "pub fn helper() {}\n");
+    write(root, "src/main.rs", "fn main() {\n    helper();\n}\n");
+
+    let semmap = semmap_for(&["src/util.rs", "src/main.rs"]);
+    let depmap = deps::analyze(root, &semmap);
+
+    assert!(call_edge(&depmap, "src/main.rs", "src/util.rs").is_none());
+}
+
+#[test]
+fn ts_and_python_calls() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "src/math.ts",
+        "export function add(a: number, b: number) { return a + b; }\nexport const square = (x: number) => x * x;\n",
+    );
+    write(
+        root,
+        "src/app.ts",
+        "import { add } from './math';\nconsole.log(add(1, 2));\nsquare(3);\n",
+    );
+    write(root, "pkg/__init__.py", "");
+    write(
+        root,
+        "pkg/store.py",
+        "class Store:\n    def save(self):\n        pass\n\ndef connect():\n    return Store()\n",
+    );
+    write(
+        root,
+        "pkg/cli.py",
+        "from pkg import store\nfrom pkg.store import Store\n\ndb = store.connect()\nStore().save()\n",
+    );
+
+    let semmap = semmap_for(&[
+        "src/math.ts",
+        "src/app.ts",
+        "pkg/__init__.py",
+        "pkg/store.py",
+        "pkg/cli.py",
+    ]);
+    let depmap = deps::analyze(root, &semmap);
+
+    let ts = call_edge(&depmap, "src/app.ts", "src/math.ts").unwrap();
+    assert_eq!(ts.symbols, vec!["add"]);
+    let py = call_edge(&depmap, "pkg/cli.py", "pkg/store.py").unwrap();
+    assert_eq!(py.symbols, vec!["Store", "connect", "save"]);
+}
+
+#[test]
+fn retain_kinds_filters_edges() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "src/util.rs",
+        "pub fn helper() {}\npub trait Tool {}\n",
+    );
+    write(
+        root,
+        "src/main.rs",
+        "use crate::util::helper;\nstruct X;\nimpl Tool for X {}\nfn main() { helper(); }\n",
+    );
+
+    let semmap = semmap_for(&["src/util.rs", "src/main.rs"]);
+    let mut depmap = deps::analyze(root, &semmap);
+    let mut kinds: Vec<DepKind> = depmap.edges.iter().map(|e| e.kind).collect();
+    kinds.sort_by_key(|k| format!("{k:?}"));
+    assert_eq!(kinds, vec![DepKind::Call, DepKind::Import, DepKind::Trait]);
+
+    deps::retain_kinds(&mut depmap, &[DepKind::parse("call").unwrap()]);
+    assert_eq!(depmap.edges.len(), 1);
+    assert_eq!(depmap.edges.first().unwrap().symbols, vec!["helper"]);
+    assert!(DepKind::parse("bogus").is_none());
+}
+
+#[test]
+fn kind_filter_does_not_hide_violations_from_checks() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/util.rs", "pub struct Util;\n");
+    write(
+        root,
+        "src/main.rs",
+        "use crate::util::Util;\nfn main() {}\n",
+    );
+    write(
+        root,
+        "SEMMAP.md",
+        "# demo -- Semantic Map\n\n**Purpose:** Demo.\n\n## Layer 1 -- Core\n\n`src/main.rs`\nRuns.\n\n## Layer 2 -- Domain\n\n`src/util.rs`\nHelps.\n",
+    );
+
+    let options = DepsOptions {
+        format: "json".into(),
+        check: true,
+        kinds: vec!["call".into()],
+        ..Default::default()
+    };
+    let err = commands::deps(&root.join("SEMMAP.md"), root, &options).unwrap_err();
+    assert!(err.contains("1 layer violations"), "{err}");
+}
+
+#[test]
+fn call_edges_render_with_symbol_labels() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/util.rs", "pub fn helper() {}\n");
+    write(
+        root,
+        "src/main.rs",
+        "use crate::util;\nfn main() { util::helper(); }\n",
+    );
+
+    let semmap = semmap_for(&["src/util.rs", "src/main.rs"]);
+    let mermaid = deps::render_mermaid(&deps::analyze(root, &semmap));
+
+    assert!(mermaid.contains("src_main_rs ==>|\"helper\"| src_util_rs"));
+    let json = serde_json::to_string(&deps::analyze(root, &semmap)).unwrap();
+    assert!(json.contains("\"symbols\":[\"helper\"]"));
+}
@@ -13,7 +13,7 @@ fn validate_fails_on_missing_file() {
     )
     .unwrap();
 
-    let result = commands::validate(&semmap, tmp.path(), false);
+    let result = commands::validate(&semmap, tmp.path(), &commands::ValidateOptions::default());
     assert!(result.is_err(), "Should fail when file doesn't exist");
 
     let err_msg = result.expect_err("should be error");
@@ -26,7 +26,12 @@ fn generate_json_format_arm_not_deleted() {
     fs::write(tmp.path().join("Cargo.toml"), "[package]\nname = \"test\"").unwrap();
 
     let out = tmp.path().join("out.json");
-    commands::generate(tmp.path(), &out, Some("testproj".into()), None, "json").unwrap();
+    let options = commands::GenerateOptions {
+        name: Some("testproj".into()),
+        format: "json".into(),
+        ..Default::default()
+    };
+    commands::generate(tmp.path(), &out, &options).unwrap();
 
     let content = fs::read_to_string(&out).unwrap();
     assert!(
@@ -45,7 +50,12 @@ fn generate_toml_format_arm_not_deleted() {
     fs::write(tmp.path().join("Cargo.toml"), "[package]\nname = \"test\"").unwrap();
 
     let out = tmp.path().join("out.toml");
-    commands::generate(tmp.path(), &out, Some("testproj".into()), None, "toml").unwrap();
+    let options = commands::GenerateOptions {
+        name: Some("testproj".into()),
+        format: "toml".into(),
+        ..Default::default()
+    };
+    commands::generate(tmp.path(), &out, &options).unwrap();
 
     let content = fs::read_to_string(&out).unwrap();
     assert!(
@@ -0,0 +1,30 @@
+//! Fixture helpers shared by the integration tests.
+#![allow(dead_code, clippy::unwrap_used)]
+
+use semmap::types::{FileEntry, Layer};
+use semmap::SemmapFile;
+use std::fs;
+use std::path::Path;
+
+/// Writes `content` to `root/rel`, creating parent directories.
+pub fn write(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// A map with every path in one Domain layer.
+pub fn semmap_for(paths: &[&str]) -> SemmapFile {
+    let mut layer = Layer::new(2, "Domain".into());
+    for path in paths {
+        layer
+            .entries
+            .push(FileEntry::new((*path).into(), "desc".into(), String::new()));
+    }
+    SemmapFile {
+        project_name: "test".into(),
+        purpose: String::new(),
+        legend: vec![],
+        layers: vec![layer],
+    }
+}
@@ -0,0 +1,163 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::write;
+use semmap::commands::{self, ConfigOptions};
+use semmap::config::{self, Config};
+use semmap::{generator, rules};
+use std::fs;
+use std::path::PathBuf;
+
+const CONFIG: &str = "facades = [\"src/api/index.ts\"]
+
+[paths]
+file = \"docs/SEMMAP.md\"
+
+[output]
+layering = \"graph\"
+
+[discovery]
+exclude = [\"src/gen/**\"]
+
+[[layers]]
+number = 3
+name = \"Shell\"
+
+[layer_overrides]
+\"src/legacy/**\" = 2
+\"src/legacy/keep.rs\" = 1
+
+[[rules]]
+name = \"no-test-imports\"
+to = \"tests/**\"
+";
+
+#[test]
+fn discovery_walks_up_and_rebases_paths() {
+    let tmp = tempfile::tempdir().unwrap();
+    write(tmp.path(), "semmap.toml", CONFIG);
+    fs::create_dir_all(tmp.path().join("src/deep")).unwrap();
+
+    let found = config::discover(tmp.path()).unwrap().unwrap();
+    assert_eq!(
+        found.path,
+        tmp.path().canonicalize().unwrap().join("semmap.toml")
+    );
+    let start = tmp.path().join("src/deep");
+    let below = config::discover(&start).unwrap().unwrap();
+    assert_eq!(below.path, found.path);
+    assert_eq!(
+        below.config.paths.file,
+        Some(start.join("../../docs/SEMMAP.md"))
+    );
+    assert_eq!(below.config.paths.root, None);
+    assert_eq!(below.config.facades, vec!["src/api/index.ts"]);
+    assert_eq!(rules::load(&below.config.rules, None).unwrap().len(), 1);
+}
+
+#[test]
+fn cargo_metadata_is_read_when_there_is_no_semmap_toml() {
+    let tmp = tempfile::tempdir().unwrap();
+    write(
+        tmp.path(),
+        "Cargo.toml",
+        "[package]\nname = \"demo\"\n\n[package.metadata.semmap]\nfacades = [\"src/lib.rs\"]\n\n\
+         [package.metadata.semmap.output]\ndiagram = \"d2\"\n\n\
+         [[package.metadata.semmap.rules]]\nname = \"db\"\nto = \"src/db/**\"\nonly_from = \"src/repo/**\"\n",
+    );
+    let found = config::discover(tmp.path()).unwrap().unwrap();
+    assert!(found.path.ends_with("Cargo.toml"));
+    assert_eq!(found.config.output.diagram.as_deref(), Some("d2"));
+    assert_eq!(rules::load(&found.config.rules, None).unwrap().len(), 1);
+
+    // A semmap.toml next to it wins
+    write(tmp.path(), "semmap.toml", "facades = []\n");
+    let found = config::discover(tmp.path()).unwrap().unwrap();
+    assert!(found.path.ends_with("semmap.toml"));
+}
+
+#[test]
+fn invalid_configuration_names_the_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    write(tmp.path(), "semmap.toml", "[output]\nfromat = \"md\"\n");
+    let err = config::discover(tmp.path()).unwrap_err();
+    assert!(err.contains("semmap.toml"), "{err}");
+    assert!(err.contains("fromat"), "{err}");
+
+    write(
+        tmp.path(),
+        "semmap.toml",
+        "[[rules]]\nname = \"empty\"\nlayer = 1\n",
+    );
+    let err = config::discover(tmp.path()).unwrap_err();
+    assert!(err.contains("semmap.toml: rule 'empty' needs"), "{err}");
+}
+
+#[test]
+fn effective_configuration_fills_in_defaults() {
+    let effective = Config::default().effective();
+    assert_eq!(effective.paths.file, Some(PathBuf::from("SEMMAP.md")));
+    assert_eq!(effective.paths.root, Some(PathBuf::from(".")));
+    assert_eq!(effective.output.diagram.as_deref(), Some("mermaid"));
+    assert!(effective
+        .discovery
+        .extensions
+        .unwrap()
+        .contains(&"rs".to_string()));
+    let names: Vec<_> = effective.layers.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["Config", "Core", "Domain", "Utilities", "Tests"]
+    );
+
+    let configured: Config = toml::from_str(CONFIG).unwrap();
+    let effective = configured.effective();
+    assert_eq!(effective.paths.file, Some(PathBuf::from("docs/SEMMAP.md")));
+    let names: Vec<_> = effective.layers.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, vec!["Config", "Core", "Domain", "Shell", "Tests"]);
+    assert_eq!(effective.rules.len(), 1);
+}
+
+#[test]
+fn generate_applies_discovery_schema_and_overrides() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "semmap.toml",
+        &CONFIG.replace("layering = \"graph\"", "layering = \"heuristic\""),
+    );
+    write(root, "src/main.rs", "mod helpers;\nfn main() {}\n");
+    write(root, "src/helpers.rs", "pub fn trim() {}\n");
+    write(root, "src/gen/out.rs", "pub fn generated() {}\n");
+    write(root, "src/legacy/old.rs", "pub fn old() {}\n");
+    write(root, "src/legacy/keep.rs", "pub fn keep() {}\n");
+
+    let found = config::discover(root).unwrap().unwrap();
+    let semmap = generator::generate(root, found.config.generator());
+    let layers = semmap.path_to_layer();
+    assert!(!layers.contains_key("src/gen/out.rs"));
+    assert_eq!(layers.get("src/legacy/old.rs"), Some(&2));
+    assert_eq!(layers.get("src/legacy/keep.rs"), Some(&1));
+    let shell = semmap.layers.iter().find(|l| l.number == 3).unwrap();
+    assert_eq!(shell.name, "Shell");
+}
+
+#[test]
+fn config_command_rejects_unknown_formats() {
+    let tmp = tempfile::tempdir().unwrap();
+    write(tmp.path(), "semmap.toml", CONFIG);
+    let found = config::discover(tmp.path()).unwrap();
+    let options = ConfigOptions {
+        format: "toml".into(),
+        ..Default::default()
+    };
+    commands::config(found.as_ref(), &options).unwrap();
+    let options = ConfigOptions {
+        format: "yaml".into(),
+        ..Default::default()
+    };
+    let err = commands::config(found.as_ref(), &options).unwrap_err();
+    assert_eq!(err, "Unknown config format: yaml (expected toml or json)");
+}
@@ -0,0 +1,132 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::cycles::{self, CycleScope};
+use semmap::deps;
+use semmap::scc;
+use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap};
+
+fn node(path: &str, layer: u8) -> DepNode {
+    DepNode {
+        path: path.into(),
+        layer,
+    }
+}
+
+fn edge(from: &str, to: &str) -> DepEdge {
+    DepEdge {
+        from: from.into(),
+        to: to.into(),
+        kind: DepKind::Import,
+        symbols: vec![],
+    }
+}
+
+fn graph() -> DependencyMap {
+    DependencyMap {
+        nodes: vec![
+            node("src/a.rs", 2),
+            node("src/b.rs", 2),
+            node("src/c.rs", 2),
+            node("src/x/d.rs", 2),
+            node("src/y/e.rs", 3),
+            node("src/leaf.rs", 3),
+        ],
+        edges: vec![
+            edge("src/a.rs", "src/b.rs"),
+            edge("src/b.rs", "src/c.rs"),
+            edge("src/c.rs", "src/a.rs"),
+            edge("src/c.rs", "src/leaf.rs"),
+            edge("src/x/d.rs", "src/y/e.rs"),
+            edge("src/y/e.rs", "src/x/d.rs"),
+        ],
+    }
+}
+
+#[test]
+fn scc_finds_components() {
+    let adjacent = vec![vec![1], vec![2], vec![0, 3], vec![]];
+    let mut components: Vec<Vec<usize>> = scc::strongly_connected(&adjacent)
+        .into_iter()
+        .map(|mut c| {
+            c.sort_unstable();
+            c
+        })
+        .collect();
+    components.sort();
+    assert_eq!(components, vec![vec![0, 1, 2], vec![3]]);
+}
+
+#[test]
+fn reports_each_cycle_with_edge_chain() {
+    let depmap = graph();
+    let found = cycles::find_cycles(&depmap);
+
+    assert_eq!(found.len(), 2);
+    let first = found.first().unwrap();
+    assert_eq!(first.files, vec!["src/a.rs", "src/b.rs", "src/c.rs"]);
+    let hops: Vec<(&str, &str)> = first
+        .chain
+        .iter()
+        .map(|e| (e.from.as_str(), e.to.as_str()))
+        .collect();
+    assert_eq!(
+        hops,
+        vec![
+            ("src/a.rs", "src/b.rs"),
+            ("src/b.rs", "src/c.rs"),
+            ("src/c.rs", "src/a.rs")
+        ]
+    );
+    assert_eq!(
+        first.to_string(),
+        "src/a.rs -[import]-> src/b.rs -[import]-> src/c.rs -[import]-> src/a.rs"
+    );
+}
+
+#[test]
+fn scope_limits_which_cycles_count() {
+    let depmap = graph();
+    let found = cycles::find_cycles(&depmap);
+    let count = |scope| found.iter().filter(|c| c.crosses(scope, &depmap)).count();
+
+    assert_eq!(count(CycleScope::Any), 2);
+    assert_eq!(count(CycleScope::Directory), 1);
+    assert_eq!(count(CycleScope::Layer), 1);
+    assert_eq!(CycleScope::parse("dir"), Some(CycleScope::Directory));
+    assert_eq!(CycleScope::parse("bogus"), None);
+}
+
+#[test]
+fn acyclic_graph_has_no_cycles() {
+    let depmap = DependencyMap {
+        nodes: vec![node("src/a.rs", 1), node("src/b.rs", 2)],
+        edges: vec![edge("src/a.rs", "src/b.rs")],
+    };
+    assert!(cycles::find_cycles(&depmap).is_empty());
+}
+
+#[test]
+fn cycles_are_highlighted_in_mermaid_and_dot() {
+    let depmap = graph();
+    let found = cycles::find_cycles(&depmap);
+    let highlighted = cycles::cycle_edges(&found, &depmap);
+
+    assert_eq!(highlighted.len(), 5);
+    assert!(!highlighted.contains(&("src/c.rs".to_string(), "src/leaf.rs".to_string())));
+
+    let mermaid = deps::render_mermaid_highlighted(&depmap, &highlighted);
+    assert!(mermaid.contains("linkStyle 0,1,2,4,5 stroke:#d33"));
+    assert!(mermaid.contains("class src_a_rs,src_b_rs,src_c_rs,src_x_d_rs,src_y_e_rs cycle"));
+
+    let dot = deps::render_dot(&depmap, &highlighted);
+    assert!(dot.contains(
+        "\"src/a.rs\" -> \"src/b.rs\" [kind=\"import\", style=solid, color=\"#dd3333\"];"
+    ));
+    assert!(dot.contains("\"src/c.rs\" -> \"src/leaf.rs\" [kind=\"import\", style=solid];"));
+}
+
+#[test]
+fn plain_mermaid_has_no_highlight() {
+    let mermaid = deps::render_mermaid(&graph());
+    assert!(!mermaid.contains("linkStyle"));
+}
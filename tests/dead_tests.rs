@@ -0,0 +1,175 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::write;
+use semmap::commands::{self, DeadOptions};
+use semmap::{dead, deps, parser};
+use std::path::Path;
+
+fn analyze(root: &Path, map: &str) -> dead::DeadReport {
+    let semmap = parser::parse(map).unwrap();
+    let depmap = deps::analyze(root, &semmap);
+    dead::analyze(root, &semmap, &depmap)
+}
+
+const RUST_MAP: &str = "# tool -- Semantic Map
+
+**Purpose:** Demo.
+
+## Layer 0 -- Config
+
+`Cargo.toml`
+Manifest.
+
+## Layer 1 -- Core
+
+`src/main.rs`
+Runs.
+
+`tools/gen.rs`
+Generates code.
+
+## Layer 2 -- Domain
+
+`src/cli.rs`
+Parses arguments.
+→ Exports: run, Cli::parse_args, fn legacy_flags()
+
+`src/cli/args.rs`
+Argument types.
+→ Exports: Args
+
+`src/orphan.rs`
+Nobody uses this.
+→ Exports: orphaned
+
+## Layer 4 -- Tests
+
+`tests/cli_tests.rs`
+Tests the CLI.
+";
+
+fn rust_project(root: &Path) {
+    write(
+        root,
+        "Cargo.toml",
+        "[package]\nname = \"tool\"\n\n[[bin]]\nname = \"gen\"\npath = \"tools/gen.rs\"\n",
+    );
+    write(root, "src/main.rs", "mod cli;\nfn main() { cli::run(); }\n");
+    write(
+        root,
+        "src/cli.rs",
+        "mod args;\npub fn run() {}\npub fn legacy_flags() {}\nimpl Cli { fn parse_args() {} }\n",
+    );
+    write(root, "src/cli/args.rs", "pub struct Args;\n");
+    write(root, "src/orphan.rs", "pub fn orphaned() {}\n");
+    write(root, "tools/gen.rs", "fn main() { let a: Args; }\n");
+    write(root, "tests/cli_tests.rs", "fn t() { parse_args(); }\n");
+}
+
+#[test]
+fn files_unreachable_from_entry_points_are_reported() {
+    let tmp = tempfile::tempdir().unwrap();
+    rust_project(tmp.path());
+    let found = analyze(tmp.path(), RUST_MAP);
+
+    assert_eq!(
+        found.entry_points,
+        vec!["src/main.rs", "tests/cli_tests.rs", "tools/gen.rs"]
+    );
+    assert_eq!(found.unreachable, vec!["src/orphan.rs"]);
+}
+
+#[test]
+fn exports_no_other_file_mentions_are_possibly_dead() {
+    let tmp = tempfile::tempdir().unwrap();
+    rust_project(tmp.path());
+    let found = analyze(tmp.path(), RUST_MAP);
+
+    let counts: Vec<_> = found
+        .exports
+        .iter()
+        .map(|e| (e.name.as_str(), e.uses))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![
+            ("run", 1),
+            ("parse_args", 1),
+            ("legacy_flags", 0),
+            ("Args", 1),
+            ("orphaned", 0),
+        ]
+    );
+    let args = found.exports.iter().find(|e| e.name == "Args").unwrap();
+    assert_eq!(args.users, vec!["tools/gen.rs"]);
+    let unused: Vec<_> = found.unused_exports().map(|e| e.name.as_str()).collect();
+    assert_eq!(unused, vec!["legacy_flags", "orphaned"]);
+    assert!(!found.is_clean());
+
+    let text = dead::render_text(&found, false);
+    assert!(text.starts_with("3 entry points, 1 unreachable files, 2 possibly unused exports\n"));
+    assert!(text.contains("\nUnreachable from any entry point:\n  src/orphan.rs\n"));
+    assert!(text.contains("    0  src/cli.rs  legacy_flags\n"));
+    assert!(!text.contains("  run\n"));
+    assert!(dead::render_text(&found, true).contains("    1  src/cli.rs  run\n"));
+}
+
+#[test]
+fn js_entry_points_include_index_tests_bins_and_tags() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "package.json", r#"{"bin": {"tool": "bin/tool.js"}}"#);
+    write(root, "bin/tool.js", "require('../src/cli');\n");
+    write(root, "src/cli.js", "");
+    write(root, "src/index.ts", "import { a } from './a';\n");
+    write(root, "src/a.ts", "");
+    write(root, "src/c.ts", "");
+    write(root, "src/c.spec.ts", "import { c } from './c';\n");
+    write(root, "scripts/deploy.ts", "");
+    write(root, "src/stale.ts", "");
+    let map = "# web -- Semantic Map\n\n## Layer 0 -- Config\n\n`package.json`\nManifest.\n\n\
+               ## Layer 1 -- Core\n\n`bin/tool.js`\nCLI.\n\n`src/index.ts`\nRoot.\n\n\
+               `scripts/deploy.ts` `[ENTRY]`\nDeploys.\n\n## Layer 2 -- Domain\n\n\
+               `src/cli.js`\nCommands.\n\n`src/a.ts`\nA.\n\n`src/c.ts`\nC.\n\n\
+               `src/stale.ts`\nOld.\n\n## Layer 4 -- Tests\n\n`src/c.spec.ts`\nSpecs.\n";
+
+    let found = analyze(root, map);
+    assert_eq!(
+        found.entry_points,
+        vec![
+            "bin/tool.js",
+            "scripts/deploy.ts",
+            "src/c.spec.ts",
+            "src/index.ts"
+        ]
+    );
+    assert_eq!(found.unreachable, vec!["src/stale.ts"]);
+}
+
+#[test]
+fn dead_check_fails_while_anything_looks_dead() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    rust_project(root);
+    write(root, "SEMMAP.md", RUST_MAP);
+    let options = DeadOptions {
+        check: true,
+        ..Default::default()
+    };
+    let err = commands::dead(&root.join("SEMMAP.md"), root, &options).unwrap_err();
+    assert_eq!(err, "1 unreachable files, 2 unused exports");
+
+    let clean = RUST_MAP
+        .replace(
+            "→ Exports: run, Cli::parse_args, fn legacy_flags()",
+            "→ Exports: run",
+        )
+        .replace(
+            "`src/orphan.rs`\nNobody uses this.\n→ Exports: orphaned\n\n",
+            "",
+        );
+    write(root, "SEMMAP.md", &clean);
+    commands::dead(&root.join("SEMMAP.md"), root, &options).unwrap();
+}
@@ -0,0 +1,108 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::deps::AnalyzeOptions;
+use semmap::types::{DepEdge, DepKind, DependencyMap};
+use semmap::{declared_deps, formatter, parser, validator};
+use std::fs;
+
+const MAP: &str = "# t -- Semantic Map
+
+## Layer 1 -- Core
+
+`src/app.rs`
+Runs the app.
+→ Depends: src/config.rs, `src/store/**`
+
+`src/config.rs`
+Loads config.
+
+`src/log.rs`
+Writes logs.
+";
+
+fn edge(from: &str, to: &str, kind: DepKind) -> DepEdge {
+    DepEdge {
+        from: from.into(),
+        to: to.into(),
+        kind,
+        symbols: vec![],
+    }
+}
+
+fn messages(semmap: &str, edges: Vec<DepEdge>) -> Vec<String> {
+    let semmap = parser::parse(semmap).unwrap();
+    let depmap = DependencyMap {
+        nodes: vec![],
+        edges,
+    };
+    declared_deps::check(&semmap, &depmap)
+        .into_iter()
+        .map(|i| format!("{}: {}", i.path.unwrap_or_default(), i.message))
+        .collect()
+}
+
+#[test]
+fn parses_and_formats_depends() {
+    let semmap = parser::parse(MAP).unwrap();
+    let entry = semmap.find_entry("src/app.rs").unwrap();
+    assert_eq!(
+        entry.depends.as_deref(),
+        Some(["src/config.rs".to_string(), "src/store/**".to_string()].as_slice())
+    );
+    assert!(semmap.find_entry("src/log.rs").unwrap().depends.is_none());
+
+    let markdown = formatter::to_markdown(&semmap);
+    assert!(markdown.contains("→ Depends: src/config.rs, src/store/**\n"));
+}
+
+#[test]
+fn reports_undeclared_and_unused_dependencies() {
+    let edges = vec![
+        edge("src/app.rs", "src/config.rs", DepKind::Import),
+        edge("src/app.rs", "src/log.rs", DepKind::Call),
+        edge("src/log.rs", "src/config.rs", DepKind::Import),
+    ];
+    assert_eq!(
+        messages(MAP, edges),
+        vec![
+            "src/app.rs: Undeclared dependency: src/log.rs",
+            "src/app.rs: Declared dependency unused: src/store/**",
+        ]
+    );
+}
+
+#[test]
+fn glob_declarations_cover_matching_files() {
+    let edges = vec![
+        edge("src/app.rs", "src/config.rs", DepKind::Import),
+        edge("src/app.rs", "src/store/db.rs", DepKind::Import),
+        edge("src/app.rs", "src/store/cache.rs", DepKind::Trait),
+    ];
+    assert!(messages(MAP, edges).is_empty());
+}
+
+#[test]
+fn empty_declaration_means_no_dependencies() {
+    let map = MAP.replace("src/config.rs, `src/store/**`", "");
+    let edges = vec![edge("src/app.rs", "src/config.rs", DepKind::Import)];
+    assert_eq!(
+        messages(&map, edges),
+        vec!["src/app.rs: Undeclared dependency: src/config.rs"]
+    );
+}
+
+#[test]
+fn strict_validation_checks_declarations_against_code() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/app.rs"), "use crate::log;\n").unwrap();
+    fs::write(root.join("src/config.rs"), "").unwrap();
+    fs::write(root.join("src/log.rs"), "").unwrap();
+
+    let semmap = parser::parse(MAP).unwrap();
+    let result = validator::validate_against_codebase(&semmap, root, &AnalyzeOptions::default());
+    let found: Vec<&str> = result.issues.iter().map(|i| i.message.as_str()).collect();
+    assert!(found.contains(&"Undeclared dependency: src/log.rs"));
+    assert!(found.contains(&"Declared dependency unused: src/config.rs"));
+}
@@ -1,8 +1,8 @@
 #![allow(clippy::unwrap_used)]
 
-use semmap::deps;
 use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap, FileEntry, Layer};
 use semmap::SemmapFile;
+use semmap::{deps, facades};
 
 #[test]
 fn layer_violation_detects_specific_patterns() {
@@ -22,6 +22,7 @@ fn layer_violation_detects_specific_patterns() {
             from: "a.rs".into(),
             to: "b.rs".into(),
             kind: DepKind::Import,
+            symbols: vec![],
         }],
     };
 
@@ -62,6 +63,7 @@ fn layer_violation_detects_specific_patterns() {
             from: "low.rs".into(),
             to: "high.rs".into(),
             kind: DepKind::Import,
+            symbols: vec![],
         }],
     };
 
@@ -89,7 +91,7 @@ fn layer_violation_detects_specific_patterns() {
     assert!(!violations_down.is_empty(), "L2 -> L3 should be violation");
 
     // Safe access without indexing
-    let first_violation = violations_down.first().unwrap();
+    let first_violation = violations_down.first().unwrap().to_string();
     assert!(first_violation.contains("L2") && first_violation.contains("L3"));
 }
 
@@ -110,6 +112,7 @@ fn layer_violation_ignores_facade_files() {
             from: "src/lib.rs".into(),
             to: "src/utils.rs".into(),
             kind: DepKind::Import,
+            symbols: vec![],
         }],
     };
 
@@ -133,9 +136,26 @@ fn layer_violation_ignores_facade_files() {
         layers: vec![layer1, layer3],
     };
 
-    let violations = deps::check_layer_violations(&depmap, &semmap);
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir(tmp.path().join("src")).unwrap();
+    std::fs::write(tmp.path().join("src/lib.rs"), "//! Root.\npub mod utils;\n").unwrap();
+    let facades = facades::detect(tmp.path(), &semmap, &[]);
+
+    let violations = deps::check_layer_violations_with(&depmap, &semmap, &facades);
     assert!(
         violations.is_empty(),
         "lib.rs facade should not trigger violation"
     );
+
+    std::fs::write(
+        tmp.path().join("src/lib.rs"),
+        "use crate::utils::helper;\nfn run() { helper(); }\n",
+    )
+    .unwrap();
+    let facades = facades::detect(tmp.path(), &semmap, &[]);
+    assert_eq!(
+        deps::check_layer_violations_with(&depmap, &semmap, &facades).len(),
+        1,
+        "lib.rs with real code is checked like any other file"
+    );
 }
@@ -1,18 +1,8 @@
 use semmap::deps::{check_layer_violations, render_mermaid};
-use semmap::types::{
-    DepEdge, DepKind, DepNode, DependencyMap, Description, FileEntry, Layer, SemmapFile,
-};
+use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap, FileEntry, Layer, SemmapFile};
 
 fn entry(path: &str) -> FileEntry {
-    FileEntry {
-        path: path.to_string(),
-        description: Description {
-            what: "Test.".to_string(),
-            why: "Test.".to_string(),
-        },
-        exports: None,
-        touch: None,
-    }
+    FileEntry::new(path.to_string(), "Test.".to_string(), "Test.".to_string())
 }
 
 fn layer(number: u8, paths: &[&str]) -> Layer {
@@ -58,6 +48,7 @@ fn test_layer_violation_detected() {
                 from: "config.rs".to_string(),
                 to: "app.rs".to_string(),
                 kind: DepKind::Import,
+                symbols: vec![],
             },
         ],
     };
@@ -66,7 +57,7 @@ fn test_layer_violation_detected() {
     assert_eq!(violations.len(), 1);
     assert!(violations
         .first()
-        .is_some_and(|v| v.contains("config.rs") && v.contains("app.rs")));
+        .is_some_and(|v| v.from == "config.rs" && v.to == "app.rs"));
 }
 
 /// Higher layer depending on lower layer is valid (no violation).
@@ -96,6 +87,7 @@ fn test_valid_dependency_no_violation() {
                 from: "high.rs".to_string(),
                 to: "low.rs".to_string(),
                 kind: DepKind::Import,
+                symbols: vec![],
             },
         ],
     };
@@ -129,6 +121,7 @@ fn test_same_layer_no_violation() {
             from: "a.rs".to_string(),
             to: "b.rs".to_string(),
             kind: DepKind::Import,
+            symbols: vec![],
         }],
     };
 
@@ -179,6 +172,7 @@ fn test_render_mermaid_edges() {
             from: "a.rs".to_string(),
             to: "b.rs".to_string(),
             kind: DepKind::Import,
+            symbols: vec![],
         }],
     };
     let output = render_mermaid(&depmap);
@@ -0,0 +1,167 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::render::{self, Grouping, RenderOptions, ViewOptions};
+use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap};
+
+fn node(path: &str, layer: u8) -> DepNode {
+    DepNode {
+        path: path.into(),
+        layer,
+    }
+}
+
+fn edge(from: &str, to: &str, symbols: &[&str]) -> DepEdge {
+    DepEdge {
+        from: from.into(),
+        to: to.into(),
+        kind: DepKind::Call,
+        symbols: symbols.iter().map(|s| (*s).to_string()).collect(),
+    }
+}
+
+fn graph() -> DependencyMap {
+    DependencyMap {
+        nodes: vec![
+            node("src/main.rs", 1),
+            node("src/api/mod.rs", 2),
+            node("src/api/users.rs", 2),
+            node("src/db/mod.rs", 3),
+            node("src/db/pool.rs", 3),
+            node("tests/api.rs", 4),
+        ],
+        edges: vec![
+            edge("src/main.rs", "src/api/mod.rs", &["serve"]),
+            edge("src/api/users.rs", "src/db/pool.rs", &["get"]),
+            edge("src/api/mod.rs", "src/db/mod.rs", &["connect"]),
+            edge("src/api/users.rs", "src/db/mod.rs", &["begin"]),
+            edge("src/db/pool.rs", "src/main.rs", &["config"]),
+            edge("tests/api.rs", "src/api/users.rs", &[]),
+        ],
+    }
+}
+
+fn paths(depmap: &DependencyMap) -> Vec<&str> {
+    depmap.nodes.iter().map(|n| n.path.as_str()).collect()
+}
+
+#[test]
+fn mermaid_groups_layers_into_named_subgraphs() {
+    let options = RenderOptions {
+        grouping: Grouping::Layer,
+        layer_names: [(1, "Core".to_string())].into(),
+        ..RenderOptions::default()
+    };
+    let mermaid = render::render_mermaid_with(&graph(), &options);
+    assert!(mermaid.contains(
+        "    subgraph layer_1[\"Layer 1 -- Core\"]\n        src_main_rs[\"main.rs\"]\n    end\n"
+    ));
+    assert!(mermaid.contains("    subgraph layer_3[\"Layer 3\"]\n"));
+}
+
+#[test]
+fn directory_clusters_in_dot() {
+    let options = RenderOptions {
+        grouping: Grouping::Directory,
+        ..RenderOptions::default()
+    };
+    let dot = render::render_dot_with(&graph(), &options);
+    assert!(dot.contains("    subgraph cluster_dir_src_db {\n        label=\"src/db\";\n"));
+    assert!(dot.contains("        \"src/db/pool.rs\" [label=\"pool.rs\", layer=3];"));
+}
+
+#[test]
+fn labels_are_unique_path_suffixes() {
+    let mermaid = render::render_mermaid(&graph());
+    assert!(mermaid.contains("src_api_mod_rs[\"api/mod.rs\"]"));
+    assert!(mermaid.contains("src_db_mod_rs[\"db/mod.rs\"]"));
+    assert!(mermaid.contains("tests_api_rs[\"api.rs\"]"));
+}
+
+#[test]
+fn violations_are_red_with_marked_sources() {
+    let options = RenderOptions {
+        violations: [("src/main.rs".to_string(), "src/api/mod.rs".to_string())].into(),
+        ..RenderOptions::default()
+    };
+    let mermaid = render::render_mermaid_with(&graph(), &options);
+    assert!(mermaid.contains("    linkStyle 0 stroke:#d33,stroke-width:2px\n"));
+    assert!(mermaid.contains("    class src_main_rs violation\n"));
+    assert!(!mermaid.contains("cycle"));
+}
+
+#[test]
+fn include_and_exclude_filter_nodes_and_edges() {
+    let view = ViewOptions {
+        include: vec!["src".into()],
+        exclude: vec!["src/db/*.rs".into()],
+        ..ViewOptions::default()
+    };
+    let shown = render::view(&graph(), &view).unwrap();
+    assert_eq!(
+        paths(&shown),
+        vec!["src/main.rs", "src/api/mod.rs", "src/api/users.rs"]
+    );
+    assert_eq!(shown.edges.len(), 1);
+}
+
+#[test]
+fn focus_keeps_neighbours_within_depth() {
+    let mut view = ViewOptions {
+        focus: Some("src/db/pool.rs".into()),
+        ..ViewOptions::default()
+    };
+    let near = render::view(&graph(), &view).unwrap();
+    assert_eq!(
+        paths(&near),
+        vec!["src/main.rs", "src/api/users.rs", "src/db/pool.rs"]
+    );
+
+    view.depth = Some(2);
+    let wider = render::view(&graph(), &view).unwrap();
+    assert_eq!(wider.nodes.len(), 6);
+
+    view.focus = Some("src/missing.rs".into());
+    assert!(render::view(&graph(), &view).is_err());
+}
+
+#[test]
+fn collapse_merges_directories_and_symbols() {
+    let view = ViewOptions {
+        collapse: true,
+        ..ViewOptions::default()
+    };
+    let collapsed = render::view(&graph(), &view).unwrap();
+    assert_eq!(paths(&collapsed), vec!["src", "src/api", "src/db", "tests"]);
+
+    let api_db = collapsed
+        .edges
+        .iter()
+        .find(|e| e.from == "src/api" && e.to == "src/db")
+        .unwrap();
+    assert_eq!(api_db.symbols, vec!["begin", "connect", "get"]);
+    assert_eq!(collapsed.edges.len(), 4);
+
+    let highlighted = [("src/api/users.rs".to_string(), "src/db/pool.rs".to_string())].into();
+    let mapped = render::view_edges(&highlighted, &view);
+    assert!(mapped.contains(&("src/api".to_string(), "src/db".to_string())));
+}
+
+#[test]
+fn d2_and_plantuml_nest_nodes_in_groups() {
+    let options = RenderOptions {
+        grouping: Grouping::Layer,
+        ..RenderOptions::default()
+    };
+    let d2 = render::render_d2_with(&graph(), &options);
+    assert!(
+        d2.contains("layer_1: \"Layer 1\" {\n  \"src/main.rs\": \"main.rs\" {class: layer-1}\n}")
+    );
+    assert!(d2.contains(
+        "layer_1.\"src/main.rs\" -> layer_2.\"src/api/mod.rs\": \"serve\" {class: call}"
+    ));
+
+    let puml = render::render_plantuml_with(&graph(), &options);
+    assert!(
+        puml.contains("package \"Layer 4\" {\n  component \"api.rs\" as tests_api_rs <<L4>>\n}")
+    );
+}
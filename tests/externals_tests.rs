@@ -0,0 +1,292 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::write;
+use semmap::commands::{self, DepsOptions};
+use semmap::externals::{self, Ecosystem, Inventory};
+use semmap::parser;
+use semmap::rules;
+use semmap::types::{DepNode, DependencyMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+fn map(paths: &[(&str, u8)]) -> String {
+    let mut out = String::from("# demo -- Semantic Map\n\n**Purpose:** Demo.\n");
+    for layer in 0..5u8 {
+        let _ = write!(out, "\n## Layer {layer} -- L{layer}\n");
+        for (path, _) in paths.iter().filter(|(_, l)| *l == layer) {
+            let _ = write!(out, "\n`{path}`\nDoes things.\n");
+        }
+    }
+    out
+}
+
+fn inventory(root: &Path, paths: &[(&str, u8)]) -> Inventory {
+    let semmap = parser::parse(&map(paths)).unwrap();
+    externals::inventory(root, &semmap)
+}
+
+#[test]
+fn rust_crates_come_from_the_nearest_cargo_toml() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "Cargo.toml",
+        "[package]\nname = \"demo\"\n\n[dependencies]\nregex = \"1.10\"\n\
+         tokio-util = { version = \"0.7\", features = [\"io\"] }\n\
+         helpers = { path = \"crates/helpers\" }\n\n[dev-dependencies]\ntempfile = \"3\"\n",
+    );
+    write(
+        root,
+        "src/lib.rs",
+        "use regex::Regex;\nuse std::fs;\nuse crate::db::pool;\n\
+         // serde_json::Value is mentioned in a comment only\n\
+         fn f() { tokio_util::io::x(); helpers::go(); }\n",
+    );
+    write(
+        root,
+        "tests/lib_tests.rs",
+        "fn t() { tempfile::tempdir(); }\n",
+    );
+    write(
+        root,
+        "crates/tool/Cargo.toml",
+        "[package]\nname = \"tool\"\n\n[dependencies]\nregex = \"1.5\"\nclap = \"4\"\n",
+    );
+    write(root, "crates/tool/src/main.rs", "use clap::Parser;\n");
+
+    let found = inventory(
+        root,
+        &[
+            ("src/lib.rs", 2),
+            ("tests/lib_tests.rs", 4),
+            ("crates/tool/src/main.rs", 1),
+        ],
+    );
+    let names: Vec<_> = found.packages.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["clap", "helpers", "regex", "tempfile", "tokio-util"]
+    );
+
+    let tokio = found.package("tokio-util").unwrap();
+    assert_eq!(tokio.ecosystem, Ecosystem::Cargo);
+    assert_eq!(tokio.version.as_deref(), Some("0.7"));
+    assert_eq!(tokio.files, vec!["src/lib.rs"]);
+    assert_eq!(
+        found.package("helpers").unwrap().version.as_deref(),
+        Some("path")
+    );
+    assert_eq!(found.package("clap").unwrap().version.as_deref(), Some("4"));
+    assert_eq!(
+        found.files.get("src/lib.rs").unwrap(),
+        &vec!["helpers", "regex", "tokio-util"]
+    );
+}
+
+#[test]
+fn each_declared_version_is_its_own_package() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "app/Cargo.toml",
+        "[package]\nname = \"app\"\n\n[dependencies]\nregex = \"1.10\"\n",
+    );
+    write(root, "app/src/main.rs", "use regex::Regex;\n");
+    write(
+        root,
+        "tool/Cargo.toml",
+        "[package]\nname = \"tool\"\n\n[dependencies]\nregex = \"1.5\"\n",
+    );
+    write(root, "tool/src/main.rs", "use regex::Regex;\n");
+
+    let found = inventory(root, &[("app/src/main.rs", 3), ("tool/src/main.rs", 3)]);
+    let versions: Vec<_> = found
+        .packages
+        .iter()
+        .map(|p| (p.name.as_str(), p.version.as_deref(), p.files.clone()))
+        .collect();
+    assert_eq!(
+        versions,
+        vec![
+            ("regex", Some("1.10"), vec!["app/src/main.rs".to_string()]),
+            ("regex", Some("1.5"), vec!["tool/src/main.rs".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn npm_packages_skip_builtins_relative_and_aliased_imports() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "package.json",
+        r#"{"dependencies": {"react": "^18.2.0", "@acme/ui": "2.1.0"},
+            "devDependencies": {"vitest": "^1.0.0"}}"#,
+    );
+    write(
+        root,
+        "tsconfig.json",
+        r#"{"compilerOptions": {"baseUrl": ".", "paths": {"@app/*": ["src/*"]}}}"#,
+    );
+    write(
+        root,
+        "src/app.tsx",
+        "import React from 'react';\nimport { Button } from '@acme/ui/button';\n\
+         import fs from 'fs';\nimport path from 'node:path';\nimport { db } from './db';\n\
+         import { cfg } from '@app/config';\nconst _ = require('lodash/fp');\n",
+    );
+    write(root, "src/db.ts", "export const db = 1;\n");
+    write(root, "src/config.ts", "export const cfg = 1;\n");
+
+    let found = inventory(
+        root,
+        &[("src/app.tsx", 1), ("src/db.ts", 3), ("src/config.ts", 0)],
+    );
+    let names: Vec<_> = found.packages.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["@acme/ui", "lodash", "react"]);
+    assert_eq!(
+        found.package("react").unwrap().version.as_deref(),
+        Some("^18.2.0")
+    );
+    assert_eq!(found.package("lodash").unwrap().version, None);
+    assert_eq!(found.package("@acme/ui").unwrap().ecosystem, Ecosystem::Npm);
+}
+
+#[test]
+fn python_packages_match_pyproject_names() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "pyproject.toml",
+        "[project]\nname = \"shop\"\ndependencies = [\n  \"requests[socks] >=2.31\",\n  \
+         \"Typing-Extensions==4.9; python_version < '3.11'\",\n]\n\n\
+         [tool.poetry.group.dev.dependencies]\npytest = \"^8.0\"\n",
+    );
+    write(
+        root,
+        "shop/api.py",
+        "import os, json\nimport requests\nfrom typing_extensions import Self\n\
+         from shop import models\nfrom . import models\nimport yaml\n",
+    );
+    write(root, "shop/__init__.py", "");
+    write(root, "shop/models.py", "");
+    write(root, "tests/test_api.py", "import pytest\n");
+
+    let found = inventory(
+        root,
+        &[
+            ("shop/api.py", 2),
+            ("shop/__init__.py", 1),
+            ("shop/models.py", 2),
+            ("tests/test_api.py", 4),
+        ],
+    );
+    let names: Vec<_> = found.packages.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["Typing-Extensions", "pytest", "requests", "yaml"]
+    );
+    assert_eq!(
+        found.package("requests").unwrap().version.as_deref(),
+        Some(">=2.31")
+    );
+    assert_eq!(
+        found
+            .package("Typing-Extensions")
+            .unwrap()
+            .version
+            .as_deref(),
+        Some("==4.9")
+    );
+    assert_eq!(
+        found.package("pytest").unwrap().version.as_deref(),
+        Some("^8.0")
+    );
+    assert_eq!(found.package("yaml").unwrap().version, None);
+}
+
+#[test]
+fn external_rules_restrict_packages_to_layers_and_files() {
+    let rules = rules::parse(
+        "[[rules]]\nname = \"sql-in-storage\"\nexternal = [\"sqlx\", \"diesel*\"]\nlayers = [3]\n\n\
+         [[rules]]\nname = \"http-in-client\"\nexternal = \"reqwest\"\nonly_from = \"src/client/**\"\n",
+    )
+    .unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "Cargo.toml",
+        "[dependencies]\nsqlx = \"0.7\"\ndiesel_migrations = \"2\"\nreqwest = \"0.12\"\n",
+    );
+    write(
+        root,
+        "src/api.rs",
+        "fn f() { sqlx::query(); reqwest::get(); }\n",
+    );
+    write(
+        root,
+        "src/store.rs",
+        "fn f() { sqlx::query(); diesel_migrations::run(); }\n",
+    );
+    write(root, "src/client/http.rs", "fn f() { reqwest::get(); }\n");
+    let paths = [
+        ("src/api.rs", 2),
+        ("src/store.rs", 3),
+        ("src/client/http.rs", 3),
+    ];
+    let found = inventory(root, &paths);
+    let depmap = DependencyMap {
+        nodes: paths
+            .iter()
+            .map(|(path, layer)| DepNode {
+                path: (*path).into(),
+                layer: *layer,
+            })
+            .collect(),
+        edges: vec![],
+    };
+    let broken: Vec<_> = rules::evaluate_externals(&rules, &found, &depmap)
+        .into_iter()
+        .map(|v| format!("{}: {} -> {}", v.rule, v.from, v.to))
+        .collect();
+    assert_eq!(
+        broken,
+        vec![
+            "sql-in-storage: src/api.rs -> sqlx",
+            "http-in-client: src/api.rs -> reqwest",
+        ]
+    );
+
+    write(root, "SEMMAP.md", &map(&paths));
+    let rules_file = root.join("rules.toml");
+    fs::write(
+        &rules_file,
+        "[[rules]]\nname = \"sql-in-storage\"\nexternal = \"sqlx\"\nlayers = [3]\n",
+    )
+    .unwrap();
+    let options = DepsOptions {
+        format: "json".into(),
+        check: true,
+        rules: Some(rules_file),
+        ..Default::default()
+    };
+    let err = commands::deps(&root.join("SEMMAP.md"), root, &options).unwrap_err();
+    assert!(err.contains("1 rule violations"), "{err}");
+}
+
+#[test]
+fn external_rules_need_a_restriction() {
+    let err = rules::parse("[[rules]]\nname = \"x\"\nexternal = \"sqlx\"\n").unwrap_err();
+    assert!(
+        err.contains("`external` with `layers` or `only_from`"),
+        "{err}"
+    );
+}
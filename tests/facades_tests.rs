@@ -0,0 +1,130 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::facades;
+use semmap::layer_check::check_layer_violations_with;
+use semmap::parser;
+use semmap::types::{DepEdge, DepKind, DependencyMap};
+use std::collections::HashSet;
+use std::fs;
+
+#[test]
+fn detects_reexport_only_files_by_content() {
+    assert!(facades::is_facade(
+        "//! Crate root.\n#![deny(missing_docs)]\n\npub mod parser;\nmod util;\npub use parser::{\n    parse,\n    Error,\n};\n",
+        "src/lib.rs"
+    ));
+    assert!(facades::is_facade(
+        "// Public API\nexport * from './user';\nexport { Order, type Line } from \"./order\";\nexport * as money from './money'\n",
+        "src/index.ts"
+    ));
+    assert!(facades::is_facade(
+        "\"\"\"Package.\"\"\"\nfrom .core import (\n    Engine,\n    run,\n)\nfrom .util import helper  # noqa\n__all__ = [\"Engine\", \"run\"]\n",
+        "pkg/__init__.py"
+    ));
+}
+
+#[test]
+fn files_with_code_or_nothing_are_not_facades() {
+    assert!(!facades::is_facade(
+        "pub mod a;\nfn main() {}\n",
+        "src/main.rs"
+    ));
+    assert!(!facades::is_facade(
+        "use crate::a;\npub use a::b;\n",
+        "src/mod.rs"
+    ));
+    assert!(!facades::is_facade(
+        "export * from './a';\nexport const x = 1;\n",
+        "index.ts"
+    ));
+    assert!(!facades::is_facade("import os\n", "pkg/__init__.py"));
+    assert!(!facades::is_facade("// empty\n", "src/lib.rs"));
+    assert!(!facades::is_facade("#include \"a.h\"\n", "src/all.h"));
+}
+
+const MAP: &str = "# t -- Semantic Map
+
+## Layer 1 -- Core
+
+`src/app.rs`
+App.
+
+`src/lib.rs`
+Root.
+
+`src/api/index.ts`
+Api barrel.
+
+## Layer 3 -- Utilities
+
+`src/util.rs`
+Helpers.
+";
+
+fn edge(from: &str, to: &str) -> DepEdge {
+    DepEdge {
+        from: from.into(),
+        to: to.into(),
+        kind: DepKind::Import,
+        symbols: vec![],
+    }
+}
+
+#[test]
+fn violations_are_traced_through_facades() {
+    let semmap = parser::parse(MAP).unwrap();
+    let depmap = DependencyMap {
+        nodes: vec![],
+        edges: vec![
+            edge("src/app.rs", "src/lib.rs"),
+            edge("src/lib.rs", "src/api/index.ts"),
+            edge("src/api/index.ts", "src/util.rs"),
+        ],
+    };
+    let facades: HashSet<String> = ["src/lib.rs", "src/api/index.ts"].map(String::from).into();
+
+    assert_eq!(
+        check_layer_violations_with(&depmap, &semmap, &facades)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["Layer violation: src/app.rs (L1) depends on src/util.rs (L3) via src/lib.rs"]
+    );
+}
+
+#[test]
+fn detect_combines_content_and_configured_globs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src/api")).unwrap();
+    fs::write(root.join("src/lib.rs"), "pub mod app;\npub mod util;\n").unwrap();
+    fs::write(root.join("src/app.rs"), "use crate::util;\n").unwrap();
+    fs::write(root.join("src/api/index.ts"), "export const handler = 1;\n").unwrap();
+
+    let globs = vec!["src/api/index.ts".to_string()];
+    let semmap = parser::parse(MAP).unwrap();
+    let mut found: Vec<String> = facades::detect(root, &semmap, &globs).into_iter().collect();
+    found.sort();
+    assert_eq!(found, vec!["src/api/index.ts", "src/lib.rs"]);
+}
+
+#[test]
+fn violations_carry_layers_kind_rule_and_suggestion() {
+    let semmap = parser::parse(MAP).unwrap();
+    let depmap = DependencyMap {
+        nodes: vec![],
+        edges: vec![edge("src/app.rs", "src/util.rs")],
+    };
+    let violations = check_layer_violations_with(&depmap, &semmap, &HashSet::new());
+    let v = violations.first().unwrap();
+    assert_eq!((v.from_layer, v.to_layer), (1, 3));
+    assert_eq!(v.kind, DepKind::Import);
+    assert_eq!(v.rule, "layer-order");
+    assert_eq!(v.via, None);
+    assert!(v.suggestion.contains("→ Allow: src/util.rs"));
+
+    let json = serde_json::to_value(v).unwrap();
+    assert_eq!(json.get("from").unwrap(), "src/app.rs");
+    assert_eq!(json.get("kind").unwrap(), "Import");
+    assert!(json.get("via").is_none());
+}
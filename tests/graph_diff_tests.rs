@@ -0,0 +1,244 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::write;
+use semmap::commands::{self, DepsOptions};
+use semmap::deps::AnalyzeOptions;
+use semmap::externals::Inventory;
+use semmap::graph_diff::{self, Analysis, Snapshot};
+use semmap::render::{self, RenderOptions};
+use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn node(path: &str, layer: u8) -> DepNode {
+    DepNode {
+        path: path.into(),
+        layer,
+    }
+}
+
+fn edge(from: &str, to: &str, kind: DepKind) -> DepEdge {
+    DepEdge {
+        from: from.into(),
+        to: to.into(),
+        kind,
+        symbols: vec![],
+    }
+}
+
+fn analysis(nodes: Vec<DepNode>, edges: Vec<DepEdge>) -> Analysis {
+    Analysis {
+        depmap: DependencyMap { nodes, edges },
+        violations: vec![],
+        cycles: vec![],
+        externals: Inventory::default(),
+    }
+}
+
+#[test]
+fn compare_reports_edge_changes_and_merges_both_sides() {
+    let old = analysis(
+        vec![
+            node("src/a.rs", 1),
+            node("src/b.rs", 2),
+            node("src/gone.rs", 3),
+        ],
+        vec![
+            edge("src/a.rs", "src/b.rs", DepKind::Import),
+            edge("src/a.rs", "src/gone.rs", DepKind::Import),
+        ],
+    );
+    let new = analysis(
+        vec![
+            node("src/a.rs", 1),
+            node("src/b.rs", 2),
+            node("src/c.rs", 3),
+        ],
+        vec![
+            edge("src/a.rs", "src/b.rs", DepKind::Import),
+            edge("src/a.rs", "src/b.rs", DepKind::Call),
+            edge("src/b.rs", "src/c.rs", DepKind::Import),
+        ],
+    );
+    let diff = graph_diff::compare("main", &old, &new);
+    let added: Vec<_> = diff
+        .added_edges
+        .iter()
+        .map(|e| (e.to.as_str(), e.kind))
+        .collect();
+    assert_eq!(
+        added,
+        vec![("src/b.rs", DepKind::Call), ("src/c.rs", DepKind::Import)]
+    );
+    assert_eq!(diff.removed_edges.len(), 1);
+    assert_eq!(
+        graph_diff::render_text(&diff),
+        "Dependency changes since main: +2 -1 edges\n\
+         + src/a.rs -> src/b.rs (call)\n\
+         + src/b.rs -> src/c.rs (import)\n\
+         - src/a.rs -> src/gone.rs (import)\n"
+    );
+
+    let merged = graph_diff::merged(&old.depmap, &new.depmap);
+    assert!(merged.nodes.iter().any(|n| n.path == "src/gone.rs"));
+    assert_eq!(merged.edges.len(), 4);
+    let options = RenderOptions {
+        added: [("src/b.rs".to_string(), "src/c.rs".to_string())].into(),
+        removed: [("src/a.rs".to_string(), "src/gone.rs".to_string())].into(),
+        ..RenderOptions::default()
+    };
+    let mermaid = render::render_mermaid_with(&merged, &options);
+    assert!(mermaid.contains("    linkStyle 2 stroke:#2a2,stroke-width:2px\n"));
+    assert!(mermaid.contains("    linkStyle 3 stroke:#999,stroke-dasharray:4 4\n"));
+
+    let unchanged = graph_diff::compare("main", &new, &new);
+    assert!(unchanged.is_empty());
+    assert_eq!(
+        graph_diff::render_text(&unchanged),
+        "* No dependency changes since main\n"
+    );
+}
+
+fn git(root: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+        .args(args)
+        .current_dir(root)
+        .output()
+        .unwrap();
+    assert!(status.status.success(), "{status:?}");
+}
+
+const MAP: &str = "# demo -- Semantic Map
+
+## Layer 1 -- Core
+
+`app/src/main.rs`
+Runs.
+
+## Layer 2 -- Domain
+
+`app/src/a.rs`
+Does a.
+
+`app/src/b.rs`
+Does b.
+
+## Layer 3 -- Utilities
+
+`app/src/util.rs`
+Helps.
+";
+
+/// A repository whose project lives in `app/`, with one commit.
+fn repo(root: &Path) {
+    git(root, &["init", "-q"]);
+    write(root, "app/Cargo.toml", "[dependencies]\nregex = \"1\"\n");
+    write(root, "app/SEMMAP.md", &MAP.replace("app/", ""));
+    write(
+        root,
+        "app/src/main.rs",
+        "use crate::a;\nfn main() { regex::x(); }\n",
+    );
+    write(root, "app/src/a.rs", "use crate::util;\n");
+    write(root, "app/src/b.rs", "");
+    write(root, "app/src/util.rs", "");
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "v1"]);
+}
+
+#[test]
+fn snapshot_exports_a_subdirectory_without_touching_the_worktree() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    repo(root);
+    write(root, "app/src/b.rs", "changed");
+
+    let app = root.join("app");
+    let dir = {
+        let mut snapshot = Snapshot::open(&app, "HEAD").unwrap();
+        let dir = snapshot.dir().to_path_buf();
+        snapshot.write(|path| path == "src/a.rs").unwrap();
+        assert!(dir.join("src/a.rs").is_file());
+        assert!(!dir.join("src/b.rs").exists());
+        snapshot
+            .write(|path| path.starts_with("src/") || graph_diff::is_analyzer_input(path))
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("src/a.rs")).unwrap(),
+            "use crate::util;\n"
+        );
+        assert_eq!(fs::read_to_string(dir.join("src/b.rs")).unwrap(), "");
+        assert!(dir.join("Cargo.toml").is_file());
+        assert!(!dir.join("SEMMAP.md").exists());
+        assert!(!dir.join("app").exists());
+        dir
+    };
+    assert!(!dir.exists());
+    assert_eq!(fs::read_to_string(app.join("src/b.rs")).unwrap(), "changed");
+
+    let err = Snapshot::open(&app, "no-such-branch").err().unwrap();
+    assert_eq!(err, "Unknown git revision: no-such-branch");
+    let err = Snapshot::open(&app, "--output=x").err().unwrap();
+    assert_eq!(err, "Unknown git revision: --output=x");
+}
+
+#[test]
+fn deps_diff_fails_check_on_new_violations_and_cycles() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    repo(root);
+    let app = root.join("app");
+    write(
+        root,
+        "app/Cargo.toml",
+        "[dependencies]\nregex = \"1\"\nsqlx = \"0.7\"\n",
+    );
+    write(
+        root,
+        "app/src/a.rs",
+        "use crate::b;\nfn f() { sqlx::q(); }\n",
+    );
+    write(root, "app/src/b.rs", "use crate::a;\nuse crate::util;\n");
+
+    let options = DepsOptions {
+        format: "json".into(),
+        check: true,
+        group: "layer".into(),
+        diff: Some("HEAD".into()),
+        ..Default::default()
+    };
+    let err = commands::deps(&app.join("SEMMAP.md"), &app, &options).unwrap_err();
+    assert_eq!(
+        err,
+        "1 new layer violations, 1 new dependency cycles since HEAD"
+    );
+
+    let mut snapshot = Snapshot::open(&app, "HEAD").unwrap();
+    snapshot.write(|_| true).unwrap();
+    let old = Analysis::compute(
+        snapshot.dir(),
+        &semmap::parser::parse(&MAP.replace("app/", "")).unwrap(),
+        &AnalyzeOptions::default(),
+        &[],
+    );
+    assert_eq!(old.externals.packages.len(), 1);
+
+    for format in ["text", "mermaid"] {
+        let options = DepsOptions {
+            format: format.into(),
+            ..options.clone()
+        };
+        assert!(commands::deps(&app.join("SEMMAP.md"), &app, &options).is_err());
+    }
+    let options = DepsOptions {
+        format: "dot".into(),
+        check: false,
+        ..options
+    };
+    let err = commands::deps(&app.join("SEMMAP.md"), &app, &options).unwrap_err();
+    assert!(err.starts_with("Unknown diff format: dot"), "{err}");
+}
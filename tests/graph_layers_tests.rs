@@ -0,0 +1,107 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::write;
+use semmap::commands::{self, GenerateOptions};
+use semmap::generator::{self, GeneratorConfig, Layering};
+use semmap::types::SemmapFile;
+use semmap::{deps, parser};
+use std::fs;
+use std::path::Path;
+
+/// main -> app -> {store, helpers}; store <-> index form a cycle.
+fn project(root: &Path) {
+    write(root, "Cargo.toml", "[package]\nname = \"demo\"\n");
+    write(
+        root,
+        "src/main.rs",
+        "mod app;\nmod helpers;\nmod index;\nmod store;\nfn main() { app::run(); }\n",
+    );
+    write(
+        root,
+        "src/app.rs",
+        "use crate::helpers::trim;\nuse crate::store::Store;\npub fn run() {}\n",
+    );
+    write(root, "src/helpers.rs", "pub fn trim() {}\n");
+    write(
+        root,
+        "src/store.rs",
+        "use crate::index::Index;\npub struct Store;\n",
+    );
+    write(
+        root,
+        "src/index.rs",
+        "use crate::store::Store;\npub struct Index;\n",
+    );
+    write(
+        root,
+        "tests/app_test.rs",
+        "use demo::app;\n#[test]\nfn runs() {}\n",
+    );
+}
+
+fn generate(root: &Path, layering: Layering) -> SemmapFile {
+    generator::generate(
+        root,
+        GeneratorConfig {
+            layering,
+            ..Default::default()
+        },
+    )
+}
+
+fn layer(semmap: &SemmapFile, path: &str) -> u8 {
+    semmap.path_to_layer().get(path).copied().unwrap()
+}
+
+#[test]
+fn graph_layering_puts_leaves_low_and_entry_points_high() {
+    let tmp = tempfile::tempdir().unwrap();
+    project(tmp.path());
+
+    let heuristic = generate(tmp.path(), Layering::Heuristic);
+    let depmap = deps::analyze(tmp.path(), &heuristic);
+    assert!(!deps::check_layer_violations(&depmap, &heuristic).is_empty());
+
+    let graph = generate(tmp.path(), Layering::Graph);
+    let depmap = deps::analyze(tmp.path(), &graph);
+    assert!(deps::check_layer_violations(&depmap, &graph).is_empty());
+    assert_eq!(layer(&graph, "src/helpers.rs"), 1);
+    assert_eq!(layer(&graph, "src/main.rs"), 3);
+    assert!(layer(&graph, "src/app.rs") >= layer(&graph, "src/store.rs"));
+    // The cycle shares a layer; config and tests keep theirs
+    assert_eq!(layer(&graph, "src/store.rs"), layer(&graph, "src/index.rs"));
+    assert_eq!(layer(&graph, "Cargo.toml"), 0);
+    assert_eq!(layer(&graph, "tests/app_test.rs"), 4);
+
+    let names: Vec<_> = graph.layers.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["Config", "Core", "Domain", "Application", "Tests"]
+    );
+}
+
+#[test]
+fn generate_command_takes_a_layering() {
+    let tmp = tempfile::tempdir().unwrap();
+    project(tmp.path());
+    let out = tmp.path().join("SEMMAP.md");
+    let options = GenerateOptions {
+        layering: "graph".into(),
+        ..Default::default()
+    };
+    commands::generate(tmp.path(), &out, &options).unwrap();
+    let semmap = parser::parse(&fs::read_to_string(&out).unwrap()).unwrap();
+    assert_eq!(layer(&semmap, "src/main.rs"), 3);
+
+    let options = GenerateOptions {
+        layering: "random".into(),
+        ..Default::default()
+    };
+    let err = commands::generate(tmp.path(), &out, &options).unwrap_err();
+    assert_eq!(
+        err,
+        "Unknown layering: random (expected heuristic or graph)"
+    );
+}
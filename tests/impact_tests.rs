@@ -0,0 +1,111 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::impact;
+use semmap::parser;
+use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap};
+
+fn node(path: &str, layer: u8) -> DepNode {
+    DepNode {
+        path: path.into(),
+        layer,
+    }
+}
+
+fn edge(from: &str, to: &str, kind: DepKind) -> DepEdge {
+    DepEdge {
+        from: from.into(),
+        to: to.into(),
+        kind,
+        symbols: vec![],
+    }
+}
+
+/// main -> cli -> parser -> types, main -> types, tests/parser -> parser
+fn depmap() -> DependencyMap {
+    DependencyMap {
+        nodes: vec![
+            node("src/main.rs", 1),
+            node("src/cli.rs", 2),
+            node("src/parser.rs", 2),
+            node("src/types.rs", 3),
+            node("src/unrelated.rs", 3),
+            node("tests/parser_tests.rs", 4),
+            node("tests/cli_tests.rs", 4),
+        ],
+        edges: vec![
+            edge("src/main.rs", "src/cli.rs", DepKind::Import),
+            edge("src/main.rs", "src/types.rs", DepKind::Import),
+            edge("src/cli.rs", "src/parser.rs", DepKind::Import),
+            edge("src/cli.rs", "src/parser.rs", DepKind::Call),
+            edge("src/parser.rs", "src/types.rs", DepKind::TypeImport),
+            edge("tests/parser_tests.rs", "src/parser.rs", DepKind::Import),
+            edge("src/unrelated.rs", "src/main.rs", DepKind::Call),
+        ],
+    }
+}
+
+#[test]
+fn transitive_dependents_get_shortest_chains() {
+    let result = impact::analyze(&depmap(), &["src/types.rs".into()]).unwrap();
+    let paths: Vec<_> = result.dependents.iter().map(|d| d.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "src/main.rs",
+            "src/parser.rs",
+            "src/cli.rs",
+            "src/unrelated.rs",
+            "tests/parser_tests.rs",
+        ]
+    );
+    assert_eq!(result.direct_count(), 2);
+
+    let main = result.dependents.first().unwrap();
+    assert_eq!(main.chain, vec!["src/main.rs", "src/types.rs"]);
+    let cli = result.dependents.get(2).unwrap();
+    assert_eq!(cli.distance, 2);
+    assert_eq!(
+        cli.chain,
+        vec!["src/cli.rs", "src/parser.rs", "src/types.rs"]
+    );
+    let unrelated = result.dependents.get(3).unwrap();
+    assert_eq!(unrelated.distance, 2);
+    assert_eq!(unrelated.chain.get(1).unwrap(), "src/main.rs");
+}
+
+#[test]
+fn affected_tests_include_targets_in_the_test_layer() {
+    let result = impact::analyze(&depmap(), &["src/cli.rs".into()]).unwrap();
+    assert!(result.tests.is_empty());
+
+    let targets = vec!["./src/parser.rs".into(), "tests/cli_tests.rs".into()];
+    let result = impact::analyze(&depmap(), &targets).unwrap();
+    assert_eq!(result.targets, vec!["src/parser.rs", "tests/cli_tests.rs"]);
+    assert_eq!(
+        result.tests,
+        vec!["tests/cli_tests.rs", "tests/parser_tests.rs"]
+    );
+    assert!(result.dependents.iter().all(|d| d.path != "src/parser.rs"));
+}
+
+#[test]
+fn unknown_targets_are_rejected() {
+    let err = impact::analyze(&depmap(), &["src/missing.rs".into()]).unwrap_err();
+    assert_eq!(err, "Not in SEMMAP: src/missing.rs");
+}
+
+#[test]
+fn text_output_groups_dependents_by_layer() {
+    let semmap = parser::parse(
+        "# demo -- Semantic Map\n\n## Layer 1 -- Core\n\n`src/main.rs`\nRuns.\n\n\
+         ## Layer 2 -- Domain\n\n`src/cli.rs`\nParses args.\n",
+    )
+    .unwrap();
+    let result = impact::analyze(&depmap(), &["src/parser.rs".into()]).unwrap();
+    let text = impact::render_text(&result, &semmap);
+    assert!(text.starts_with("Impact of src/parser.rs: 4 dependents (2 direct) in 4 layers\n"));
+    assert!(text.contains("## Layer 1 -- Core\n  src/main.rs (transitive)\n"));
+    assert!(text.contains("    src/main.rs -> src/cli.rs -> src/parser.rs\n"));
+    assert!(text.contains("## Layer 2 -- Domain\n  src/cli.rs (direct)\n"));
+    assert!(text.contains("## Layer 4\n  tests/parser_tests.rs (direct)\n"));
+}
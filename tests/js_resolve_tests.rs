@@ -0,0 +1,128 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::{semmap_for, write};
+use semmap::deps;
+use semmap::js_resolve::JsResolver;
+use semmap::lang_js;
+use semmap::types::DepKind;
+
+#[test]
+fn resolves_modern_extensions_and_index_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/button.tsx", "");
+    write(root, "src/util/index.mjs", "");
+    write(root, "src/esm.ts", "");
+
+    let resolver = JsResolver::load(root);
+    assert_eq!(
+        resolver.resolve("src/app.ts", "./button"),
+        Some("src/button.tsx".into())
+    );
+    assert_eq!(
+        resolver.resolve("src/app.ts", "./util"),
+        Some("src/util/index.mjs".into())
+    );
+    assert_eq!(
+        resolver.resolve("src/app.ts", "./esm.js"),
+        Some("src/esm.ts".into())
+    );
+    assert_eq!(resolver.resolve("src/app.ts", "react"), None);
+}
+
+#[test]
+fn resolves_package_json_entry() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "packages/core/package.json",
+        r#"{ "exports": { ".": { "import": "./lib/main.js" } } }"#,
+    );
+    write(root, "packages/core/lib/main.js", "");
+
+    let resolver = JsResolver::load(root);
+    assert_eq!(
+        resolver.resolve("src/app.ts", "../packages/core"),
+        Some("packages/core/lib/main.js".into())
+    );
+}
+
+#[test]
+fn resolves_tsconfig_paths_through_extends() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "config/tsconfig.base.json",
+        r#"{
+            // shared settings
+            "compilerOptions": {
+                "baseUrl": "..",
+                "paths": { "@app/*": ["src/app/*"], },
+            },
+        }"#,
+    );
+    write(
+        root,
+        "tsconfig.json",
+        r#"{ "extends": "./config/tsconfig.base" }"#,
+    );
+    write(root, "src/app/store.ts", "");
+    write(root, "src/shared/log.ts", "");
+
+    let resolver = JsResolver::load(root);
+    assert_eq!(
+        resolver.resolve("src/main.ts", "@app/store"),
+        Some("src/app/store.ts".into())
+    );
+    assert_eq!(
+        resolver.resolve("src/main.ts", "src/shared/log"),
+        Some("src/shared/log.ts".into())
+    );
+}
+
+#[test]
+fn extracts_reexports_dynamic_and_type_imports() {
+    let content = r"
+import type { Props } from './props';
+import { type A, type B } from './types';
+import {
+    render,
+} from './render';
+export * from './public';
+export { helper } from './helper';
+const lazy = await import('./lazy');
+";
+    let specs = lang_js::extract_specifiers(content);
+    let kind_of = |s: &str| specs.iter().find(|(p, _)| p == s).map(|(_, k)| *k);
+
+    assert_eq!(kind_of("./props"), Some(DepKind::TypeImport));
+    assert_eq!(kind_of("./types"), Some(DepKind::TypeImport));
+    assert_eq!(kind_of("./render"), Some(DepKind::Import));
+    assert_eq!(kind_of("./public"), Some(DepKind::Import));
+    assert_eq!(kind_of("./helper"), Some(DepKind::Import));
+    assert_eq!(kind_of("./lazy"), Some(DepKind::Import));
+}
+
+#[test]
+fn analyze_resolves_against_project_root() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "web/main.ts",
+        "import { a } from './a';\nimport './a';",
+    );
+    write(root, "web/a.tsx", "");
+
+    let depmap = deps::analyze(root, &semmap_for(&["web/main.ts", "web/a.tsx"]));
+
+    assert_eq!(depmap.edges.len(), 1, "duplicate imports should collapse");
+    assert!(depmap
+        .edges
+        .iter()
+        .any(|e| e.from == "web/main.ts" && e.to == "web/a.tsx"));
+}
@@ -0,0 +1,150 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::{semmap_for, write};
+use semmap::deps;
+use semmap::generator::{self, GeneratorConfig};
+use semmap::inference;
+use semmap::lang_jvm;
+use semmap::stereotype::{self, Stereotype};
+use std::path::Path;
+
+#[test]
+fn java_exports_public_types_and_methods() {
+    let content = r"
+package com.acme;
+
+public final class Orders {
+    public static List<Order> findAll(int limit) { return null; }
+    public Orders() {}
+    private void hidden() {}
+}
+public record Money(long cents) {}
+";
+    let exports = lang_jvm::java_exports(content);
+    assert_eq!(exports, vec!["Orders", "findAll", "Money"]);
+}
+
+#[test]
+fn kotlin_exports_skip_private_and_members() {
+    let content = r"
+package com.acme
+
+data class User(val id: Long)
+private fun secret() = 1
+internal object Registry
+fun String.slugify(): String = this
+object Ids {
+    fun next() = 1
+}
+";
+    let exports = lang_jvm::kotlin_exports(content);
+    assert_eq!(exports, vec!["User", "slugify", "Ids"]);
+}
+
+#[test]
+fn spring_annotations_drive_stereotypes() {
+    let controller = "@RestController\npublic class OrderController {}";
+    let service = "@Service\npublic class OrderService {}";
+    let repo = "@Repository\npublic interface OrderRepo {}";
+    let junit = "import org.junit.jupiter.api.Test;\nclass OrderChecks {}";
+
+    let classify = |c| stereotype::classify("src/main/java/com/acme/X.java", c);
+    assert_eq!(classify(controller), Stereotype::Handler);
+    assert_eq!(classify(service), Stereotype::Service);
+    assert_eq!(classify(repo), Stereotype::Repository);
+    assert_eq!(classify(junit), Stereotype::Test);
+}
+
+#[test]
+fn maven_layout_maps_to_layers() {
+    assert_eq!(lang_jvm::source_set_layer("pom.xml"), Some(0));
+    assert_eq!(lang_jvm::source_set_layer("app/build.gradle.kts"), Some(0));
+    assert_eq!(
+        lang_jvm::source_set_layer("src/main/resources/application.yml"),
+        Some(0)
+    );
+    assert_eq!(
+        lang_jvm::source_set_layer("core/src/test/kotlin/a/BTest.kt"),
+        Some(4)
+    );
+    assert_eq!(lang_jvm::source_set_layer("src/main/java/a/B.java"), None);
+}
+
+#[test]
+fn generate_picks_up_javadoc_and_build_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "pom.xml", "<project/>");
+    write(
+        root,
+        "src/main/java/com/acme/Billing.java",
+        "package com.acme;\n\n/** Computes invoices for customers. */\npublic class Billing {}",
+    );
+
+    let semmap = generator::generate(root, GeneratorConfig::default());
+    let pom_layer = semmap
+        .layers
+        .iter()
+        .find(|l| l.entries.iter().any(|e| e.path == "pom.xml"))
+        .unwrap();
+    assert_eq!(pom_layer.number, 0);
+
+    let billing = semmap
+        .find_entry("src/main/java/com/acme/Billing.java")
+        .unwrap();
+    assert_eq!(billing.description.what, "Computes invoices for customers.");
+    assert_eq!(billing.exports, Some(vec!["Billing".to_string()]));
+}
+
+#[test]
+fn gradle_build_and_settings_files_are_described() {
+    let what = |path: &str| inference::infer_what(path, Path::new(path), "");
+    assert_eq!(
+        what("build.gradle"),
+        "Gradle build script and dependencies."
+    );
+    assert_eq!(
+        what("app/build.gradle.kts"),
+        "Gradle build script and dependencies."
+    );
+    assert_eq!(
+        what("settings.gradle"),
+        "Gradle settings and included projects."
+    );
+    assert_eq!(
+        what("settings.gradle.kts"),
+        "Gradle settings and included projects."
+    );
+}
+
+#[test]
+fn analyze_resolves_package_imports() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let app = "src/main/java/com/acme/App.java";
+    let model = "src/main/java/com/acme/model/Order.java";
+    let util = "src/main/kotlin/com/acme/util/Strings.kt";
+    write(
+        root,
+        app,
+        "package com.acme;\nimport com.acme.model.*;\nimport static com.acme.util.slugify;\n",
+    );
+    write(
+        root,
+        model,
+        "package com.acme.model;\npublic class Order {}",
+    );
+    write(
+        root,
+        util,
+        "package com.acme.util\n\nfun slugify(s: String) = s\n",
+    );
+
+    let semmap = semmap_for(&[app, model, util]);
+
+    let depmap = deps::analyze(root, &semmap);
+    let targets: Vec<&str> = depmap.edges.iter().map(|e| e.to.as_str()).collect();
+    assert_eq!(targets, vec![model, util]);
+}
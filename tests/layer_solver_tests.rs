@@ -0,0 +1,192 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::layer_check::check_layer_violations;
+use semmap::layer_solver::{self, LayerMove};
+use semmap::types::{AllowedDep, DepEdge, DepKind, DependencyMap, FileEntry, Layer, SemmapFile};
+use std::collections::HashSet;
+
+fn semmap(files: &[(&str, u8)]) -> SemmapFile {
+    let mut semmap = SemmapFile::new("demo".into(), String::new());
+    for number in 0..=4 {
+        let mut layer = Layer::new(number, format!("L{number}"));
+        for (path, _) in files.iter().filter(|(_, l)| *l == number) {
+            layer.entries.push(FileEntry::new(
+                (*path).into(),
+                "Does.".into(),
+                String::new(),
+            ));
+        }
+        semmap.layers.push(layer);
+    }
+    semmap
+}
+
+fn depmap(edges: &[(&str, &str)]) -> DependencyMap {
+    DependencyMap {
+        nodes: vec![],
+        edges: edges
+            .iter()
+            .map(|(from, to)| DepEdge {
+                from: (*from).into(),
+                to: (*to).into(),
+                kind: DepKind::Import,
+                symbols: vec![],
+            })
+            .collect(),
+    }
+}
+
+fn solve(depmap: &DependencyMap, semmap: &SemmapFile) -> Vec<LayerMove> {
+    layer_solver::solve(depmap, semmap, &HashSet::new(), &[]).unwrap()
+}
+
+#[test]
+fn moves_the_one_file_behind_several_violations() {
+    let mut map = semmap(&[
+        ("a.rs", 1),
+        ("x.rs", 3),
+        ("y.rs", 3),
+        ("z.rs", 3),
+        ("b.rs", 2),
+    ]);
+    let deps = depmap(&[
+        ("a.rs", "x.rs"),
+        ("a.rs", "y.rs"),
+        ("a.rs", "z.rs"),
+        ("b.rs", "a.rs"),
+    ]);
+    assert_eq!(check_layer_violations(&deps, &map).len(), 3);
+
+    let moves = solve(&deps, &map);
+    assert_eq!(moves.len(), 2, "{moves:?}");
+    let a = moves.iter().find(|m| m.path == "a.rs").unwrap();
+    assert_eq!((a.from, a.to), (1, 3));
+    // b.rs now depends on a layer 3 file, so it follows
+    assert!(moves.iter().any(|m| m.path == "b.rs" && m.to == 3));
+
+    layer_solver::apply(&mut map, &moves);
+    assert!(check_layer_violations(&deps, &map).is_empty());
+    let layer3 = map.layers.iter().find(|l| l.number == 3).unwrap();
+    let paths: Vec<_> = layer3.entries.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, vec!["a.rs", "b.rs", "x.rs", "y.rs", "z.rs"]);
+
+    let text = layer_solver::render_text(&moves, 3, &map);
+    assert!(text.starts_with("3 layer violations; moving 2 files fixes them all:\n"));
+    assert!(text.contains("  a.rs: Layer 1 (L1) -> Layer 3 (L3)\n"));
+}
+
+#[test]
+fn apply_adds_a_missing_target_layer() {
+    let mut map = semmap(&[("a.rs", 1), ("b.rs", 2)]);
+    map.layers.retain(|l| l.number != 3);
+    let moves = vec![LayerMove {
+        path: "a.rs".into(),
+        from: 1,
+        to: 3,
+    }];
+    layer_solver::apply(&mut map, &moves);
+    let numbers: Vec<u8> = map.layers.iter().map(|l| l.number).collect();
+    assert_eq!(numbers, vec![0, 1, 2, 3, 4]);
+    let layer3 = map.layers.iter().find(|l| l.number == 3).unwrap();
+    assert_eq!(layer3.entries.first().unwrap().path, "a.rs");
+    assert!(map.find_entry("a.rs").is_some());
+}
+
+#[test]
+fn cycles_across_layers_share_one_layer() {
+    let mut map = semmap(&[("p.rs", 1), ("q.rs", 2), ("r.rs", 2)]);
+    let deps = depmap(&[("p.rs", "q.rs"), ("q.rs", "r.rs"), ("r.rs", "p.rs")]);
+    let moves = solve(&deps, &map);
+    assert_eq!(
+        moves,
+        vec![LayerMove {
+            path: "p.rs".into(),
+            from: 1,
+            to: 2
+        }]
+    );
+    layer_solver::apply(&mut map, &moves);
+    assert!(check_layer_violations(&deps, &map).is_empty());
+}
+
+#[test]
+fn allowed_dependencies_need_no_move() {
+    let mut map = semmap(&[("a.rs", 1), ("x.rs", 3)]);
+    let deps = depmap(&[("a.rs", "x.rs")]);
+    assert_eq!(solve(&deps, &map).len(), 1);
+    let entry = map
+        .layers
+        .iter_mut()
+        .flat_map(|l| &mut l.entries)
+        .next()
+        .unwrap();
+    entry.allows.push(AllowedDep {
+        target: "x.rs".into(),
+        reason: "shared helper".into(),
+    });
+    assert!(solve(&deps, &map).is_empty());
+}
+
+#[test]
+fn pinned_files_in_a_cycle_have_no_solution() {
+    let map = semmap(&[("p.rs", 1), ("q.rs", 2), ("r.rs", 3)]);
+    let deps = depmap(&[("p.rs", "q.rs"), ("q.rs", "r.rs"), ("r.rs", "p.rs")]);
+    let pins = vec!["p.rs".to_string(), "r.rs".to_string()];
+    let err = layer_solver::solve(&deps, &map, &HashSet::new(), &pins).unwrap_err();
+    assert_eq!(err.chain, vec!["p.rs", "q.rs", "r.rs"]);
+    assert!(err.cycle);
+    assert!(err.to_string().contains("they are in a dependency cycle"));
+
+    // One pin leaves the others free to join it
+    let pins = vec!["r.rs".to_string()];
+    let moves = layer_solver::solve(&deps, &map, &HashSet::new(), &pins).unwrap();
+    assert!(moves.iter().all(|m| m.to == 3 && m.path != "r.rs"));
+    assert_eq!(moves.len(), 2);
+}
+
+/// Every assignment of layers 1..=3 to `files`, checked against the solver.
+#[test]
+fn solutions_are_minimal() {
+    let files = ["a", "b", "c", "d", "e", "f"];
+    let layers = [3u8, 1, 2, 1, 3, 2];
+    let edges = [
+        ("a", "b"),
+        ("b", "c"),
+        ("b", "e"),
+        ("d", "a"),
+        ("d", "f"),
+        ("f", "e"),
+        ("c", "d"),
+    ];
+    let labelled: Vec<(&str, u8)> = files.iter().copied().zip(layers).collect();
+    let map = semmap(&labelled);
+    let deps = depmap(&edges);
+
+    let mut best = usize::MAX;
+    for code in 0..3usize.pow(6) {
+        let candidate: Vec<u8> = (0..6)
+            .map(|i| u8::try_from(code / 3usize.pow(i) % 3).unwrap() + 1)
+            .collect();
+        let layer_of = |f: &str| {
+            let i = files.iter().position(|x| *x == f).unwrap();
+            *candidate.get(i).unwrap()
+        };
+        if edges
+            .iter()
+            .all(|(from, to)| layer_of(to) <= layer_of(from))
+        {
+            let changed = candidate
+                .iter()
+                .zip(layers)
+                .filter(|(c, l)| **c != *l)
+                .count();
+            best = best.min(changed);
+        }
+    }
+
+    let mut solved = map.clone();
+    let moves = solve(&deps, &map);
+    assert_eq!(moves.len(), best, "{moves:?}");
+    layer_solver::apply(&mut solved, &moves);
+    assert!(check_layer_violations(&deps, &solved).is_empty());
+}
@@ -0,0 +1,145 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::metrics::{self, FileMetrics, Thresholds};
+use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap};
+use std::fs;
+
+fn edge(from: &str, to: &str) -> DepEdge {
+    DepEdge {
+        from: from.into(),
+        to: to.into(),
+        kind: DepKind::Import,
+        symbols: vec![],
+    }
+}
+
+/// `core.rs` is imported by five files; `app.rs` imports all of them.
+fn star() -> DependencyMap {
+    let users = ["a", "b", "c", "d", "e"].map(|n| format!("src/{n}.rs"));
+    let mut nodes = vec![
+        DepNode {
+            path: "src/core.rs".into(),
+            layer: 2,
+        },
+        DepNode {
+            path: "src/app.rs".into(),
+            layer: 1,
+        },
+    ];
+    let mut edges = Vec::new();
+    for user in &users {
+        nodes.push(DepNode {
+            path: user.clone(),
+            layer: 2,
+        });
+        edges.push(edge(user, "src/core.rs"));
+        edges.push(edge("src/app.rs", user));
+    }
+    DependencyMap { nodes, edges }
+}
+
+fn find<'a>(all: &'a [FileMetrics], path: &str) -> &'a FileMetrics {
+    all.iter().find(|m| m.path == path).unwrap()
+}
+
+#[test]
+fn coupling_and_instability() {
+    let tmp = tempfile::tempdir().unwrap();
+    let all = metrics::compute(tmp.path(), &star(), &Thresholds::default());
+
+    let core = find(&all, "src/core.rs");
+    assert_eq!((core.afferent, core.efferent, core.cbo), (5, 0, 5));
+    assert!(core.instability.abs() < f64::EPSILON);
+    assert!(core.hub);
+
+    let user = find(&all, "src/a.rs");
+    assert_eq!((user.afferent, user.efferent), (1, 1));
+    assert!((user.instability - 0.5).abs() < f64::EPSILON);
+    assert!(!user.hub);
+
+    let app = find(&all, "src/app.rs");
+    assert!((app.instability - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn abstractness_and_distance_from_sources() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::create_dir(tmp.path().join("src")).unwrap();
+    fs::write(
+        tmp.path().join("src/core.rs"),
+        "pub trait Store {}\npub trait Cache {}\npub struct Memory;\npub enum Mode {}\n",
+    )
+    .unwrap();
+
+    let all = metrics::compute(tmp.path(), &star(), &Thresholds::default());
+    let core = find(&all, "src/core.rs");
+    assert!((core.abstractness - 0.5).abs() < f64::EPSILON);
+    assert!((core.distance - 0.5).abs() < f64::EPSILON);
+}
+
+#[test]
+fn thresholds_flag_god_modules_and_coupling() {
+    let tmp = tempfile::tempdir().unwrap();
+    let thresholds = Thresholds {
+        max_cbo: 4,
+        god_module_threshold: 6,
+        min_hub_afferent: 10,
+        ..Thresholds::default()
+    };
+    let all = metrics::compute(tmp.path(), &star(), &thresholds);
+    assert!(!find(&all, "src/core.rs").hub);
+
+    let issues = metrics::issues(&all, &thresholds);
+    assert_eq!(
+        issues,
+        vec![
+            "High coupling: src/core.rs (CBO 5 > 4)",
+            "High coupling: src/app.rs (CBO 5 > 4)"
+        ]
+    );
+
+    let strict = Thresholds {
+        god_module_threshold: 5,
+        ..thresholds
+    };
+    let all = metrics::compute(tmp.path(), &star(), &strict);
+    assert_eq!(
+        metrics::issues(&all, &strict),
+        vec![
+            "God module: src/core.rs (Ca 5 + Ce 0 >= 5)",
+            "God module: src/app.rs (Ca 0 + Ce 5 >= 5)"
+        ]
+    );
+}
+
+#[test]
+fn thresholds_load_from_slopchop_toml() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert_eq!(Thresholds::load(tmp.path()), Thresholds::default());
+
+    fs::write(
+        tmp.path().join("slopchop.toml"),
+        "[rules]\nmax_cbo = 3\n\n[rules.locality]\nhub_threshold = 2.5\nmin_hub_afferent = 7\ngod_module_threshold = 12\n",
+    )
+    .unwrap();
+    let loaded = Thresholds::load(tmp.path());
+    assert_eq!(loaded.max_cbo, 3);
+    assert!((loaded.hub_threshold - 2.5).abs() < f64::EPSILON);
+    assert_eq!(loaded.min_hub_afferent, 7);
+    assert_eq!(loaded.god_module_threshold, 12);
+}
+
+#[test]
+fn table_lists_every_file_with_flags() {
+    let tmp = tempfile::tempdir().unwrap();
+    let all = metrics::compute(tmp.path(), &star(), &Thresholds::default());
+    let table = metrics::render_table(&all);
+
+    assert!(table.starts_with("Path"));
+    assert_eq!(table.lines().count(), 8);
+    let core_row = table
+        .lines()
+        .find(|l| l.starts_with("src/core.rs"))
+        .unwrap();
+    assert!(core_row.ends_with("hub"));
+}
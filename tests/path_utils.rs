@@ -1,4 +1,7 @@
-use semmap::path_utils::{build_root_prefix, prefix_path, strip_prefix_for_lookup};
+use semmap::path_utils::{
+    build_root_prefix, glob_match, join_normalized, normalize_path, prefix_path,
+    strip_prefix_for_lookup,
+};
 
 /// Current directory should produce empty prefix.
 #[test]
@@ -56,3 +59,32 @@ fn test_strip_prefix_no_match() {
         "other/foo.rs"
     );
 }
+
+/// Dot segments should be resolved lexically.
+#[test]
+fn test_normalize_path_dot_segments() {
+    assert_eq!(normalize_path("src/./a/../b.ts"), Some("src/b.ts".into()));
+    assert_eq!(
+        join_normalized("src/app", "../lib/x"),
+        Some("src/lib/x".into())
+    );
+    assert_eq!(normalize_path("../outside"), None);
+}
+
+/// `**` crosses directories, `*` and `?` stay inside one segment.
+#[test]
+fn test_glob_match() {
+    assert!(glob_match("src/db/**", "src/db/pool.rs"));
+    assert!(glob_match("src/db/**", "src/db/pg/conn.rs"));
+    assert!(!glob_match("src/db/**", "src/dbx/pool.rs"));
+    assert!(glob_match("src/**/mod.rs", "src/mod.rs"));
+    assert!(glob_match("src/**/mod.rs", "src/a/b/mod.rs"));
+    assert!(glob_match("src/*.rs", "src/main.rs"));
+    assert!(!glob_match("src/*.rs", "src/cli/main.rs"));
+    assert!(glob_match("tests/**", "tests/a.rs"));
+    assert!(glob_match("**", "anything/at/all"));
+    assert!(glob_match("src/?.rs", "src/a.rs"));
+    assert!(!glob_match("src/?.rs", "src/ab.rs"));
+    assert!(glob_match("src/main.rs", "src/main.rs"));
+    assert!(!glob_match("src/main.rs", "src/main.rsx"));
+}
@@ -0,0 +1,112 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::write;
+use semmap::lang_python::{self, PyImport};
+use semmap::python_resolve::PythonResolver;
+use semmap::python_stdlib::is_stdlib;
+use std::path::Path;
+
+fn targets(root: &Path, source: &str, content: &str) -> Vec<String> {
+    let resolver = PythonResolver::load(root);
+    let mut found: Vec<String> = lang_python::extract_imports(content, source, &resolver)
+        .into_iter()
+        .map(|(t, _)| t)
+        .collect();
+    found.sort();
+    found
+}
+
+#[test]
+fn parses_multi_import_and_parenthesised_from() {
+    let content =
+        "import os, pkg.util as u\nfrom ..core import (\n    a,  # first\n    b as c,\n)\n";
+    let imports = lang_python::parse_imports(content);
+    assert_eq!(
+        imports,
+        vec![
+            PyImport::Module("os".into()),
+            PyImport::Module("pkg.util".into()),
+            PyImport::From {
+                level: 2,
+                module: "core".into(),
+                names: vec!["a".into(), "b".into()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn resolves_relative_imports_inside_packages() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "app/__init__.py", "");
+    write(root, "app/models.py", "");
+    write(root, "app/api/__init__.py", "");
+    write(root, "app/api/views.py", "");
+    write(root, "app/api/forms.py", "");
+
+    let found = targets(
+        root,
+        "app/api/views.py",
+        "from . import forms\nfrom ..models import User\nfrom .. import api\n",
+    );
+    assert_eq!(
+        found,
+        vec!["app/api/__init__.py", "app/api/forms.py", "app/models.py"]
+    );
+}
+
+#[test]
+fn resolves_dotted_absolute_imports_in_src_layout() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/shop/__init__.py", "");
+    write(root, "src/shop/billing/__init__.py", "");
+    write(root, "src/shop/billing/invoice.py", "");
+    write(root, "src/shop/cli.py", "");
+
+    let found = targets(
+        root,
+        "src/shop/cli.py",
+        "import shop.billing.invoice\nfrom shop.billing import Invoice\nimport json\n",
+    );
+    assert_eq!(
+        found,
+        vec![
+            "src/shop/billing/__init__.py",
+            "src/shop/billing/invoice.py"
+        ]
+    );
+}
+
+#[test]
+fn discovers_roots_from_pyproject() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "pyproject.toml",
+        "[tool.setuptools.packages.find]\nwhere = [\"lib\"]\n",
+    );
+    write(root, "lib/engine/__init__.py", "");
+    write(root, "scripts/run.py", "");
+
+    let found = targets(root, "scripts/run.py", "from engine import start\n");
+    assert_eq!(found, vec!["lib/engine/__init__.py"]);
+}
+
+#[test]
+fn stdlib_list_is_complete() {
+    for module in [
+        "asyncio",
+        "dataclasses",
+        "xml.etree",
+        "tomllib",
+        "__future__",
+    ] {
+        assert!(is_stdlib(module), "{module} should be stdlib");
+    }
+    assert!(!is_stdlib("requests"));
+}
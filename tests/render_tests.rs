@@ -0,0 +1,93 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::render::{self, Highlighted, RenderOptions};
+use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap};
+
+fn graph() -> DependencyMap {
+    DependencyMap {
+        nodes: vec![
+            DepNode {
+                path: "src/app.rs".into(),
+                layer: 1,
+            },
+            DepNode {
+                path: "src/store.rs".into(),
+                layer: 2,
+            },
+        ],
+        edges: vec![
+            DepEdge {
+                from: "src/app.rs".into(),
+                to: "src/store.rs".into(),
+                kind: DepKind::Call,
+                symbols: vec!["load".into(), "save".into()],
+            },
+            DepEdge {
+                from: "src/store.rs".into(),
+                to: "src/app.rs".into(),
+                kind: DepKind::TypeImport,
+                symbols: vec![],
+            },
+        ],
+    }
+}
+
+fn highlighted() -> Highlighted {
+    [("src/app.rs".to_string(), "src/store.rs".to_string())].into()
+}
+
+#[test]
+fn dot_keeps_layers_and_kinds() {
+    let dot = render::render_dot(&graph(), &highlighted());
+    assert!(dot.contains("\"src/app.rs\" [label=\"app.rs\", layer=1];"));
+    assert!(dot.contains(
+        "\"src/app.rs\" -> \"src/store.rs\" [kind=\"call\", style=bold, label=\"load, save\", color=\"#dd3333\"];"
+    ));
+    assert!(dot.contains("[kind=\"type-import\", arrowhead=odot];"));
+}
+
+#[test]
+fn plantuml_uses_layer_stereotypes_and_styled_arrows() {
+    let puml = render::render_plantuml(&graph(), &highlighted());
+    assert!(puml.starts_with("@startuml\n"));
+    assert!(puml.contains("component \"store.rs\" as src_store_rs <<L2>>"));
+    assert!(puml.contains("src_app_rs -[#dd3333,bold]-> src_store_rs : call (load, save)"));
+    assert!(puml.contains("src_store_rs -[dotted]-> src_app_rs : type-import"));
+    assert!(puml.ends_with("@enduml\n"));
+}
+
+#[test]
+fn d2_assigns_layer_and_kind_classes() {
+    let d2 = render::render_d2(&graph(), &Highlighted::new());
+    assert!(d2.contains("  layer-1: {style.fill: \"#eaf6ee\"}"));
+    assert!(d2.contains("  call: {style.stroke-width: 3}"));
+    assert!(d2.contains("\"src/app.rs\": \"app.rs\" {class: layer-1}"));
+    assert!(d2.contains("\"src/app.rs\" -> \"src/store.rs\": \"load, save\" {class: call}"));
+    assert!(d2.contains("\"src/store.rs\" -> \"src/app.rs\" {class: type-import}"));
+}
+
+#[test]
+fn graphml_has_layer_and_kind_data() {
+    let xml = render::render_graphml(&graph(), &highlighted());
+    assert!(xml.contains("<key id=\"layer\" for=\"node\" attr.name=\"layer\" attr.type=\"int\"/>"));
+    assert!(xml.contains(
+        "<node id=\"src/store.rs\"><data key=\"label\">store.rs</data><data key=\"layer\">2</data></node>"
+    ));
+    assert!(xml.contains(
+        "<edge id=\"e0\" source=\"src/app.rs\" target=\"src/store.rs\"><data key=\"kind\">call</data><data key=\"symbols\">load, save</data><data key=\"highlighted\">true</data></edge>"
+    ));
+    assert_eq!(xml.matches("<edge ").count(), 2);
+}
+
+#[test]
+fn render_dispatches_by_format_name() {
+    let depmap = graph();
+    let none = RenderOptions::default();
+    for format in render::FORMATS {
+        assert!(render::render(format, &depmap, &none).is_some(), "{format}");
+    }
+    assert!(render::render("", &depmap, &none)
+        .unwrap()
+        .starts_with("graph TD"));
+    assert!(render::render("svg", &depmap, &none).is_none());
+}
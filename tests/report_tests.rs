@@ -0,0 +1,99 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::commands::{self, ReportOptions};
+use semmap::error::ValidationIssue;
+use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap};
+use semmap::validator::ValidationResult;
+use semmap::{deps, formatter, parser, report};
+use std::fs;
+
+const MAP: &str = "# Shop <beta> -- Semantic Map
+
+**Purpose:** Sells things.
+
+## Layer 1 -- Core
+
+`src/app.rs` `[ENTRY]` `[CORE]`
+Runs the shop. Wires everything.
+→ Exports: run
+
+## Layer 3 -- Utilities
+
+`src/util.rs`
+Helpers.
+";
+
+fn depmap() -> DependencyMap {
+    DependencyMap {
+        nodes: vec![
+            DepNode {
+                path: "src/app.rs".into(),
+                layer: 1,
+            },
+            DepNode {
+                path: "src/util.rs".into(),
+                layer: 3,
+            },
+        ],
+        edges: vec![DepEdge {
+            from: "src/app.rs".into(),
+            to: "src/util.rs".into(),
+            kind: DepKind::Call,
+            symbols: vec!["slug".into()],
+        }],
+    }
+}
+
+#[test]
+fn entry_tags_are_parsed_and_written_back() {
+    let semmap = parser::parse(MAP).unwrap();
+    let entry = semmap.find_entry("src/app.rs").unwrap();
+    assert_eq!(entry.tags, vec!["ENTRY", "CORE"]);
+    assert!(formatter::to_markdown(&semmap).contains("`src/app.rs` `[ENTRY]` `[CORE]`\n"));
+}
+
+#[test]
+fn html_combines_map_graph_and_issues() {
+    let semmap = parser::parse(MAP).unwrap();
+    let depmap = depmap();
+    let violations = deps::check_layer_violations(&depmap, &semmap);
+    let validation = ValidationResult {
+        issues: vec![ValidationIssue::warning("Add //! doc comment").for_path("src/util.rs")],
+    };
+    let html = report::render_html(&semmap, &depmap, &validation, &violations);
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h1>Shop &lt;beta&gt;</h1>"));
+    assert!(html.contains("<span class=\"tag\">ENTRY</span>"));
+    assert!(html.contains("<div class=\"meta\">Exports: run</div>"));
+    assert!(html.contains("<g class=\"node layer-3\" data-path=\"src/util.rs\""));
+    assert!(html.contains(
+        "class=\"edge kind-call violation\" data-from=\"src/app.rs\" data-to=\"src/util.rs\""
+    ));
+    assert!(html.contains("<td class=\"warning\">warning</td><td><code>src/util.rs</code></td>"));
+    assert!(html.contains("Layer violation: src/app.rs (L1) depends on src/util.rs (L3)"));
+    assert!(html.contains("<input id=\"search\""));
+    assert!(!html.contains("http://") && !html.contains("https://") && !html.contains("src=\""));
+}
+
+#[test]
+fn report_command_writes_html_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/app.rs"), "use crate::util;\n").unwrap();
+    fs::write(root.join("src/util.rs"), "").unwrap();
+    fs::write(root.join("SEMMAP.md"), MAP).unwrap();
+
+    let options = ReportOptions {
+        html: root.join("out/report.html"),
+        strict: false,
+        ..Default::default()
+    };
+    assert!(commands::report(&root.join("SEMMAP.md"), root, &options).is_err());
+
+    fs::create_dir(root.join("out")).unwrap();
+    commands::report(&root.join("SEMMAP.md"), root, &options).unwrap();
+    let html = fs::read_to_string(root.join("out/report.html")).unwrap();
+    assert!(html.contains("data-from=\"src/app.rs\" data-to=\"src/util.rs\""));
+}
@@ -0,0 +1,192 @@
+#![allow(clippy::unwrap_used)]
+
+use semmap::commands::{self, DepsOptions};
+use semmap::config;
+use semmap::rules::{self, RuleViolation};
+use semmap::types::{DepEdge, DepKind, DepNode, DependencyMap};
+use std::fs;
+use std::path::Path;
+
+fn node(path: &str, layer: u8) -> DepNode {
+    DepNode {
+        path: path.into(),
+        layer,
+    }
+}
+
+fn edge(from: &str, to: &str) -> DepEdge {
+    DepEdge {
+        from: from.into(),
+        to: to.into(),
+        kind: DepKind::Import,
+        symbols: vec![],
+    }
+}
+
+fn graph() -> DependencyMap {
+    DependencyMap {
+        nodes: vec![
+            node("src/main.rs", 1),
+            node("src/cli.rs", 1),
+            node("src/api/users.rs", 2),
+            node("src/repo/users.rs", 2),
+            node("src/db/pool.rs", 3),
+            node("src/db/query.rs", 3),
+            node("src/config.rs", 0),
+            node("tests/helpers.rs", 4),
+        ],
+        edges: vec![
+            edge("src/main.rs", "src/cli.rs"),
+            edge("src/api/users.rs", "src/cli.rs"),
+            edge("src/api/users.rs", "src/db/pool.rs"),
+            edge("src/repo/users.rs", "src/db/pool.rs"),
+            edge("src/db/query.rs", "src/db/pool.rs"),
+            edge("src/api/users.rs", "src/config.rs"),
+            edge("src/repo/users.rs", "tests/helpers.rs"),
+        ],
+    }
+}
+
+const RULES: &str = r#"
+[[rules]]
+name = "domain-deps"
+message = "Domain code may only use config and utilities"
+layer = 2
+may_depend_on = [0, 3]
+
+[[rules]]
+name = "db-through-repo"
+message = "Database access goes through src/repo"
+to = "src/db/**"
+only_from = ["src/repo/**"]
+
+[[rules]]
+name = "cli-entry"
+to = "src/cli.rs"
+only_from = "src/main.rs"
+
+[[rules]]
+name = "no-test-imports"
+to = "tests/**"
+"#;
+
+fn violation(rule: &str, from: &str, to: &str) -> RuleViolation {
+    RuleViolation {
+        rule: rule.into(),
+        from: from.into(),
+        to: to.into(),
+    }
+}
+
+#[test]
+fn evaluates_layer_and_path_rules() {
+    let rules = rules::parse(RULES).unwrap();
+    let violations = rules::evaluate(&rules, &graph());
+
+    assert_eq!(
+        violations,
+        vec![
+            violation("domain-deps", "src/api/users.rs", "src/cli.rs"),
+            violation("domain-deps", "src/repo/users.rs", "tests/helpers.rs"),
+            violation("db-through-repo", "src/api/users.rs", "src/db/pool.rs"),
+            violation("cli-entry", "src/api/users.rs", "src/cli.rs"),
+            violation("no-test-imports", "src/repo/users.rs", "tests/helpers.rs"),
+        ]
+    );
+}
+
+#[test]
+fn from_glob_narrows_a_forbidden_edge() {
+    let rules = rules::parse(
+        "[[rules]]\nname = \"api-no-config\"\nfrom = \"src/api/**\"\nto = \"src/config.rs\"\n",
+    )
+    .unwrap();
+    let violations = rules::evaluate(&rules, &graph());
+    assert_eq!(
+        violations,
+        vec![violation(
+            "api-no-config",
+            "src/api/users.rs",
+            "src/config.rs"
+        )]
+    );
+}
+
+#[test]
+fn report_groups_by_rule_with_message() {
+    let rules = rules::parse(RULES).unwrap();
+    let violations = rules::evaluate(&rules, &graph());
+    let lines = rules::report(&rules, &violations);
+
+    assert_eq!(
+        lines.first().unwrap(),
+        "Rule domain-deps: Domain code may only use config and utilities"
+    );
+    assert!(lines.contains(&"Rule cli-entry".to_string()));
+    assert!(lines.contains(&"    src/api/users.rs -> src/db/pool.rs".to_string()));
+}
+
+#[test]
+fn rejects_rules_without_a_target() {
+    let err = rules::parse("[[rules]]\nname = \"empty\"\nlayer = 1\n").unwrap_err();
+    assert!(err.contains("empty"));
+}
+
+#[test]
+fn rejects_half_layer_rules_and_duplicate_names() {
+    let err =
+        rules::parse("[[rules]]\nname = \"half\"\nlayer = 2\nto = \"src/db/**\"\n").unwrap_err();
+    assert_eq!(err, "rule 'half' needs both `layer` and `may_depend_on`");
+    let err =
+        rules::parse("[[rules]]\nname = \"half\"\nmay_depend_on = [1]\nto = \"x\"\n").unwrap_err();
+    assert!(err.contains("'half' needs both"), "{err}");
+
+    let err = rules::parse(
+        "[[rules]]\nname = \"twice\"\nto = \"a/**\"\n\n[[rules]]\nname = \"twice\"\nto = \"b/**\"\n",
+    )
+    .unwrap_err();
+    assert_eq!(err, "rule 'twice' is declared more than once");
+}
+
+#[test]
+fn load_reads_configured_rules_or_explicit_path() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert!(rules::load(&[], None).unwrap().is_empty());
+    assert!(rules::load(&[], Some(Path::new("missing.toml"))).is_err());
+
+    let configured: toml::Value = toml::from_str(RULES).unwrap();
+    let configured = configured.get("rules").unwrap().as_array().unwrap();
+    assert_eq!(rules::load(configured, None).unwrap().len(), 4);
+
+    let path = tmp.path().join("rules.toml");
+    fs::write(&path, "[[rules]]\nname = \"x\"\nto = \"tests/**\"\n").unwrap();
+    assert_eq!(rules::load(configured, Some(&path)).unwrap().len(), 1);
+}
+
+#[test]
+fn deps_check_fails_on_rule_violation() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/main.rs"), "use crate::cli;\n").unwrap();
+    fs::write(root.join("src/api.rs"), "use crate::cli;\n").unwrap();
+    fs::write(root.join("src/cli.rs"), "").unwrap();
+    fs::write(
+        root.join("SEMMAP.md"),
+        "# t -- Semantic Map\n\n## Layer 1 -- Core\n\n`src/main.rs`\nEntry.\n\n`src/api.rs`\nApi.\n\n`src/cli.rs`\nCli.\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("semmap.toml"),
+        "[[rules]]\nname = \"cli-entry\"\nto = \"src/cli.rs\"\nonly_from = \"src/main.rs\"\n",
+    )
+    .unwrap();
+
+    let options = DepsOptions {
+        check: true,
+        config: config::discover(root).unwrap().unwrap().config,
+        ..DepsOptions::default()
+    };
+    let err = commands::deps(&root.join("SEMMAP.md"), root, &options).unwrap_err();
+    assert_eq!(err, "1 rule violations");
+}
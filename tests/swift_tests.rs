@@ -0,0 +1,169 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::write;
+use semmap::deps;
+use semmap::doc_extractor;
+use semmap::generator::{self, GeneratorConfig};
+use semmap::lang_swift;
+use semmap::swift_package::{SwiftPackage, TargetKind};
+use std::path::Path;
+
+const MANIFEST: &str = r#"// swift-tools-version:5.9
+import PackageDescription
+
+let package = Package(
+    name: "Weather",
+    dependencies: [
+        .package(url: "https://github.com/apple/swift-argument-parser", from: "1.0.0"),
+    ],
+    targets: [
+        .executableTarget(
+            name: "weather",
+            dependencies: [
+                "WeatherCore",
+                .product(name: "ArgumentParser", package: "swift-argument-parser"),
+            ]
+        ),
+        .target(name: "WeatherCore", dependencies: [.target(name: "Networking")]),
+        .target(name: "Networking", path: "Sources/Net"),
+        .testTarget(name: "WeatherCoreTests", dependencies: ["WeatherCore"]),
+    ]
+)
+"#;
+
+fn project(root: &Path) {
+    write(root, "Package.swift", MANIFEST);
+    write(
+        root,
+        "Sources/weather/main.swift",
+        "import ArgumentParser\nimport WeatherCore\nimport Networking\n",
+    );
+    write(
+        root,
+        "Sources/WeatherCore/Forecast.swift",
+        "import Foundation\nimport Networking\n\n/// Daily forecast for a location.\npublic struct Forecast {\n    public let high: Int\n    func hidden() {}\n}\n",
+    );
+    write(
+        root,
+        "Sources/Net/Client.swift",
+        "open class Client {}\npublic func fetch() {}\n",
+    );
+    write(
+        root,
+        "Tests/WeatherCoreTests/ForecastTests.swift",
+        "@testable import WeatherCore\n",
+    );
+}
+
+#[test]
+fn parses_package_targets() {
+    let package = SwiftPackage::parse(MANIFEST);
+    let summary: Vec<(&str, TargetKind, &str)> = package
+        .targets
+        .iter()
+        .map(|t| (t.name.as_str(), t.kind, t.dir.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("weather", TargetKind::Executable, "Sources/weather"),
+            ("WeatherCore", TargetKind::Library, "Sources/WeatherCore"),
+            ("Networking", TargetKind::Library, "Sources/Net"),
+            (
+                "WeatherCoreTests",
+                TargetKind::Test,
+                "Tests/WeatherCoreTests"
+            ),
+        ]
+    );
+    let exe = package.target_named("weather").unwrap();
+    assert_eq!(exe.dependencies, vec!["WeatherCore"]);
+}
+
+#[test]
+fn extracts_public_and_open_declarations() {
+    let exports = lang_swift::extract_exports(
+        "@MainActor public final class Store {}\nopen class Client {}\nstruct Hidden {}\npublic static func make() {}\ninternal let x = 1\n",
+    );
+    assert_eq!(exports, vec!["Store", "Client", "make"]);
+    assert_eq!(
+        doc_extractor::extract_doc_comment(
+            "//  Forecast.swift\n\n/// Daily forecast.\npublic struct F {}"
+        ),
+        Some("Daily forecast.".into())
+    );
+}
+
+#[test]
+fn generate_layers_by_target_kind() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    project(root);
+
+    let semmap = generator::generate(root, GeneratorConfig::default());
+    let layers = semmap.path_to_layer();
+    assert_eq!(layers.get("Package.swift"), Some(&0));
+    assert_eq!(layers.get("Sources/weather/main.swift"), Some(&1));
+    assert_eq!(layers.get("Sources/Net/Client.swift"), Some(&2));
+    assert_eq!(
+        layers.get("Tests/WeatherCoreTests/ForecastTests.swift"),
+        Some(&4)
+    );
+    let forecast = semmap
+        .find_entry("Sources/WeatherCore/Forecast.swift")
+        .unwrap();
+    assert_eq!(forecast.description.what, "Daily forecast for a location.");
+    assert_eq!(
+        forecast.exports,
+        Some(vec!["Forecast".to_string(), "high".to_string()])
+    );
+}
+
+#[test]
+fn imports_follow_declared_target_dependencies() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    project(root);
+
+    let semmap = generator::generate(root, GeneratorConfig::default());
+    let depmap = deps::analyze(root, &semmap);
+    let mut edges: Vec<(&str, &str)> = depmap
+        .edges
+        .iter()
+        .map(|e| (e.from.as_str(), e.to.as_str()))
+        .collect();
+    edges.sort_unstable();
+
+    // main.swift imports Networking, but `weather` does not declare it.
+    assert_eq!(
+        edges,
+        vec![
+            (
+                "Sources/WeatherCore/Forecast.swift",
+                "Sources/Net/Client.swift"
+            ),
+            (
+                "Sources/weather/main.swift",
+                "Sources/WeatherCore/Forecast.swift"
+            ),
+            (
+                "Tests/WeatherCoreTests/ForecastTests.swift",
+                "Sources/WeatherCore/Forecast.swift"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn swift_frameworks_drive_stereotypes() {
+    use semmap::stereotype::{self, Stereotype};
+    let classify = |c| stereotype::classify("Sources/App/Thing.swift", c);
+    assert_eq!(classify("import ArgumentParser\n"), Stereotype::Cli);
+    assert_eq!(
+        classify("import Foundation\nimport Vapor\n"),
+        Stereotype::Handler
+    );
+    assert_eq!(classify("import CoreData\n"), Stereotype::Repository);
+}
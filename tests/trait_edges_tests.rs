@@ -0,0 +1,186 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use common::{semmap_for, write};
+use semmap::deps;
+use semmap::symbols;
+use semmap::types::{DepKind, DependencyMap};
+
+fn trait_targets(depmap: &DependencyMap, from: &str) -> Vec<String> {
+    depmap
+        .edges
+        .iter()
+        .filter(|e| e.from == from && e.kind == DepKind::Trait)
+        .map(|e| e.to.clone())
+        .collect()
+}
+
+#[test]
+fn rust_impl_for_links_to_trait_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(
+        root,
+        "src/store.rs",
+        "pub trait Store {\n    fn get(&self);\n}\n",
+    );
+    write(
+        root,
+        "src/memory.rs",
+        "use crate::store;\n\npub struct Memory;\n\nimpl store::Store for Memory {\n    fn get(&self) {}\n}\n",
+    );
+    write(
+        root,
+        "src/disk.rs",
+        "pub struct Disk;\n\nimpl<T: Clone> Store for Disk {\n    fn get(&self) {}\n}\n",
+    );
+
+    let semmap = semmap_for(&["src/store.rs", "src/memory.rs", "src/disk.rs"]);
+    let depmap = deps::analyze(root, &semmap);
+
+    assert_eq!(
+        trait_targets(&depmap, "src/memory.rs"),
+        vec!["src/store.rs"]
+    );
+    assert_eq!(trait_targets(&depmap, "src/disk.rs"), vec!["src/store.rs"]);
+    assert!(depmap
+        .edges
+        .iter()
+        .any(|e| e.from == "src/memory.rs" && e.kind == DepKind::Import));
+}
+
+#[test]
+fn library_traits_do_not_link_to_local_namesakes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/error.rs", "pub enum Error {\n    Io,\n}\n");
+    write(root, "src/display.rs", "pub trait Display {}\n");
+    write(root, "src/store.rs", "pub trait Store {}\n");
+    write(
+        root,
+        "src/app.rs",
+        "use std::fmt;\n\nstruct App;\n\nimpl std::error::Error for App {}\nimpl fmt::Display for App {}\nimpl crate::store::Store for App {}\n",
+    );
+
+    let semmap = semmap_for(&[
+        "src/error.rs",
+        "src/display.rs",
+        "src/store.rs",
+        "src/app.rs",
+    ]);
+    let depmap = deps::analyze(root, &semmap);
+
+    assert_eq!(trait_targets(&depmap, "src/app.rs"), vec!["src/store.rs"]);
+}
+
+#[test]
+fn ts_and_java_implements_and_extends() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/repo.ts", "export interface Repo<T> {}\n");
+    write(root, "src/base.ts", "export abstract class Base {}\n");
+    write(
+        root,
+        "src/users.ts",
+        "import { Base } from './base';\nexport class Users extends Base implements Repo<User>, Other {\n}\n",
+    );
+    write(root, "src/Shape.java", "public interface Shape {}\n");
+    write(
+        root,
+        "src/Circle.java",
+        "public final class Circle implements Shape, java.io.Serializable {\n}\n",
+    );
+
+    let semmap = semmap_for(&[
+        "src/repo.ts",
+        "src/base.ts",
+        "src/users.ts",
+        "src/Shape.java",
+        "src/Circle.java",
+    ]);
+    let depmap = deps::analyze(root, &semmap);
+
+    let mut users = trait_targets(&depmap, "src/users.ts");
+    users.sort();
+    assert_eq!(users, vec!["src/base.ts", "src/repo.ts"]);
+    assert_eq!(
+        trait_targets(&depmap, "src/Circle.java"),
+        vec!["src/Shape.java"]
+    );
+}
+
+#[test]
+fn ambiguous_definition_prefers_imported_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/a/handler.ts", "export interface Handler {}\n");
+    write(root, "src/b/handler.ts", "export interface Handler {}\n");
+    write(
+        root,
+        "src/app.ts",
+        "import { Handler } from './b/handler';\nclass App implements Handler {\n}\n",
+    );
+    write(
+        root,
+        "src/other.ts",
+        "class Other implements Handler {\n}\n",
+    );
+
+    let semmap = semmap_for(&[
+        "src/a/handler.ts",
+        "src/b/handler.ts",
+        "src/app.ts",
+        "src/other.ts",
+    ]);
+    let depmap = deps::analyze(root, &semmap);
+
+    assert_eq!(
+        trait_targets(&depmap, "src/app.ts"),
+        vec!["src/b/handler.ts"]
+    );
+    assert!(trait_targets(&depmap, "src/other.ts").is_empty());
+}
+
+#[test]
+fn kotlin_and_swift_supertypes() {
+    assert_eq!(
+        symbols::supertypes(
+            "class Service(private val repo: Repo) : Base(repo), Closeable {\n}\n",
+            "Service.kt"
+        ),
+        vec!["Base", "Closeable"]
+    );
+    assert_eq!(
+        symbols::supertypes(
+            "struct Forecast: Codable, Equatable {\n}\n",
+            "Forecast.swift"
+        ),
+        vec!["Codable", "Equatable"]
+    );
+    let defs = symbols::type_definitions("public protocol Fetching {}\n", "Fetching.swift");
+    assert_eq!(
+        defs,
+        vec![symbols::TypeDef {
+            name: "Fetching".into(),
+            is_abstract: true,
+        }]
+    );
+}
+
+#[test]
+fn trait_edges_render_dotted_in_mermaid() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    write(root, "src/store.rs", "pub trait Store {}\n");
+    write(
+        root,
+        "src/memory.rs",
+        "struct Memory;\nimpl Store for Memory {}\n",
+    );
+
+    let semmap = semmap_for(&["src/store.rs", "src/memory.rs"]);
+    let mermaid = deps::render_mermaid(&deps::analyze(root, &semmap));
+
+    assert!(mermaid.contains("src_memory_rs -.->|\"Store\"| src_store_rs"));
+}
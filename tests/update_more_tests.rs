@@ -1,4 +1,5 @@
 use semmap::commands;
+use semmap::config::Config;
 use semmap::parser;
 use std::fs;
 use tempfile::TempDir;
@@ -24,10 +25,10 @@ fn test_update_is_idempotent() -> TestResult {
     fs::write(root.join("Cargo.toml"), "")?;
     fs::write(root.join("lib.rs"), "")?;
 
-    commands::update(&root.join("SEMMAP.md"), root)?;
+    commands::update(&root.join("SEMMAP.md"), root, &Config::default())?;
     let first_output = fs::read_to_string(root.join("SEMMAP.md"))?;
 
-    commands::update(&root.join("SEMMAP.md"), root)?;
+    commands::update(&root.join("SEMMAP.md"), root, &Config::default())?;
     let second_output = fs::read_to_string(root.join("SEMMAP.md"))?;
 
     assert_eq!(first_output, second_output,
@@ -52,7 +53,7 @@ fn test_update_preserves_existing_descriptions() -> TestResult {
     fs::write(root.join("keep.rs"), "")?;
     fs::write(root.join("new.rs"), "")?;
 
-    commands::update(&root.join("SEMMAP.md"), root)?;
+    commands::update(&root.join("SEMMAP.md"), root, &Config::default())?;
     let semmap = parse_semmap(root)?;
     let keep_entry = semmap.find_entry("keep.rs").ok_or("keep.rs missing")?;
 
@@ -79,7 +80,7 @@ fn test_update_creates_missing_layers_for_new_entries() -> TestResult {
     fs::write(root.join("utils.rs"), "")?;
     fs::write(root.join("test.rs"), "#[test] fn t() {}")?;
 
-    commands::update(&root.join("SEMMAP.md"), root)?;
+    commands::update(&root.join("SEMMAP.md"), root, &Config::default())?;
     let semmap = parse_semmap(root)?;
 
     let layer_numbers: Vec<_> = semmap.layers.iter().map(|l| l.number).collect();
@@ -1,4 +1,5 @@
 use semmap::commands;
+use semmap::config::Config;
 use semmap::parser;
 use std::fs;
 use tempfile::TempDir;
@@ -24,7 +25,7 @@ fn test_update_adds_new_file_to_correct_layer() -> TestResult {
     fs::write(root.join("Cargo.toml"), "")?;
     fs::write(root.join("logic.rs"), "pub fn compute() {}")?;
 
-    commands::update(&root.join("SEMMAP.md"), root)?;
+    commands::update(&root.join("SEMMAP.md"), root, &Config::default())?;
     let semmap = parse_semmap(root)?;
 
     let layer2 = semmap.layers.iter().find(|l| l.number == 2)
@@ -51,7 +52,7 @@ fn test_update_with_root_prefix_handles_paths_correctly() -> TestResult {
     fs::write(root.join("Cargo.toml"), "")?;
     fs::write(root.join("main.rs"), "fn main() {}")?;
 
-    commands::update(&temp.path().join("SEMMAP.md"), &root)?;
+    commands::update(&temp.path().join("SEMMAP.md"), &root, &Config::default())?;
     let semmap = parse_semmap(temp.path())?;
 
     let all_paths = semmap.all_paths();
@@ -73,7 +74,7 @@ fn test_update_removes_deleted_files() -> TestResult {
     create_semmap(root, "# Test -- Semantic Map\nPurpose: Test.\n\n## Layer 0 -- Config\n`exists.toml`\nExists. Yes.\n\n## Layer 2 -- Domain\n`deleted.rs`\nGone. Deleted.\n")?;
     fs::write(root.join("exists.toml"), "")?;
 
-    commands::update(&root.join("SEMMAP.md"), root)?;
+    commands::update(&root.join("SEMMAP.md"), root, &Config::default())?;
     let semmap = parse_semmap(root)?;
 
     assert!(semmap.find_entry("exists.toml").is_some(),
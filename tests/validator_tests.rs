@@ -1,5 +1,5 @@
 use semmap::error::Severity;
-use semmap::types::{Description, FileEntry, Layer, SemmapFile};
+use semmap::types::{FileEntry, Layer, SemmapFile};
 use semmap::validator::validate;
 use std::error::Error;
 use tempfile::TempDir;
@@ -7,15 +7,7 @@ use tempfile::TempDir;
 type TestResult = Result<(), Box<dyn Error>>;
 
 fn entry(path: &str, what: &str, why: &str) -> FileEntry {
-    FileEntry {
-        path: path.to_string(),
-        description: Description {
-            what: what.to_string(),
-            why: why.to_string(),
-        },
-        exports: None,
-        touch: None,
-    }
+    FileEntry::new(path.to_string(), what.to_string(), why.to_string())
 }
 
 fn layer(number: u8, name: &str, entries: Vec<FileEntry>) -> Layer {